@@ -0,0 +1,105 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use editorconfiger::{glob, similar, ValidationFormatter, ValidationResult};
+
+const LANGUAGES: &[&str] = &[
+    "cs", "fs", "vb", "rs", "go", "py", "js", "ts", "jsx", "tsx", "c", "h", "cpp", "hpp", "java",
+    "kt", "swift", "rb", "php", "lua", "md", "yml", "yaml", "json", "xml", "toml", "sh", "ps1",
+];
+
+const PROPERTIES: &[&str] = &[
+    "indent_style",
+    "indent_size",
+    "tab_width",
+    "end_of_line",
+    "charset",
+    "trim_trailing_whitespace",
+    "insert_final_newline",
+    "max_line_length",
+];
+
+struct Formatter;
+
+impl ValidationFormatter for Formatter {
+    fn format(&self, result: ValidationResult) {
+        black_box(result);
+    }
+}
+
+/// Builds .editorconfig similar to big real world ones (like dotnet/runtime one):
+/// one section per language with standard properties and a bunch of vendor specific keys
+fn large_config() -> String {
+    let mut content = String::from("root = true\n\n[*]\nindent_style = space\nindent_size = 4\n");
+    for (i, lang) in LANGUAGES.iter().enumerate() {
+        content.push_str(&format!("\n# {lang} files\n[*.{lang}]\n"));
+        for (j, property) in PROPERTIES.iter().enumerate() {
+            content.push_str(&format!("{property} = {}\n", (i + j) % 8 + 1));
+        }
+        for j in 0..30 {
+            content.push_str(&format!(
+                "dotnet_diagnostic.{lang}{j:04}.severity = warning\n"
+            ));
+        }
+    }
+    content.push_str("\n[src/**/*.{cs,fs,vb}]\nindent_size = 4\n");
+    content.push_str("\n[*.{json,yml,yaml}]\nindent_size = 2\n");
+    content
+}
+
+/// Builds hundreds of keys where some pairs differ only by prefix so they're similar
+fn many_keys() -> Vec<String> {
+    (0..300)
+        .map(|i| match i % 3 {
+            0 => format!("key_{i}"),
+            1 => format!("x_key_{}", i - 1),
+            _ => format!("{}_property_{i}", PROPERTIES[i % PROPERTIES.len()]),
+        })
+        .collect()
+}
+
+fn bench_validate(c: &mut Criterion) {
+    let content = large_config();
+    let mut group = c.benchmark_group("validate");
+    group.bench_function("large", |b| {
+        b.iter(|| editorconfiger::validate(black_box(&content), "", &Formatter));
+    });
+    group.bench_function("small", |b| {
+        b.iter(|| {
+            editorconfiger::validate(
+                black_box("root = true\n[*]\nindent_size = 4\n[*.md]\nindent_size = 2\n"),
+                "",
+                &Formatter,
+            );
+        });
+    });
+    group.finish();
+}
+
+fn bench_glob_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("glob::parse");
+    let titles = [
+        "*.{cs,fs,vb}",
+        "src/**/*.{cs,fs,vb,csproj,fsproj,vbproj}",
+        "{a,{b,{c,{d,e}}}}/*.c",
+        "{src/{core,ui,web}/*.js,lib/{a,b,c}/*.ts,tests/**/*.{json,yml}}",
+        "**/*.[a-z]",
+    ];
+    for title in titles {
+        group.bench_with_input(BenchmarkId::from_parameter(title), title, |b, t| {
+            b.iter(|| glob::parse(black_box(t)));
+        });
+    }
+    group.finish();
+}
+
+fn bench_similar(c: &mut Criterion) {
+    let keys = many_keys();
+    let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+    let mut group = c.benchmark_group("similar");
+    group.bench_function("find_suffix_pairs", |b| {
+        b.iter(|| similar::find_suffix_pairs(black_box(&keys), similar::DEFAULT_MIN_SUFFIX_LEN));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_validate, bench_glob_parse, bench_similar);
+criterion_main!(benches);
@@ -3,11 +3,17 @@
 use std::io;
 
 use clap::{
-    arg, command, crate_authors, crate_description, crate_name, crate_version, value_parser,
-    ArgAction, ArgMatches, Command,
+    arg, command, crate_authors, crate_description, crate_name, crate_version, value_parser, Arg,
+    ArgAction, ArgMatches, Command, ValueEnum,
 };
 use clap_complete::{generate, Shell};
-use editorconfiger::console::{Comparator, Error, Formatter};
+use editorconfiger::console::{
+    self, Comparator, CsvComparator, Error, Formatter, MarkdownComparator, MietteFormatter,
+    YamlFormatter,
+};
+use editorconfiger::{
+    CompareConfig, Errorer, ValidationConfig, ValidationFormatter, ValidationState, WalkOptions,
+};
 
 #[cfg(target_os = "linux")]
 use mimalloc::MiMalloc;
@@ -20,6 +26,72 @@ const PATH: &str = "PATH";
 const FILE1: &str = "FILE1";
 const FILE2: &str = "FILE2";
 const PROBLEMS: &str = "problems";
+const FORMAT: &str = "format";
+const NAME: &str = "name";
+const IGNORE_SECTION: &str = "ignore-section";
+const REQUIRE_ROOT: &str = "require-root";
+const NESTED_ROOT: &str = "nested-root";
+const TIMINGS: &str = "timings";
+const REPORT: &str = "report";
+/// How many slowest files to print along with timings
+const SLOWEST_FILES: usize = 10;
+const CONTENT: &str = "content";
+const LIST: &str = "list";
+const MAX_DEPTH: &str = "max-depth";
+const RELATIVE: &str = "relative";
+const NORMALIZE_WHITESPACE: &str = "normalize-whitespace";
+const STRICT: &str = "strict";
+const WITH: &str = "with";
+const FAIL_ON: &str = "fail-on";
+const QUIET: &str = "quiet";
+const IGNORE_KEY: &str = "ignore-key";
+const WRITE: &str = "write";
+const STATS: &str = "stats";
+const INLINE: &str = "<inline>";
+const COLOR: &str = "color";
+const AUTO: &str = "auto";
+const ALWAYS: &str = "always";
+const NEVER: &str = "never";
+
+/// Validation results output format selected by `--format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Plain text
+    Human,
+    /// YAML document per file
+    Yaml,
+    /// Diagnostics with source snippets
+    Pretty,
+}
+
+/// Comparison results output format selected by `--format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CompareFormat {
+    /// Plain text table
+    Human,
+    /// Markdown table
+    Markdown,
+    /// CSV records only
+    Csv,
+}
+
+/// Validation state threshold selected by `--fail-on`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum FailOn {
+    /// Fail only on invalid files
+    Invalid,
+    /// Fail on files with any problem
+    Problems,
+    /// Never fail
+    Never,
+}
+
+/// Directory level aggregation selected by `--report`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReportKind {
+    /// How many times each property value is set
+    Frequency,
+}
 
 fn main() {
     let app = build_cli();
@@ -29,36 +101,322 @@ fn main() {
         Some(("c", cmd)) => compare(cmd),
         Some(("vf", cmd)) => validate_file(cmd),
         Some(("vd", cmd)) => validate_folder(cmd),
+        Some(("fmt", cmd)) => format_file(cmd),
         Some(("completion", cmd)) => print_completions(cmd),
         _ => {}
     }
 }
 
 fn validate_file(cmd: &ArgMatches) {
-    let path = cmd.get_one::<String>(PATH).unwrap();
-    let formatter = Formatter::new(false);
-    let err = Error {};
-    editorconfiger::validate_one(path, &formatter, &err);
+    let config = validation_config(cmd);
+    let state = match cmd.get_one::<OutputFormat>(FORMAT).unwrap() {
+        OutputFormat::Yaml => {
+            let formatter = YamlFormatter::new(false);
+            validate_file_with(cmd, &config, &formatter)
+        }
+        OutputFormat::Pretty => {
+            let formatter = MietteFormatter::new(false).with_color(use_color(cmd));
+            validate_file_with(cmd, &config, &formatter)
+        }
+        OutputFormat::Human => {
+            let formatter = Formatter::new(false).with_color(use_color(cmd));
+            validate_file_with(cmd, &config, &formatter)
+        }
+    };
+    exit_on_failure(cmd, state);
+}
+
+fn validate_file_with<V: ValidationFormatter>(
+    cmd: &ArgMatches,
+    config: &ValidationConfig,
+    formatter: &V,
+) -> ValidationState {
+    if let Some(content) = cmd.get_one::<String>(CONTENT) {
+        editorconfiger::validate_with(&unescape(content), INLINE, config, formatter)
+    } else {
+        let err = Error::new().with_color(use_color(cmd));
+        cmd.get_many::<String>(PATH)
+            .unwrap()
+            .map(|path| {
+                // File that cannot be read is considered invalid
+                editorconfiger::validate_one_with(path, config, formatter, &err)
+                    .unwrap_or(ValidationState::Invalid)
+            })
+            .fold(ValidationState::Valid, ValidationState::worst)
+    }
+}
+
+/// Exits with non zero code if the state reaches the threshold selected by `--fail-on`
+fn exit_on_failure(cmd: &ArgMatches, state: ValidationState) {
+    let fail = match cmd.get_one::<FailOn>(FAIL_ON).unwrap() {
+        FailOn::Invalid => state == ValidationState::Invalid,
+        FailOn::Problems => !state.is_ok(),
+        FailOn::Never => false,
+    };
+    if fail {
+        std::process::exit(1);
+    }
+}
+
+/// Interprets `\n`, `\r`, `\t` and `\\` escape sequences so that multiline content
+/// can be passed as a single command line argument
+fn unescape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some('\\') => result.push('\\'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
 }
 
 fn validate_folder(cmd: &ArgMatches) {
     let path = cmd.get_one::<String>(PATH).unwrap();
     let only_problems = cmd.get_flag(PROBLEMS);
-    let formatter = Formatter::new(only_problems);
-    let err = Error {};
-    let results = editorconfiger::validate_all(path, &formatter, &err);
+    let mut options = WalkOptions::new();
+    if let Some(names) = cmd.get_many::<String>(NAME) {
+        options = options.names(names.cloned().collect());
+    }
+    if let Some(depth) = cmd.get_one::<usize>(MAX_DEPTH) {
+        options = options.max_depth(*depth);
+    }
+    let relative = cmd.get_flag(RELATIVE);
+    options = options
+        .relative_paths(relative)
+        .missing_root(cmd.get_flag(REQUIRE_ROOT))
+        .nested_root(cmd.get_flag(NESTED_ROOT))
+        .timings(cmd.get_flag(TIMINGS))
+        .property_frequency(matches!(
+            cmd.get_one::<ReportKind>(REPORT),
+            Some(ReportKind::Frequency)
+        ));
+    if cmd.get_flag(LIST) {
+        let files = editorconfiger::list_config_files(path, &options);
+        for file in &files {
+            let shown = if relative {
+                file.strip_prefix(path).unwrap_or(file)
+            } else {
+                file
+            };
+            println!(" {}", shown.display());
+        }
+        println!();
+        println!("  Total .editorconfig files found: {}", files.len());
+        return;
+    }
+    let config = validation_config(cmd);
+    let err = Error::new().with_color(use_color(cmd));
+    let format = *cmd.get_one::<OutputFormat>(FORMAT).unwrap();
+    let report = match format {
+        OutputFormat::Yaml => {
+            let formatter = YamlFormatter::new(only_problems);
+            editorconfiger::validate_all_with(path, &options, &config, &formatter, &err)
+        }
+        OutputFormat::Pretty => {
+            let formatter = MietteFormatter::new(only_problems).with_color(use_color(cmd));
+            editorconfiger::validate_all_with(path, &options, &config, &formatter, &err)
+        }
+        OutputFormat::Human => {
+            let formatter = Formatter::new(only_problems).with_color(use_color(cmd));
+            editorconfiger::validate_all_with(path, &options, &config, &formatter, &err)
+        }
+    };
+    if format != OutputFormat::Yaml {
+        if !report.missing_root.is_empty() {
+            println!();
+            println!("  Top-most .editorconfig files without root = true:");
+            for file in &report.missing_root {
+                println!("    {file}");
+            }
+        }
+        if !report.nested_root.is_empty() {
+            println!();
+            println!("  Nested .editorconfig files with root = true:");
+            for file in &report.nested_root {
+                println!("    {file}");
+            }
+        }
+        println!();
+        let counts = report.counts();
+        println!("  Total .editorconfig files found: {}", counts.found);
+        println!("  Validated: {}", counts.validated);
+        println!("  Could not be read: {}", counts.errored);
+        if let Some(timings) = &report.timings {
+            println!();
+            println!("  Directory walk took: {:?}", timings.walk);
+            println!("  Validation took: {:?}", timings.validation);
+            println!("  Slowest files:");
+            for (file, duration) in timings.files.iter().take(SLOWEST_FILES) {
+                println!("    {duration:?} {file}");
+            }
+        }
+        if !report.property_frequency.is_empty() {
+            print_frequency(&report.property_frequency);
+        }
+    }
+    let state = if report.errors > 0 {
+        ValidationState::Invalid
+    } else {
+        report.worst_state.unwrap_or(ValidationState::Valid)
+    };
+    exit_on_failure(cmd, state);
+}
+
+fn print_frequency(frequency: &[(String, String, usize)]) {
+    let width = frequency
+        .iter()
+        .map(|(_, _, count)| count.to_string().len())
+        .max()
+        .unwrap_or_default();
     println!();
-    println!("  Total .editorconfig files found: {results}");
+    println!("  Property frequency:");
+    for (name, value, count) in frequency {
+        println!("    {count:>width$}  {name} = {value}");
+    }
+}
+
+fn format_file(cmd: &ArgMatches) {
+    let path = cmd.get_one::<String>(PATH).unwrap();
+    let err = Error::new().with_color(use_color(cmd));
+    let canonical = editorconfiger::read_config(path)
+        .and_then(|content| editorconfiger::canonicalize(&content));
+    let written = match canonical {
+        Ok(canonical) if cmd.get_flag(WRITE) => std::fs::write(path, canonical),
+        Ok(canonical) => {
+            print!("{canonical}");
+            Ok(())
+        }
+        Err(e) => {
+            err.error(path, &e.to_string());
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = written {
+        err.error(path, &e.to_string());
+        std::process::exit(1);
+    }
 }
 
 fn compare(cmd: &ArgMatches) {
     let path1 = cmd.get_one::<String>(FILE1).unwrap();
+    let err = Error::new().with_color(use_color(cmd));
+    let format = *cmd.get_one::<CompareFormat>(FORMAT).unwrap();
+    if let Some(with) = cmd.get_many::<String>(WITH) {
+        let paths: Vec<&str> = std::iter::once(path1)
+            .chain(cmd.get_one::<String>(FILE2))
+            .chain(with)
+            .map(String::as_str)
+            .collect();
+        // CSV and Markdown output must contain nothing but the table
+        if format == CompareFormat::Human {
+            for (i, path) in paths.iter().enumerate() {
+                println!(" FILE #{}: {path}", i + 1);
+            }
+        }
+        match format {
+            CompareFormat::Markdown => {
+                editorconfiger::compare_many(&paths, &err, &MarkdownComparator::new());
+            }
+            CompareFormat::Csv => editorconfiger::compare_many(&paths, &err, &CsvComparator::new()),
+            CompareFormat::Human => {
+                let cmp = Comparator::new().with_color(use_color(cmd));
+                editorconfiger::compare_many(&paths, &err, &cmp);
+            }
+        }
+        return;
+    }
     let path2 = cmd.get_one::<String>(FILE2).unwrap();
-    let err = Error {};
-    println!(" FILE #1: {path1}");
-    println!(" FILE #2: {path2}");
-    let cmp = Comparator {};
-    editorconfiger::compare_files(path1, path2, &err, &cmp);
+    let ignore_keys = cmd
+        .get_many::<String>(IGNORE_KEY)
+        .map(|keys| keys.cloned().collect())
+        .unwrap_or_default();
+    let config = CompareConfig::new()
+        .normalize_whitespace(cmd.get_flag(NORMALIZE_WHITESPACE))
+        .ignore_keys(ignore_keys);
+    if cmd.get_flag(QUIET) {
+        let code = match (
+            editorconfiger::read_config(path1),
+            editorconfiger::read_config(path2),
+        ) {
+            (Ok(c1), Ok(c2)) => i32::from(!editorconfiger::configs_equal_with(&c1, &c2, &config)),
+            _ => 2,
+        };
+        std::process::exit(code);
+    }
+    // CSV and Markdown output must contain nothing but the table
+    if format == CompareFormat::Human {
+        println!(" FILE #1: {path1}");
+        println!(" FILE #2: {path2}");
+    }
+    let read = |path: &str| {
+        editorconfiger::read_config(path)
+            .map_err(|e| err.error(path, &e.to_string()))
+            .ok()
+    };
+    let Some(c1) = read(path1) else { return };
+    let Some(c2) = read(path2) else { return };
+    let labels = [path1.as_str(), path2.as_str()];
+    match format {
+        CompareFormat::Markdown => {
+            let cmp = MarkdownComparator::new();
+            editorconfiger::compare_labeled_with(&c1, &c2, labels, &config, &cmp);
+        }
+        CompareFormat::Csv => {
+            let cmp = CsvComparator::new();
+            editorconfiger::compare_labeled_with(&c1, &c2, labels, &config, &cmp);
+        }
+        CompareFormat::Human => {
+            let cmp = Comparator::new().with_color(use_color(cmd));
+            editorconfiger::compare_labeled_with(&c1, &c2, labels, &config, &cmp);
+        }
+    }
+    // CSV output must contain nothing but records
+    if cmd.get_flag(STATS) && format != CompareFormat::Csv {
+        let stats = editorconfiger::diff_stats_with(&c1, &c2, &config);
+        let [first, second] = console::column_titles(labels);
+        println!("  Sections only in {first}: {}", stats.sections_only_first);
+        println!(
+            "  Sections only in {second}: {}",
+            stats.sections_only_second
+        );
+        println!(
+            "  Properties with different values: {}",
+            stats.props_differing
+        );
+        println!("  Properties only in {first}: {}", stats.props_only_first);
+        println!("  Properties only in {second}: {}", stats.props_only_second);
+    }
+}
+
+fn validation_config(cmd: &ArgMatches) -> ValidationConfig {
+    let ignore_sections = cmd
+        .get_many::<String>(IGNORE_SECTION)
+        .map(|patterns| patterns.cloned().collect())
+        .unwrap_or_default();
+    ValidationConfig::new()
+        .ignore_sections(ignore_sections)
+        .strict(cmd.get_flag(STRICT))
+}
+
+fn use_color(cmd: &ArgMatches) -> bool {
+    match cmd.get_one::<String>(COLOR).map(String::as_str) {
+        Some(ALWAYS) => true,
+        Some(NEVER) => false,
+        _ => console::stdout_is_terminal(),
+    }
 }
 
 fn print_completions(matches: &ArgMatches) {
@@ -75,16 +433,36 @@ fn build_cli() -> Command {
         .version(crate_version!())
         .author(crate_authors!("\n"))
         .about(crate_description!())
+        .arg(
+            arg!(--color <WHEN>)
+                .required(false)
+                .global(true)
+                .value_parser([AUTO, ALWAYS, NEVER])
+                .default_value(AUTO)
+                .help("Coloring of the output"),
+        )
         .subcommand(
             Command::new("vf")
                 .aliases(["validate-file"])
-                .about("Validate single .editorconfig file")
+                .about("Validate one or more .editorconfig files")
                 .arg(
                     arg!([PATH])
-                        .help("Path to .editorconfig file")
-                        .required(true)
+                        .help("Paths to .editorconfig files")
+                        .required_unless_present(CONTENT)
+                        .action(ArgAction::Append)
+                        .num_args(1..)
                         .index(1),
-                ),
+                )
+                .arg(
+                    arg!(--content <CONTENT>)
+                        .required(false)
+                        .conflicts_with(PATH)
+                        .help("Validate the string passed instead of a file. \\n, \\r, \\t and \\\\ escape sequences are interpreted"),
+                )
+                .arg(format_arg())
+                .arg(ignore_section_arg())
+                .arg(strict_arg())
+                .arg(fail_on_arg()),
         )
         .subcommand(
             Command::new("vd")
@@ -100,12 +478,59 @@ fn build_cli() -> Command {
                     arg!(-p - -problems).action(ArgAction::SetTrue).help(
                         "Show only files that have problems. Correct files will not be shown.",
                     ),
-                ),
+                )
+                .arg(
+                    arg!(-n --name <NAME>)
+                        .required(false)
+                        .action(ArgAction::Append)
+                        .help("File name pattern to validate. * and ? wildcards are supported. Can be specified multiple times. .editorconfig by default"),
+                )
+                .arg(
+                    arg!(--"max-depth" <N>)
+                        .required(false)
+                        .value_parser(value_parser!(usize))
+                        .help("Maximum directory depth to search: 0 means only the given directory, 1 also its immediate subdirectories and so on. Unlimited by default"),
+                )
+                .arg(
+                    arg!(--relative)
+                        .action(ArgAction::SetTrue)
+                        .help("Show paths relative to the directory being validated"),
+                )
+                .arg(
+                    arg!(-l --list)
+                        .action(ArgAction::SetTrue)
+                        .help("Only list files that would be validated without validating them"),
+                )
+                .arg(
+                    arg!(--"require-root")
+                        .action(ArgAction::SetTrue)
+                        .help("Report top-most .editorconfig files that lack root = true"),
+                )
+                .arg(
+                    arg!(--timings)
+                        .action(ArgAction::SetTrue)
+                        .help("Print time spent walking directory tree and validating files along with the slowest files"),
+                )
+                .arg(
+                    arg!(--report <REPORT>)
+                        .required(false)
+                        .value_parser(value_parser!(ReportKind))
+                        .help("Print directory level aggregation after validation results"),
+                )
+                .arg(
+                    arg!(--"nested-root")
+                        .action(ArgAction::SetTrue)
+                        .help("Report .editorconfig files with root = true nested into directory whose .editorconfig has root = true too"),
+                )
+                .arg(format_arg())
+                .arg(ignore_section_arg())
+                .arg(strict_arg())
+                .arg(fail_on_arg()),
         )
         .subcommand(
             Command::new("c")
                 .aliases(["compare"])
-                .about("Compare two .editorconfig files")
+                .about("Compare two or more .editorconfig files")
                 .arg(
                     arg!([FILE1])
                         .help("Path to the first .editorconfig file")
@@ -115,8 +540,61 @@ fn build_cli() -> Command {
                 .arg(
                     arg!([FILE2])
                         .help("Path to the second .editorconfig file")
-                        .required(true)
+                        .required_unless_present(WITH)
                         .index(2),
+                )
+                .arg(
+                    arg!(--with <FILE>)
+                        .required(false)
+                        .action(ArgAction::Append)
+                        .conflicts_with_all([NORMALIZE_WHITESPACE, IGNORE_KEY, STATS])
+                        .help("Path to one more .editorconfig file to compare. Can be specified multiple times"),
+                )
+                .arg(
+                    arg!(-f --format <FORMAT>)
+                        .required(false)
+                        .value_parser(value_parser!(CompareFormat))
+                        .default_value("human")
+                        .help("Comparison results output format"),
+                )
+                .arg(
+                    arg!(-w --"normalize-whitespace")
+                        .action(ArgAction::SetTrue)
+                        .help("Consider values that differ only in internal whitespace equal"),
+                )
+                .arg(
+                    arg!(--"ignore-key" <KEY>)
+                        .required(false)
+                        .action(ArgAction::Append)
+                        .help("Property key to leave out of comparison. Trailing * matches any key that starts with the rest, like ij_*. Can be specified multiple times"),
+                )
+                .arg(
+                    arg!(-q --quiet)
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with(WITH)
+                        .help("Print nothing and exit with 0 if files are equal, 1 if they differ and 2 if some file cannot be read"),
+                )
+                .arg(
+                    arg!(--stats)
+                        .action(ArgAction::SetTrue)
+                        .conflicts_with(QUIET)
+                        .help("Print numbers of differing sections and properties after comparison results"),
+                ),
+        )
+        .subcommand(
+            Command::new("fmt")
+                .aliases(["format"])
+                .about("Print .editorconfig file with duplicate sections merged and properties sorted")
+                .arg(
+                    arg!([PATH])
+                        .help("Path to .editorconfig file")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    arg!(-w --write)
+                        .action(ArgAction::SetTrue)
+                        .help("Write the result back to the file instead of printing it"),
                 ),
         )
         .subcommand(
@@ -130,3 +608,89 @@ fn build_cli() -> Command {
                 ),
         )
 }
+
+fn format_arg() -> Arg {
+    arg!(-f --format <FORMAT>)
+        .required(false)
+        .value_parser(value_parser!(OutputFormat))
+        .default_value("human")
+        .help("Validation results output format")
+}
+
+fn ignore_section_arg() -> Arg {
+    arg!(--"ignore-section" <PATTERN>)
+        .required(false)
+        .action(ArgAction::Append)
+        .help("Section title pattern to skip during validation. Can be specified multiple times")
+}
+
+fn fail_on_arg() -> Arg {
+    arg!(--"fail-on" <WHEN>)
+        .required(false)
+        .value_parser(value_parser!(FailOn))
+        .default_value("invalid")
+        .help("Exit with non zero code when files are invalid, have any problems or never")
+}
+
+fn strict_arg() -> Arg {
+    arg!(--strict)
+        .action(ArgAction::SetTrue)
+        .help("Treat files that have any problem as invalid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn assert_parses_all_variants<E: ValueEnum + PartialEq + std::fmt::Debug>() {
+        for variant in E::value_variants() {
+            // Arrange
+            let value = variant.to_possible_value().unwrap();
+
+            // Act
+            let actual = E::from_str(value.get_name(), false);
+
+            // Assert
+            assert_eq!(actual.as_ref(), Ok(variant));
+        }
+    }
+
+    #[test]
+    fn value_enums_parse_all_variants() {
+        assert_parses_all_variants::<OutputFormat>();
+        assert_parses_all_variants::<CompareFormat>();
+        assert_parses_all_variants::<FailOn>();
+        assert_parses_all_variants::<ReportKind>();
+    }
+
+    #[test]
+    fn value_enums_reject_unknown() {
+        assert!(OutputFormat::from_str("json", false).is_err());
+        assert!(CompareFormat::from_str("yaml", false).is_err());
+        assert!(FailOn::from_str("always", false).is_err());
+    }
+
+    #[test]
+    fn build_cli_is_consistent() {
+        build_cli().debug_assert();
+    }
+
+    #[rstest]
+    #[case("[*]\\na = b", "[*]\na = b")]
+    #[case("a\\r\\nb\\tc", "a\r\nb\tc")]
+    #[case("a\\\\nb", "a\\nb")]
+    #[case("a\\xb", "a\\xb")]
+    #[case("a\\", "a\\")]
+    #[case("plain", "plain")]
+    #[trace]
+    fn unescape_tests(#[case] input: &str, #[case] expected: &str) {
+        // Arrange
+
+        // Act
+        let actual = unescape(input);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+}
@@ -1,145 +1,1210 @@
 use crate::{
-    CompareItem, ComparisonFormatter, Errorer, ValidationFormatter, ValidationResult,
-    ValidationState,
+    tokens, CompareItem, ComparisonFormatter, Errorer, ExtDuplicate, NCompareFormatter,
+    NCompareItem, Token, ValidationFormatter, ValidationResult, ValidationState,
 };
 use ansi_term::Colour::{Green, Red, Yellow};
+use ansi_term::{Colour, Style};
+use miette::{
+    GraphicalReportHandler, GraphicalTheme, LabeledSpan, MietteDiagnostic, NamedSource, Severity,
+};
 use prettytable::format::TableFormat;
 use prettytable::{cell, format, row, Cell, Row, Table};
+use serde::Serialize;
+use std::cell::RefCell;
 use std::collections::BTreeMap;
+use std::io::{self, IsTerminal, Stdout, Write};
+use std::path::Path;
 
-pub struct Formatter {
+pub struct Formatter<W: Write = Stdout> {
     only_problems: bool,
+    color: bool,
+    indent: usize,
+    writer: RefCell<W>,
 }
 
 impl Formatter {
+    /// Creates formatter that writes to stdout and colors output only if stdout is a terminal
     #[must_use]
     pub fn new(only_problems: bool) -> Self {
-        Self { only_problems }
+        Self {
+            only_problems,
+            color: stdout_is_terminal(),
+            indent: 0,
+            writer: RefCell::new(io::stdout()),
+        }
     }
 }
 
-impl ValidationFormatter for Formatter {
+impl<W: Write> Formatter<W> {
+    /// Creates formatter that writes to the writer specified without colors
+    /// unless they're enabled by [`Formatter::with_color`]
+    #[must_use]
+    pub fn with_writer(only_problems: bool, writer: W) -> Self {
+        Self {
+            only_problems,
+            color: false,
+            indent: 0,
+            writer: RefCell::new(writer),
+        }
+    }
+
+    /// Enables or disables colored output regardless of stdout kind
+    #[must_use]
+    pub fn with_color(mut self, enabled: bool) -> Self {
+        self.color = enabled;
+        self
+    }
+
+    /// Shifts all output lines right by `indent` spaces
+    #[must_use]
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Returns the writer back so as written output can be used
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+}
+
+impl<W: Write> ValidationFormatter for Formatter<W> {
     fn format(&self, result: ValidationResult) {
+        // Any failure to write is ignored the same way as tables printing does
+        let _ = self.write(&mut *self.writer.borrow_mut(), result);
+    }
+}
+
+impl<W: Write> Formatter<W> {
+    fn write(&self, out: &mut W, result: ValidationResult) -> io::Result<()> {
+        let pad = " ".repeat(self.indent);
         let state = result.state();
         let msg = match state {
-            ValidationState::Valid => Green.paint("valid"),
-            ValidationState::Invalid => Red.paint("invalid"),
-            ValidationState::SomeProblems => Yellow.paint("has some problems"),
+            ValidationState::Valid => paint(self.color, Green, "valid"),
+            ValidationState::Invalid => paint(self.color, Red, "invalid"),
+            ValidationState::SomeProblems => paint(self.color, Yellow, "has some problems"),
         };
 
         if !self.only_problems || !state.is_ok() {
-            println!(" {} {}", result.path, msg);
+            writeln!(out, "{pad} {} {}", result.path, msg)?;
         }
         if state.is_ok() {
-            return;
+            return Ok(());
         }
 
         if !result.duplicate_sections.is_empty() {
-            println!("   Duplicate sections:");
+            writeln!(out, "{pad}   Duplicate sections:")?;
             for section in result.duplicate_sections {
-                println!("     {section}");
+                writeln!(out, "{pad}     {section}")?;
             }
         }
         if !result.duplicate_properties.is_empty() {
-            println!("   Duplicate properties:");
+            writeln!(out, "{pad}   Duplicate properties:")?;
             for (section, duplicates) in result.duplicate_properties {
-                println!("     [{section}]:");
+                writeln!(out, "{pad}     [{section}]:")?;
                 for property in duplicates {
-                    println!("       {property}");
+                    writeln!(out, "{pad}       {property}")?;
                 }
             }
         }
 
         if !result.similar_properties.is_empty() {
             let mut table = Table::new();
-            table.set_format(new_format(6));
-            println!("   Similar properties:");
+            table.set_format(new_format(self.indent + 6));
+            writeln!(out, "{pad}   Similar properties:")?;
             for (section, sims) in result.similar_properties {
-                println!("     [{section}]:");
+                writeln!(out, "{pad}     [{section}]:")?;
 
                 for (first, second) in sims {
                     table.add_row(row![first, second]);
                 }
             }
-            table.printstd();
+            print_table(&table, out);
+        }
+
+        if !result.invalid_values.is_empty() {
+            writeln!(out, "{pad}   Invalid values:")?;
+            for (section, values) in result.invalid_values {
+                writeln!(out, "{pad}     [{section}]:")?;
+                for (property, value) in values {
+                    writeln!(out, "{pad}       {property} = {value}")?;
+                }
+            }
         }
 
         if !result.ext_problems.is_empty() {
             for item in result.ext_problems {
-                if !item.duplicates.is_empty() {
-                    println!("   Duplicates related to {}:", item.ext);
-                    for duplicate in item.duplicates {
-                        println!("       {duplicate}");
+                let (conflicting, redundant): (Vec<_>, Vec<_>) = item
+                    .duplicates
+                    .into_iter()
+                    .partition(ExtDuplicate::is_conflicting);
+                if !conflicting.is_empty() {
+                    writeln!(out, "{pad}   Conflicting values related to {}:", item.ext)?;
+                    for duplicate in conflicting {
+                        writeln!(out, "{pad}       {}", duplicate.name)?;
+                        for (section, value) in duplicate.values {
+                            writeln!(out, "{pad}         [{section}] {value}")?;
+                        }
+                    }
+                }
+                if !redundant.is_empty() {
+                    writeln!(out, "{pad}   Redundant duplicates related to {}:", item.ext)?;
+                    for duplicate in redundant {
+                        writeln!(out, "{pad}       {}", duplicate.name)?;
                     }
                 }
 
                 if !item.similar.is_empty() {
                     let mut table = Table::new();
-                    table.set_format(new_format(6));
-                    println!("   Similar properties related to {}:", item.ext);
+                    table.set_format(new_format(self.indent + 6));
+                    writeln!(out, "{pad}   Similar properties related to {}:", item.ext)?;
                     for (first, second) in item.similar {
                         table.add_row(row![first, second]);
                     }
-                    table.printstd();
+                    print_table(&table, out);
                 }
             }
         }
-        println!();
+
+        if !result.overlapping_sections.is_empty() {
+            writeln!(out, "{pad}   Sections that match the same files:")?;
+            for (first, second) in result.overlapping_sections {
+                writeln!(out, "{pad}     [{first}] and [{second}]")?;
+            }
+        }
+
+        if !result.equivalent_sections.is_empty() {
+            writeln!(
+                out,
+                "{pad}   Sections that are the same glob written differently:"
+            )?;
+            for (first, second) in result.equivalent_sections {
+                writeln!(out, "{pad}     [{first}] and [{second}]")?;
+            }
+        }
+
+        if !result.oversized_properties.is_empty() {
+            writeln!(out, "{pad}   Properties that exceed length limit:")?;
+            for (section, property) in result.oversized_properties {
+                writeln!(out, "{pad}     [{section}]: {property}")?;
+            }
+        }
+
+        if !result.lexer_errors.is_empty() {
+            writeln!(out, "{pad}   Lines that cannot be parsed:")?;
+            for error in result.lexer_errors {
+                writeln!(out, "{pad}     line {}", error.line)?;
+            }
+        }
+
+        if !result.unbalanced_brackets.is_empty() {
+            writeln!(out, "{pad}   Section heads with unbalanced brackets:")?;
+            for (line, head) in result.unbalanced_brackets {
+                writeln!(out, "{pad}     line {line}: {head}")?;
+            }
+        }
+
+        if !result.invalid_key_chars.is_empty() {
+            writeln!(out, "{pad}   Keys with non-printable characters:")?;
+            for key in result.invalid_key_chars {
+                writeln!(out, "{pad}     {}", key.escape_debug())?;
+            }
+        }
+
+        if !result.inconsistent_assignment_style.is_empty() {
+            writeln!(out, "{pad}   Assignments in inconsistent style:")?;
+            for (section, property) in result.inconsistent_assignment_style {
+                writeln!(out, "{pad}     [{section}]: {property}")?;
+            }
+        }
+
+        if !result.tab_in_value.is_empty() {
+            writeln!(out, "{pad}   Values that contain tab characters:")?;
+            for (section, property) in result.tab_in_value {
+                writeln!(out, "{pad}     [{section}]: {property}")?;
+            }
+        }
+
+        if !result.empty_sections.is_empty() {
+            writeln!(out, "{pad}   Sections without properties:")?;
+            for section in result.empty_sections {
+                writeln!(out, "{pad}     [{section}]")?;
+            }
+        }
+
+        if !result.whitespace_titles.is_empty() {
+            writeln!(
+                out,
+                "{pad}   Section titles with leading or trailing whitespace:"
+            )?;
+            for title in result.whitespace_titles {
+                writeln!(out, "{pad}     [{title}]")?;
+            }
+        }
+
+        if !result.encoding_warnings.is_empty() {
+            writeln!(out, "{pad}   Encoding problems:")?;
+            for warning in result.encoding_warnings {
+                writeln!(out, "{pad}     {warning}")?;
+            }
+        }
+
+        if !result.unknown_properties.is_empty() {
+            writeln!(out, "{pad}   Unknown properties:")?;
+            for (section, unknown) in result.unknown_properties {
+                writeln!(out, "{pad}     [{section}]:")?;
+                for property in unknown {
+                    match property.suggestion {
+                        Some(suggestion) => {
+                            writeln!(
+                                out,
+                                "{pad}       {} (did you mean {suggestion}?)",
+                                property.name
+                            )?;
+                        }
+                        None => writeln!(out, "{pad}       {}", property.name)?,
+                    }
+                }
+            }
+        }
+
+        if !result.deprecated_properties.is_empty() {
+            writeln!(out, "{pad}   Deprecated properties:")?;
+            for (property, replacement) in result.deprecated_properties {
+                writeln!(out, "{pad}     {property} (use {replacement} instead)")?;
+            }
+        }
+
+        if !result.useless_properties.is_empty() {
+            writeln!(out, "{pad}   Properties that have no effect:")?;
+            for (section, properties) in result.useless_properties {
+                writeln!(out, "{pad}     [{section}]:")?;
+                for property in properties {
+                    writeln!(out, "{pad}       {property}")?;
+                }
+            }
+        }
+
+        if !result.misplaced_root.is_empty() {
+            writeln!(out, "{pad}   root defined inside section is ignored:")?;
+            for (section, line) in result.misplaced_root {
+                writeln!(out, "{pad}     [{section}] at line {line}")?;
+            }
+        }
+
+        if !result.tab_width_conflicts.is_empty() {
+            writeln!(
+                out,
+                "{pad}   indent_size different from tab_width with tab indentation:"
+            )?;
+            for (section, conflicts) in result.tab_width_conflicts {
+                for (size, width) in conflicts {
+                    writeln!(
+                        out,
+                        "{pad}     [{section}]: indent_size = {size}, tab_width = {width}"
+                    )?;
+                }
+            }
+        }
+
+        if !result.dead_sections.is_empty() {
+            writeln!(out, "{pad}   Sections that never match any file:")?;
+            for section in result.dead_sections {
+                writeln!(out, "{pad}     [{section}]")?;
+            }
+        }
+
+        if !result.duplicate_glob_members.is_empty() {
+            writeln!(out, "{pad}   Repeated alternatives in section titles:")?;
+            for (section, members) in result.duplicate_glob_members {
+                writeln!(out, "{pad}     [{section}]: {}", members.join(", "))?;
+            }
+        }
+
+        if !result.non_canonical_keys.is_empty() {
+            writeln!(out, "{pad}   Keys that aren't lowercase snake case:")?;
+            for (section, keys) in result.non_canonical_keys {
+                writeln!(out, "{pad}     [{section}]:")?;
+                for (key, canonical) in keys {
+                    writeln!(out, "{pad}       {key} (use {canonical} instead)")?;
+                }
+            }
+        }
+
+        if !result.incomplete_sections.is_empty() {
+            writeln!(out, "{pad}   Indentation properties without indent_style:")?;
+            for (section, properties) in result.incomplete_sections {
+                writeln!(out, "{pad}     [{section}]:")?;
+                for property in properties {
+                    writeln!(out, "{pad}       {property}")?;
+                }
+            }
+        }
+        writeln!(out)
     }
 }
 
-pub struct Error {}
+/// Serializable representation of a [`ValidationResult`] shared by all
+/// machine readable formatters so they always emit the same structure
+#[derive(Serialize)]
+struct Report<'a> {
+    path: &'a str,
+    state: &'static str,
+    is_root: bool,
+    duplicate_sections: Vec<&'a str>,
+    duplicate_properties: BTreeMap<&'a str, Vec<&'a str>>,
+    similar_properties: BTreeMap<&'a str, Vec<(&'a str, &'a str)>>,
+    invalid_values: BTreeMap<&'a str, Vec<(&'a str, &'a str)>>,
+    ext_problems: Vec<ExtReport<'a>>,
+    overlapping_sections: Vec<(&'a str, &'a str)>,
+    equivalent_sections: Vec<(&'a str, &'a str)>,
+    incomplete_sections: BTreeMap<&'a str, Vec<&'a str>>,
+    oversized_properties: Vec<(&'a str, &'a str)>,
+    tab_in_value: Vec<(&'a str, &'a str)>,
+    inconsistent_assignment_style: Vec<(&'a str, &'a str)>,
+    invalid_key_chars: Vec<&'a str>,
+    lexer_errors: Vec<usize>,
+    unbalanced_brackets: Vec<(usize, &'a str)>,
+    empty_sections: Vec<&'a str>,
+    whitespace_titles: Vec<&'a str>,
+    encoding_warnings: Vec<String>,
+    unknown_properties: BTreeMap<&'a str, Vec<UnknownReport<'a>>>,
+    useless_properties: BTreeMap<&'a str, Vec<&'a str>>,
+    tab_width_conflicts: BTreeMap<&'a str, Vec<(&'a str, &'a str)>>,
+    duplicate_glob_members: BTreeMap<&'a str, Vec<&'a str>>,
+    dead_sections: Vec<&'a str>,
+    misplaced_root: Vec<(&'a str, usize)>,
+    non_canonical_keys: BTreeMap<&'a str, Vec<(&'a str, String)>>,
+    deprecated_properties: Vec<(&'a str, &'static str)>,
+    section_property_counts: BTreeMap<&'a str, usize>,
+    override_conflicts: BTreeMap<&'a str, Vec<(&'a str, &'a str, &'a str)>>,
+    boolean_flips: BTreeMap<&'a str, Vec<(&'a str, &'a str, &'a str)>>,
+    commented_overrides: Vec<&'a str>,
+    shadowed_sections: Vec<(&'a str, &'a str)>,
+    indent_size_tab_without_width: Vec<&'a str>,
+}
+
+#[derive(Serialize)]
+struct UnknownReport<'a> {
+    name: &'a str,
+    suggestion: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct ExtReport<'a> {
+    ext: String,
+    duplicates: Vec<ExtDuplicateReport<'a>>,
+    similar: Vec<(&'a str, &'a str)>,
+}
+
+#[derive(Serialize)]
+struct ExtDuplicateReport<'a> {
+    name: &'a str,
+    conflicting: bool,
+    values: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> From<ValidationResult<'a>> for Report<'a> {
+    fn from(result: ValidationResult<'a>) -> Self {
+        let state = match result.state() {
+            ValidationState::Valid => "valid",
+            ValidationState::Invalid => "invalid",
+            ValidationState::SomeProblems => "some_problems",
+        };
+        Self {
+            path: result.path,
+            state,
+            is_root: result.is_root,
+            duplicate_sections: result.duplicate_sections,
+            duplicate_properties: result.duplicate_properties,
+            similar_properties: result.similar_properties,
+            invalid_values: result.invalid_values,
+            ext_problems: result
+                .ext_problems
+                .into_iter()
+                .map(|e| ExtReport {
+                    ext: e.ext,
+                    duplicates: e
+                        .duplicates
+                        .into_iter()
+                        .map(|d| ExtDuplicateReport {
+                            name: d.name,
+                            conflicting: d.is_conflicting(),
+                            values: d.values,
+                        })
+                        .collect(),
+                    similar: e.similar,
+                })
+                .collect(),
+            overlapping_sections: result.overlapping_sections,
+            equivalent_sections: result.equivalent_sections,
+            incomplete_sections: result.incomplete_sections,
+            oversized_properties: result.oversized_properties,
+            tab_in_value: result.tab_in_value,
+            inconsistent_assignment_style: result.inconsistent_assignment_style,
+            invalid_key_chars: result.invalid_key_chars,
+            lexer_errors: result.lexer_errors.iter().map(|e| e.line).collect(),
+            unbalanced_brackets: result.unbalanced_brackets,
+            empty_sections: result.empty_sections,
+            whitespace_titles: result.whitespace_titles,
+            encoding_warnings: result.encoding_warnings,
+            unknown_properties: result
+                .unknown_properties
+                .into_iter()
+                .map(|(section, unknown)| {
+                    let unknown = unknown
+                        .into_iter()
+                        .map(|u| UnknownReport {
+                            name: u.name,
+                            suggestion: u.suggestion,
+                        })
+                        .collect();
+                    (section, unknown)
+                })
+                .collect(),
+            useless_properties: result.useless_properties,
+            tab_width_conflicts: result.tab_width_conflicts,
+            duplicate_glob_members: result.duplicate_glob_members,
+            dead_sections: result.dead_sections,
+            misplaced_root: result.misplaced_root,
+            non_canonical_keys: result.non_canonical_keys,
+            deprecated_properties: result.deprecated_properties,
+            section_property_counts: result.section_property_counts,
+            override_conflicts: result.override_conflicts,
+            boolean_flips: result.boolean_flips,
+            commented_overrides: result.commented_overrides,
+            shadowed_sections: result.shadowed_sections,
+            indent_size_tab_without_width: result.indent_size_tab_without_width,
+        }
+    }
+}
+
+/// Outputs each validation result as a separate YAML document
+pub struct YamlFormatter<W: Write = Stdout> {
+    only_problems: bool,
+    writer: RefCell<W>,
+}
+
+impl YamlFormatter {
+    /// Creates formatter that writes to stdout
+    #[must_use]
+    pub fn new(only_problems: bool) -> Self {
+        Self::with_writer(only_problems, io::stdout())
+    }
+}
+
+impl<W: Write> YamlFormatter<W> {
+    /// Creates formatter that writes to the writer specified
+    #[must_use]
+    pub fn with_writer(only_problems: bool, writer: W) -> Self {
+        Self {
+            only_problems,
+            writer: RefCell::new(writer),
+        }
+    }
+
+    /// Returns the writer back so as written output can be used
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+}
+
+impl<W: Write> ValidationFormatter for YamlFormatter<W> {
+    fn format(&self, result: ValidationResult) {
+        if self.only_problems && result.state().is_ok() {
+            return;
+        }
+        let report = Report::from(result);
+        match serde_yaml::to_string(&report) {
+            Ok(doc) => {
+                // Any failure to write is ignored the same way as tables printing does
+                let _ = write!(self.writer.borrow_mut(), "---\n{doc}");
+            }
+            Err(e) => eprintln!("{}", Red.paint(e.to_string())),
+        }
+    }
+}
+
+/// Outputs duplicates found as rich diagnostics with labels pointing at offending lines
+/// of the validated content. Other findings are summarized by kind.
+pub struct MietteFormatter<W: Write = Stdout> {
+    only_problems: bool,
+    color: bool,
+    writer: RefCell<W>,
+}
+
+impl MietteFormatter {
+    /// Creates formatter that writes to stdout and colors output only if stdout is a terminal
+    #[must_use]
+    pub fn new(only_problems: bool) -> Self {
+        Self {
+            only_problems,
+            color: stdout_is_terminal(),
+            writer: RefCell::new(io::stdout()),
+        }
+    }
+}
+
+impl<W: Write> MietteFormatter<W> {
+    /// Creates formatter that writes to the writer specified without colors
+    /// unless they're enabled by [`MietteFormatter::with_color`]
+    #[must_use]
+    pub fn with_writer(only_problems: bool, writer: W) -> Self {
+        Self {
+            only_problems,
+            color: false,
+            writer: RefCell::new(writer),
+        }
+    }
+
+    /// Returns the writer back so as written output can be used
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+
+    /// Enables or disables colored output regardless of stdout kind
+    #[must_use]
+    pub fn with_color(mut self, enabled: bool) -> Self {
+        self.color = enabled;
+        self
+    }
+
+    fn render(
+        &self,
+        out: &mut W,
+        result: &ValidationResult,
+        message: String,
+        labels: Vec<LabeledSpan>,
+    ) -> io::Result<()> {
+        if labels.is_empty() {
+            return Ok(());
+        }
+        let theme = if self.color {
+            GraphicalTheme::unicode()
+        } else {
+            GraphicalTheme::unicode_nocolor()
+        };
+        let diagnostic = MietteDiagnostic::new(message)
+            .with_severity(Severity::Error)
+            .with_labels(labels);
+        let report = miette::Report::new(diagnostic)
+            .with_source_code(NamedSource::new(result.path, result.content.to_string()));
+        let mut rendered = String::new();
+        if GraphicalReportHandler::new_themed(theme)
+            .render_report(&mut rendered, report.as_ref())
+            .is_ok()
+        {
+            writeln!(out, "{rendered}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> ValidationFormatter for MietteFormatter<W> {
+    fn format(&self, result: ValidationResult) {
+        // Any failure to write is ignored the same way as tables printing does
+        let _ = self.write(&mut *self.writer.borrow_mut(), result);
+    }
+}
+
+impl<W: Write> MietteFormatter<W> {
+    fn write(&self, out: &mut W, result: ValidationResult) -> io::Result<()> {
+        let state = result.state();
+        let msg = match state {
+            ValidationState::Valid => paint(self.color, Green, "valid"),
+            ValidationState::Invalid => paint(self.color, Red, "invalid"),
+            ValidationState::SomeProblems => paint(self.color, Yellow, "has some problems"),
+        };
+        if !self.only_problems || !state.is_ok() {
+            writeln!(out, " {} {}", result.path, msg)?;
+        }
+        if state.is_ok() {
+            return Ok(());
+        }
+
+        let mut sections = Vec::new();
+        let mut properties = Vec::new();
+        let mut conflicts = Vec::new();
+        let mut section = "";
+        for t in tokens(result.content) {
+            match t.token {
+                Token::Head(title) => {
+                    section = title;
+                    if result.duplicate_sections.contains(&title) {
+                        sections.push(LabeledSpan::at(t.span, "duplicate section"));
+                    }
+                }
+                Token::Pair(key, _) => {
+                    if result
+                        .duplicate_properties
+                        .get(section)
+                        .is_some_and(|props| props.contains(&key))
+                    {
+                        properties.push(LabeledSpan::at(t.span.clone(), "duplicate property"));
+                    }
+                    let conflicting = result.ext_problems.iter().find(|e| {
+                        e.duplicates.iter().any(|d| {
+                            d.name == key
+                                && d.is_conflicting()
+                                && d.values.iter().any(|(s, _)| *s == section)
+                        })
+                    });
+                    if let Some(ext) = conflicting {
+                        conflicts.push(LabeledSpan::at(
+                            t.span,
+                            format!("conflicting value for {}", ext.ext),
+                        ));
+                    }
+                }
+                Token::Comment(_) => {}
+            }
+        }
+
+        self.render(out, &result, "Duplicate sections".to_string(), sections)?;
+        self.render(out, &result, "Duplicate properties".to_string(), properties)?;
+        self.render(
+            out,
+            &result,
+            "Properties set to different values by sections matching the same files".to_string(),
+            conflicts,
+        )?;
+
+        let others = [
+            ("similar properties", result.similar_properties.len()),
+            ("sections with invalid values", result.invalid_values.len()),
+            ("overlapping sections", result.overlapping_sections.len()),
+            ("equivalent sections", result.equivalent_sections.len()),
+            ("incomplete sections", result.incomplete_sections.len()),
+            ("oversized properties", result.oversized_properties.len()),
+            ("values with tab characters", result.tab_in_value.len()),
+            (
+                "assignments in inconsistent style",
+                result.inconsistent_assignment_style.len(),
+            ),
+            (
+                "keys with non-printable characters",
+                result.invalid_key_chars.len(),
+            ),
+            ("unparsable lines", result.lexer_errors.len()),
+            (
+                "section heads with unbalanced brackets",
+                result.unbalanced_brackets.len(),
+            ),
+            ("empty sections", result.empty_sections.len()),
+            (
+                "section titles with surrounding whitespace",
+                result.whitespace_titles.len(),
+            ),
+            ("encoding problems", result.encoding_warnings.len()),
+            (
+                "sections with unknown properties",
+                result.unknown_properties.len(),
+            ),
+            (
+                "sections with useless properties",
+                result.useless_properties.len(),
+            ),
+            ("misplaced root properties", result.misplaced_root.len()),
+            (
+                "sections with indent_size different from tab_width",
+                result.tab_width_conflicts.len(),
+            ),
+            ("sections that never match", result.dead_sections.len()),
+            (
+                "sections with repeated alternatives",
+                result.duplicate_glob_members.len(),
+            ),
+            (
+                "sections with non canonical keys",
+                result.non_canonical_keys.len(),
+            ),
+            ("deprecated properties", result.deprecated_properties.len()),
+        ];
+        for (kind, count) in others.into_iter().filter(|(_, count)| *count > 0) {
+            writeln!(out, "   {count} {kind}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints file errors to stdout. Create it with [`Error::new`] or [`Error::default`]
+pub struct Error {
+    color: bool,
+}
+
+impl Error {
+    /// Creates error printer that colors output only if stdout is a terminal
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            color: stdout_is_terminal(),
+        }
+    }
+
+    /// Enables or disables colored output regardless of stdout kind
+    #[must_use]
+    pub fn with_color(mut self, enabled: bool) -> Self {
+        self.color = enabled;
+        self
+    }
+}
+
+impl Default for Error {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Errorer for Error {
     fn error(&self, path: &str, err: &str) {
-        println!(" {path}");
-        println!("  Error: {}", Red.paint(err));
-        println!();
+        // Any failure to print is ignored the same way as Table::printstd does
+        let _ = write_error(io::stdout().lock(), path, err, self.color);
     }
 }
 
-pub struct Comparator {}
+fn write_error<W: Write>(mut out: W, path: &str, err: &str, color: bool) -> io::Result<()> {
+    writeln!(out, " {path}")?;
+    writeln!(out, "  Error: {}", paint(color, Red, err))?;
+    writeln!(out)
+}
+
+/// Prints comparison results as a table. Create it with [`Comparator::new`] or
+/// [`Comparator::default`] to print to stdout or with [`Comparator::with_writer`]
+pub struct Comparator<W: Write = Stdout> {
+    color: bool,
+    indent: usize,
+    writer: RefCell<W>,
+}
 
-impl ComparisonFormatter for Comparator {
+impl Comparator {
+    /// Creates comparator that writes to stdout and colors output only if stdout is a terminal
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            color: stdout_is_terminal(),
+            indent: 0,
+            writer: RefCell::new(io::stdout()),
+        }
+    }
+}
+
+impl<W: Write> Comparator<W> {
+    /// Creates comparator that writes to the writer specified without colors
+    /// unless they're enabled by [`Comparator::with_color`]
+    #[must_use]
+    pub fn with_writer(writer: W) -> Self {
+        Self {
+            color: false,
+            indent: 0,
+            writer: RefCell::new(writer),
+        }
+    }
+
+    /// Enables or disables colored output regardless of stdout kind
+    #[must_use]
+    pub fn with_color(mut self, enabled: bool) -> Self {
+        self.color = enabled;
+        self
+    }
+
+    /// Shifts all output lines right by `indent` spaces
+    #[must_use]
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Returns the writer back so as written output can be used
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner()
+    }
+
+    fn styled(&self, text: &str, style: Style) -> Cell {
+        if self.color {
+            Cell::new(&style.paint(text).to_string())
+        } else {
+            Cell::new(text)
+        }
+    }
+}
+
+impl Default for Comparator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> ComparisonFormatter for Comparator<W> {
     fn format(&self, result: BTreeMap<&str, Vec<CompareItem>>) {
+        self.print_comparison(result, ["FILE #1", "FILE #2"]);
+    }
+
+    fn format_labeled(&self, result: BTreeMap<&str, Vec<CompareItem>>, labels: [&str; 2]) {
+        let titles = column_titles(labels);
+        self.print_comparison(result, [titles[0], titles[1]]);
+    }
+}
+
+impl<W: Write> Comparator<W> {
+    fn print_comparison(&self, result: BTreeMap<&str, Vec<CompareItem>>, titles: [&str; 2]) {
         let mut table = Table::new();
-        table.set_format(new_format(0));
-        table.set_titles(row![bF->"", bF->"FILE #1", bF->"FILE #2"]);
+        table.set_format(new_format(self.indent));
+        table.set_titles(Row::new(
+            std::iter::once("")
+                .chain(titles)
+                .map(|t| self.styled(t, Style::new().bold()))
+                .collect(),
+        ));
 
         for (sect, values) in result {
             if !sect.is_empty() {
                 table.add_empty_row();
             }
 
-            table.add_row(row![bFH3=>sect]);
+            let title = self.styled(sect, Style::new().bold()).with_hspan(3);
+            table.add_row(Row::new(vec![title]));
             for value in values {
                 let v1 = value.first_value.unwrap_or_default();
                 let v2 = value.second_value.unwrap_or_default();
-                let c1: Cell;
-                let c2: Cell;
-                if v1 == v2 {
-                    // No color
-                    c1 = cell!(v1);
-                    c2 = cell!(v2);
-                } else if v1.is_empty() || v2.is_empty() {
-                    // Green because one is missing in other
-                    c1 = cell!(Fg->v1);
-                    c2 = cell!(Fg->v2);
-                } else {
-                    // Yellow because values are different
-                    c1 = cell!(Fy->v1);
-                    c2 = cell!(Fy->v2);
-                }
+                let style = difference_style(&difference(&value));
+                let c1 = self.styled(v1, style);
+                let c2 = self.styled(v2, style);
 
                 let r = Row::new(vec![cell!(value.key), c1, c2]);
                 table.add_row(r);
             }
         }
         table.add_empty_row();
-        table.printstd();
+        print_table(&table, &mut *self.writer.borrow_mut());
+    }
+}
+
+impl<W: Write> NCompareFormatter for Comparator<W> {
+    fn format(&self, result: BTreeMap<&str, Vec<NCompareItem>>) {
+        let files = result
+            .values()
+            .flatten()
+            .map(|item| item.values.len())
+            .next()
+            .unwrap_or_default();
+        let mut table = Table::new();
+        table.set_format(new_format(self.indent));
+        let titles = std::iter::once(String::new())
+            .chain((1..=files).map(|i| format!("FILE #{i}")))
+            .map(|t| self.styled(&t, Style::new().bold()))
+            .collect();
+        table.set_titles(Row::new(titles));
+
+        for (sect, values) in result {
+            if !sect.is_empty() {
+                table.add_empty_row();
+            }
+
+            let title = self.styled(sect, Style::new().bold()).with_hspan(files + 1);
+            table.add_row(Row::new(vec![title]));
+            for value in values {
+                let style = difference_style(&n_difference(&value.values));
+                let cells = std::iter::once(cell!(value.key))
+                    .chain(
+                        value
+                            .values
+                            .iter()
+                            .map(|v| self.styled(v.unwrap_or_default(), style)),
+                    )
+                    .collect();
+                table.add_row(Row::new(cells));
+            }
+        }
+        table.add_empty_row();
+        print_table(&table, &mut *self.writer.borrow_mut());
+    }
+}
+
+/// Titles comparison columns with file names of labels. Labels are used as is
+/// if file names are the same, e.g. when both files are `.editorconfig`
+#[must_use]
+pub fn column_titles(labels: [&str; 2]) -> [&str; 2] {
+    let names = labels.map(|label| {
+        Path::new(label)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(label)
+    });
+    if names[0] == names[1] {
+        labels
+    } else {
+        names
+    }
+}
+
+/// Same rules as two files comparison uses: no difference if all values are equal,
+/// missing if some file misses the property and value difference otherwise
+fn n_difference(values: &[Option<&str>]) -> Difference {
+    if values.windows(2).all(|w| w[0] == w[1]) {
+        Difference::None
+    } else if values.iter().any(Option::is_none) {
+        Difference::Missing
+    } else {
+        Difference::Value
+    }
+}
+
+fn difference_style(difference: &Difference) -> Style {
+    match difference {
+        // No color
+        Difference::None => Style::new(),
+        // Green because one is missing in other
+        Difference::Missing => Green.normal(),
+        // Yellow because values are different
+        Difference::Value => Yellow.normal(),
+    }
+}
+
+/// Prints comparison results as a GitHub-flavored Markdown table so that they can be
+/// pasted into issues or pull requests. Emphasis is used instead of color to mark
+/// values that are missing in one of the files (italic) or different (bold).
+#[derive(Default)]
+pub struct MarkdownComparator;
+
+impl MarkdownComparator {
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ComparisonFormatter for MarkdownComparator {
+    fn format(&self, result: BTreeMap<&str, Vec<CompareItem>>) {
+        // Any failure to print is ignored the same way as Table::printstd does
+        let _ = write_comparison_markdown(io::stdout().lock(), result, ["File #1", "File #2"]);
+    }
+
+    fn format_labeled(&self, result: BTreeMap<&str, Vec<CompareItem>>, labels: [&str; 2]) {
+        // Any failure to print is ignored the same way as Table::printstd does
+        let _ = write_comparison_markdown(io::stdout().lock(), result, column_titles(labels));
+    }
+}
+
+fn write_comparison_markdown<W: Write>(
+    mut out: W,
+    result: BTreeMap<&str, Vec<CompareItem>>,
+    titles: [&str; 2],
+) -> io::Result<()> {
+    writeln!(out)?;
+    writeln!(
+        out,
+        "| Section | Property | {} | {} |",
+        markdown_escape(titles[0]),
+        markdown_escape(titles[1])
+    )?;
+    writeln!(out, "|---|---|---|---|")?;
+    for (sect, values) in result {
+        let title = if sect.is_empty() {
+            "(root)".to_string()
+        } else {
+            markdown_escape(sect)
+        };
+        for value in values {
+            let v1 = value.first_value.unwrap_or_default();
+            let v2 = value.second_value.unwrap_or_default();
+            let emphasis = match difference(&value) {
+                Difference::None => "",
+                Difference::Missing => "_",
+                Difference::Value => "**",
+            };
+            writeln!(
+                out,
+                "| {title} | {} | {} | {} |",
+                markdown_escape(value.key),
+                markdown_emphasize(v1, emphasis),
+                markdown_emphasize(v2, emphasis)
+            )?;
+        }
+    }
+    writeln!(out)
+}
+
+/// How values of the compared property differ
+#[derive(Debug, PartialEq, Eq)]
+enum Difference {
+    None,
+    /// Property is defined only in one of files
+    Missing,
+    /// Property is defined in both files with different values
+    Value,
+}
+
+fn difference(item: &CompareItem) -> Difference {
+    if !item.presence.in_both() {
+        Difference::Missing
+    } else if item.first_value == item.second_value {
+        Difference::None
+    } else {
+        Difference::Value
+    }
+}
+
+fn markdown_emphasize(value: &str, emphasis: &str) -> String {
+    if value.is_empty() {
+        String::new()
+    } else {
+        format!("{emphasis}{}{emphasis}", markdown_escape(value))
+    }
+}
+
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '|' | '*' | '_' | '`' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl NCompareFormatter for MarkdownComparator {
+    fn format(&self, result: BTreeMap<&str, Vec<NCompareItem>>) {
+        let files = result
+            .values()
+            .flatten()
+            .map(|item| item.values.len())
+            .next()
+            .unwrap_or_default();
+        let headers: Vec<String> = (1..=files).map(|i| format!(" File #{i} |")).collect();
+        println!();
+        println!("| Section | Property |{}", headers.concat());
+        println!("|---|---|{}", "---|".repeat(files));
+        for (sect, values) in result {
+            let title = if sect.is_empty() {
+                "(root)".to_string()
+            } else {
+                markdown_escape(sect)
+            };
+            for value in values {
+                let emphasis = match n_difference(&value.values) {
+                    Difference::None => "",
+                    Difference::Missing => "_",
+                    Difference::Value => "**",
+                };
+                let cells: Vec<String> = value
+                    .values
+                    .iter()
+                    .map(|v| format!(" {} |", markdown_emphasize(v.unwrap_or_default(), emphasis)))
+                    .collect();
+                println!(
+                    "| {title} | {} |{}",
+                    markdown_escape(value.key),
+                    cells.concat()
+                );
+            }
+        }
+        println!();
+    }
+}
+
+/// Outputs comparison result as CSV rows of section, property and values of each file.
+/// Missing values are blank cells.
+#[derive(Default)]
+pub struct CsvComparator;
+
+impl CsvComparator {
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ComparisonFormatter for CsvComparator {
+    fn format(&self, result: BTreeMap<&str, Vec<CompareItem>>) {
+        // Any failure to print is ignored the same way as Table::printstd does
+        let _ = write_comparison_csv(io::stdout().lock(), result, ["file1_value", "file2_value"]);
+    }
+
+    fn format_labeled(&self, result: BTreeMap<&str, Vec<CompareItem>>, labels: [&str; 2]) {
+        // Any failure to print is ignored the same way as Table::printstd does
+        let _ = write_comparison_csv(io::stdout().lock(), result, column_titles(labels));
+    }
+}
+
+impl NCompareFormatter for CsvComparator {
+    fn format(&self, result: BTreeMap<&str, Vec<NCompareItem>>) {
+        let files = result
+            .values()
+            .flatten()
+            .map(|item| item.values.len())
+            .next()
+            .unwrap_or_default();
+        let rows = result.into_iter().flat_map(|(sect, values)| {
+            values.into_iter().map(move |v| {
+                let mut row = vec![sect, v.key];
+                row.extend(v.values.iter().map(|value| value.unwrap_or_default()));
+                row
+            })
+        });
+        let headers: Vec<String> = (1..=files).map(|i| format!("file{i}_value")).collect();
+        // Any failure to print is ignored the same way as Table::printstd does
+        let _ = write_csv(io::stdout().lock(), &headers, rows);
+    }
+}
+
+fn write_comparison_csv<W: io::Write>(
+    writer: W,
+    result: BTreeMap<&str, Vec<CompareItem>>,
+    titles: [&str; 2],
+) -> csv::Result<()> {
+    let rows = result.into_iter().flat_map(|(sect, values)| {
+        values.into_iter().map(move |v| {
+            vec![
+                sect,
+                v.key,
+                v.first_value.unwrap_or_default(),
+                v.second_value.unwrap_or_default(),
+            ]
+        })
+    });
+    write_csv(writer, &titles, rows)
+}
+
+/// Writes header of section, key and `values` titles followed by `rows`
+fn write_csv<'a, W: io::Write>(
+    writer: W,
+    values: &[impl AsRef<str>],
+    rows: impl Iterator<Item = Vec<&'a str>>,
+) -> csv::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    let header = ["section", "key"]
+        .into_iter()
+        .chain(values.iter().map(AsRef::as_ref));
+    writer.write_record(header)?;
+    for row in rows {
+        writer.write_record(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Checks whether stdout is attached to a terminal so colors can be used safely
+#[must_use]
+pub fn stdout_is_terminal() -> bool {
+    io::stdout().is_terminal()
+}
+
+fn paint(color: bool, colour: Colour, text: &str) -> String {
+    if color {
+        colour.paint(text).to_string()
+    } else {
+        text.to_string()
     }
 }
 
+/// Prints table into `out`. Cells are colored by painting their text so escape codes
+/// get into any writer and don't break alignment since they're not counted in width
+fn print_table<W: Write>(table: &Table, out: &mut W) {
+    // Any failure to print is ignored the same way as Table::printstd does
+    let _ = table.print(out);
+}
+
 fn new_format(ident: usize) -> TableFormat {
     format::FormatBuilder::new()
         .column_separator(' ')
@@ -152,3 +1217,404 @@ fn new_format(ident: usize) -> TableFormat {
         .padding(0, 0)
         .build()
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_in_result)]
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn difference_tells_missing_from_empty() {
+        // Arrange
+        let missing = CompareItem::new("a", Some(""), None);
+        let empty = CompareItem::new("a", Some(""), Some("x"));
+        let same = CompareItem::new("a", Some(""), Some(""));
+
+        // Act & Assert
+        assert_eq!(difference(&missing), Difference::Missing);
+        assert_eq!(difference(&empty), Difference::Value);
+        assert_eq!(difference(&same), Difference::None);
+        assert_eq!(
+            difference(&CompareItem::only_second("a", "")),
+            Difference::Missing
+        );
+    }
+
+    #[test]
+    fn formatter_writes_to_writer() {
+        // Arrange
+        let content = "[*]\nindent_size = 2\nindent_size = 4\n[*]\ncharset = utf-8\n";
+        let formatter = Formatter::with_writer(false, Vec::new());
+
+        // Act
+        crate::validate(content, "test", &formatter);
+
+        // Assert
+        let output = String::from_utf8(formatter.into_inner()).unwrap();
+        assert_eq!(
+            output,
+            " test invalid\n   Duplicate sections:\n     *\n   Duplicate properties:\n     [*]:\n       indent_size\n\n"
+        );
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    #[trace]
+    fn formatter_colors_writer_output_only_if_enabled(#[case] color: bool) {
+        // Arrange
+        let content = "[*]\nindent_size = 2\nindent_size = 4\n";
+        let formatter = Formatter::with_writer(false, Vec::new()).with_color(color);
+
+        // Act
+        crate::validate(content, "test", &formatter);
+
+        // Assert
+        let output = String::from_utf8(formatter.into_inner()).unwrap();
+        assert_eq!(output.contains('\u{1b}'), color);
+        assert_eq!(output.contains(&Red.paint("invalid").to_string()), color);
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    #[trace]
+    fn comparator_colors_writer_output_only_if_enabled(#[case] color: bool) {
+        // Arrange
+        let comparator = Comparator::with_writer(Vec::new()).with_color(color);
+
+        // Act
+        crate::compare_contents(
+            "[*]\nindent_size = 2\n",
+            "[*]\nindent_size = 4\n",
+            &crate::CompareConfig::default(),
+            |result| ComparisonFormatter::format(&comparator, result),
+        );
+
+        // Assert
+        let output = String::from_utf8(comparator.into_inner()).unwrap();
+        assert_eq!(output.contains('\u{1b}'), color);
+        assert_eq!(output.contains(&Yellow.paint("2").to_string()), color);
+        let plain: Vec<&str> = output
+            .lines()
+            .filter(|line| line.contains("indent_size"))
+            .collect();
+        assert_eq!(plain.len(), 1);
+        // escape codes don't shift columns
+        let plain = plain[0]
+            .replace(&Yellow.prefix().to_string(), "")
+            .replace(&Yellow.suffix().to_string(), "");
+        assert!(plain.contains("indent_size 2       4"));
+    }
+
+    #[test]
+    fn formatter_writes_only_problems_to_writer() {
+        // Arrange
+        let formatter = Formatter::with_writer(true, Vec::new());
+
+        // Act
+        crate::validate("[*]\nindent_size = 2\n", "test", &formatter);
+
+        // Assert
+        assert!(formatter.into_inner().is_empty());
+    }
+
+    #[test]
+    fn formatter_writes_with_indent() {
+        // Arrange
+        let content = "[*]\nab = 1\ndab = 2\n";
+        let formatter = Formatter::with_writer(false, Vec::new()).with_indent(2);
+
+        // Act
+        crate::validate(content, "test", &formatter);
+
+        // Assert
+        let output = String::from_utf8(formatter.into_inner()).unwrap();
+        assert_eq!(
+            output,
+            "   test has some problems\n     Similar properties:\n       [*]:\n         dab ab \n\n"
+        );
+    }
+
+    #[test]
+    fn formatter_indents_every_header() {
+        // Arrange
+        let content = "root = true\n[*.{c,h}]\na = b\n[*.[ch]]\nc = d\n[ *.md ]\ne = f\n[*.go]\nindent_style = tab\nindent_size = 2\ntab_width = 4\n";
+        let formatter = Formatter::with_writer(false, Vec::new()).with_indent(2);
+
+        // Act
+        crate::validate(content, "test", &formatter);
+
+        // Assert
+        let output = String::from_utf8(formatter.into_inner()).unwrap();
+        assert!(output.contains("\n     Sections that are the same glob written differently:\n"));
+        assert!(output.contains("\n     Section titles with leading or trailing whitespace:\n"));
+        assert!(
+            output.contains("\n     indent_size different from tab_width with tab indentation:\n")
+        );
+        assert!(output
+            .lines()
+            .filter(|line| !line.is_empty())
+            .all(|line| line.starts_with("   ")));
+    }
+
+    #[test]
+    fn comparator_writes_with_indent() {
+        // Arrange
+        let comparator = Comparator::with_writer(Vec::new()).with_indent(4);
+
+        // Act
+        crate::compare_contents(
+            "[*]\nindent_size = 2\n",
+            "[*]\nindent_size = 4\n",
+            &crate::CompareConfig::default(),
+            |result| ComparisonFormatter::format(&comparator, result),
+        );
+
+        // Assert
+        let output = String::from_utf8(comparator.into_inner()).unwrap();
+        assert!(output
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .all(|line| line.starts_with("    ")));
+        assert!(output.contains("    indent_size 2"));
+    }
+
+    #[test]
+    fn comparator_writes_to_writer() {
+        // Arrange
+        let comparator = Comparator::with_writer(Vec::new());
+
+        // Act
+        crate::compare_contents(
+            "[*]\nindent_size = 2\n",
+            "[*]\nindent_size = 4\n",
+            &crate::CompareConfig::default(),
+            |result| ComparisonFormatter::format(&comparator, result),
+        );
+
+        // Assert
+        let output = String::from_utf8(comparator.into_inner()).unwrap();
+        assert!(output.contains("FILE #1"));
+        assert!(output.contains("indent_size 2       4"));
+        assert!(!output.contains('\u{1b}'));
+    }
+
+    fn compare_labeled(labels: [&str; 2]) -> String {
+        let comparator = Comparator::with_writer(Vec::new());
+        crate::compare_labeled_with(
+            "[*]\nindent_size = 2\n",
+            "[*]\nindent_size = 4\n",
+            labels,
+            &crate::CompareConfig::default(),
+            &comparator,
+        );
+        String::from_utf8(comparator.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn comparator_titles_columns_with_file_names() {
+        // Arrange
+        let labels = ["a/current.editorconfig", "b/proposed.editorconfig"];
+
+        // Act
+        let output = compare_labeled(labels);
+
+        // Assert
+        assert!(output.contains(" current.editorconfig "));
+        assert!(output.contains(" proposed.editorconfig"));
+        assert!(!output.contains("a/current"));
+        assert!(!output.contains("FILE #1"));
+    }
+
+    #[test]
+    fn comparator_titles_columns_with_labels_if_file_names_same() {
+        // Arrange
+        let labels = ["current/.editorconfig", "proposed/.editorconfig"];
+
+        // Act
+        let output = compare_labeled(labels);
+
+        // Assert
+        assert!(output.contains("current/.editorconfig"));
+        assert!(output.contains("proposed/.editorconfig"));
+        assert!(!output.contains("FILE #1"));
+    }
+
+    #[rstest]
+    #[case(false, " f\n  Error: failed\n\n")]
+    #[case(true, " f\n  Error: \u{1b}[31mfailed\u{1b}[0m\n\n")]
+    #[trace]
+    fn write_error_tests(#[case] color: bool, #[case] expected: &str) {
+        // Arrange
+        let mut output = Vec::new();
+
+        // Act
+        write_error(&mut output, "f", "failed", color).unwrap();
+
+        // Assert
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+
+    #[test]
+    fn yaml_formatter_output_parses_back() {
+        // Arrange
+        let content = "root = true\n[*]\nindent_size = 2\nindent_size = 4\n[*.{md,txt}]\na = b\n[*.md]\na = c\n";
+        let formatter = YamlFormatter::with_writer(false, Vec::new());
+
+        // Act
+        crate::validate(content, "test", &formatter);
+
+        // Assert
+        let output = String::from_utf8(formatter.into_inner()).unwrap();
+        let report: serde_yaml::Value = serde_yaml::from_str(&output).unwrap();
+        let keys: Vec<&str> = report
+            .as_mapping()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str().unwrap())
+            .collect();
+        for key in [
+            "path",
+            "state",
+            "is_root",
+            "duplicate_sections",
+            "duplicate_properties",
+            "ext_problems",
+            "unknown_properties",
+            "deprecated_properties",
+        ] {
+            assert!(keys.contains(&key), "{key} is missing");
+        }
+        assert_eq!(report["path"].as_str(), Some("test"));
+        assert_eq!(report["state"].as_str(), Some("invalid"));
+        assert_eq!(report["is_root"].as_bool(), Some(true));
+        assert_eq!(
+            report["duplicate_properties"]["*"][0].as_str(),
+            Some("indent_size")
+        );
+        let duplicate = &report["ext_problems"][0]["duplicates"][0];
+        assert_eq!(duplicate["name"].as_str(), Some("a"));
+        assert_eq!(duplicate["conflicting"].as_bool(), Some(true));
+    }
+
+    #[test]
+    fn yaml_formatter_skips_valid_files_if_only_problems() {
+        // Arrange
+        let formatter = YamlFormatter::with_writer(true, Vec::new());
+
+        // Act
+        crate::validate("root = true\n[*]\nindent_size = 2\n", "test", &formatter);
+
+        // Assert
+        assert!(formatter.into_inner().is_empty());
+    }
+
+    #[test]
+    fn miette_formatter_renders_summary_without_colors() {
+        // Arrange
+        let content = "[*]\nindent_size = 2\nindent_size = 4\n[*.md]\n";
+        let formatter = MietteFormatter::with_writer(false, Vec::new()).with_color(false);
+
+        // Act
+        crate::validate(content, "test", &formatter);
+
+        // Assert
+        let output = String::from_utf8(formatter.into_inner()).unwrap();
+        assert!(!output.contains('\u{1b}'));
+        assert!(output.starts_with(" test invalid\n"));
+        assert!(output.contains("Duplicate properties"));
+        assert!(output.contains("duplicate property"));
+        assert!(output.contains("\n   1 empty sections\n"));
+    }
+
+    #[test]
+    fn write_markdown_table() {
+        // Arrange
+        let c1 = "root = true\n[*]\nindent_size = 2\ncharset = utf-8\nlist = a|b\n";
+        let c2 = "root = true\n[*]\nindent_size = 4\ncharset = utf-8\n";
+        let mut output = Vec::new();
+
+        // Act
+        crate::compare_contents(c1, c2, &crate::CompareConfig::default(), |result| {
+            write_comparison_markdown(&mut output, result, ["File #1", "File #2"]).unwrap();
+        });
+
+        // Assert
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().filter(|l| !l.is_empty()).collect();
+        assert_eq!(lines[0], "| Section | Property | File #1 | File #2 |");
+        assert_eq!(lines[1], "|---|---|---|---|");
+        assert!(lines.contains(&"| (root) | root | true | true |"));
+        assert!(lines.contains(&"| \\* | charset | utf-8 | utf-8 |"));
+        assert!(lines.contains(&"| \\* | indent\\_size | **2** | **4** |"));
+        assert!(lines.contains(&"| \\* | list | _a\\|b_ |  |"));
+    }
+
+    #[test]
+    fn write_csv_round_trip() {
+        // Arrange
+        let c1 = "[*]\nindent_size = 2\nlist = a, b\n";
+        let c2 = "[*]\nindent_size = 4\n[*.md]\nquote = say \"hi\"\n";
+        let mut output = Vec::new();
+
+        // Act
+        crate::compare_contents(c1, c2, &crate::CompareConfig::default(), |result| {
+            write_comparison_csv(&mut output, result, ["file1_value", "file2_value"]).unwrap();
+        });
+
+        // Assert
+        let mut reader = csv::Reader::from_reader(output.as_slice());
+        let header: Vec<String> = reader.headers().unwrap().iter().map(String::from).collect();
+        assert_eq!(header, vec!["section", "key", "file1_value", "file2_value"]);
+        let records: Vec<Vec<String>> = reader
+            .records()
+            .map(|r| r.unwrap().iter().map(String::from).collect())
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                vec!["*", "indent_size", "2", "4"],
+                vec!["*", "list", "a, b", ""],
+                vec!["*.md", "quote", "", "say \"hi\""],
+            ]
+        );
+    }
+
+    #[test]
+    fn write_labeled_markdown_and_csv_headers() {
+        // Arrange
+        let titles = column_titles(["a/current.editorconfig", "b/proposed_1.editorconfig"]);
+        let mut markdown = Vec::new();
+        let mut csv = Vec::new();
+
+        // Act
+        crate::compare_contents(
+            "[*]\na = b\n",
+            "[*]\na = c\n",
+            &crate::CompareConfig::default(),
+            |result| {
+                write_comparison_markdown(&mut markdown, result.clone(), titles).unwrap();
+                write_comparison_csv(&mut csv, result, titles).unwrap();
+            },
+        );
+
+        // Assert
+        let markdown = String::from_utf8(markdown).unwrap();
+        assert!(markdown
+            .contains("| Section | Property | current.editorconfig | proposed\\_1.editorconfig |"));
+        let mut reader = csv::Reader::from_reader(csv.as_slice());
+        let header: Vec<&str> = reader.headers().unwrap().iter().collect();
+        assert_eq!(
+            header,
+            vec![
+                "section",
+                "key",
+                "current.editorconfig",
+                "proposed_1.editorconfig"
+            ]
+        );
+    }
+}
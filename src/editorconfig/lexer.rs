@@ -1,6 +1,6 @@
 use nom::branch::alt;
-use nom::bytes::complete::is_not;
-use nom::error::{FromExternalError, ParseError, Error};
+use nom::bytes::complete::{is_not, tag, take_till};
+use nom::error::{ErrorKind, FromExternalError, ParseError, Error};
 use nom::{character::complete, combinator, IResult};
 use nom::{sequence, Parser};
 
@@ -15,22 +15,35 @@ pub enum Token<'a> {
     Comment(&'a str),
 }
 
-/// Splits input into tokens
-pub fn tokenize(input: &str) -> impl Iterator<Item = Token> {
-    TokenIterator::new(input)
+/// Comment start strings defined by the specification
+pub const DEFAULT_COMMENT_PREFIXES: &[&str] = &["#", ";"];
+
+/// Splits input into tokens. Lines and inline comments start with any of `comment_prefixes`.
+/// If `spaced_comments` is set inline comment inside value must be preceded by whitespace
+/// and some value text, so `color = #fff` keeps `#fff` as the value
+pub fn tokenize<'a>(
+    input: &'a str,
+    comment_prefixes: &'a [&'a str],
+    spaced_comments: bool,
+) -> impl Iterator<Item = Token<'a>> {
+    TokenIterator::new(input, comment_prefixes, spaced_comments)
 }
 
 struct TokenIterator<'a> {
     input: &'a str,
     not_parsed_trail: &'a str,
+    comment_prefixes: &'a [&'a str],
+    spaced_comments: bool,
 }
 
 impl<'a> TokenIterator<'a> {
     /// Creates a new `TokenIterator` to parse the given input string.
-    fn new(input: &'a str) -> Self {
+    fn new(input: &'a str, comment_prefixes: &'a [&'a str], spaced_comments: bool) -> Self {
         Self {
             input,
             not_parsed_trail: "",
+            comment_prefixes,
+            spaced_comments,
         }
     }
 
@@ -40,7 +53,8 @@ impl<'a> TokenIterator<'a> {
     /// If parsing fails, it returns `None`.
     fn parse_line(&mut self, trail: &'a str, val: &'a str) -> Option<Token<'a>> {
         self.input = trail;
-        let (remain, token) = line::<'a, Error<&'a str>>(val).ok()?;
+        let (remain, token) =
+            line::<Error<&'a str>>(val, self.comment_prefixes, self.spaced_comments).ok()?;
         self.not_parsed_trail = remain;
         Some(token)
     }
@@ -51,9 +65,10 @@ impl<'a> Iterator for TokenIterator<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         if !self.not_parsed_trail.is_empty() {
-            let parsed_comment = comment::<'a, Error<&'a str>>(self.not_parsed_trail);
+            let parsed_comment =
+                comment::<Error<&'a str>>(self.not_parsed_trail, self.comment_prefixes);
             self.not_parsed_trail = "";
-            // if there were an error while parsing inline comment (for example it's not started from # or ;)
+            // if there were an error while parsing inline comment (for example it's not started from comment prefix)
             // just throw it and continue parsing
             // It may be sensible to warn user about it. Should think over it.
             if let Ok((_, inline_comment)) = parsed_comment {
@@ -67,7 +82,11 @@ impl<'a> Iterator for TokenIterator<'a> {
             if self.input.is_empty() {
                 break;
             }
-            let mut parser = sequence::terminated(complete::not_line_ending, complete::line_ending);
+            // lone \r is accepted as line ending too because `end_of_line = cr` files use it
+            let mut parser = sequence::terminated(
+                take_till(|c| c == '\r' || c == '\n'),
+                alt((complete::line_ending, tag("\r"))),
+            );
             let parsed: IResult<&'a str, &'a str, Error<&'a str>> = parser.parse(self.input);
             return if let Ok((trail, val)) = parsed {
                 if let Some(token) = self.parse_line(trail, val) {
@@ -82,18 +101,34 @@ impl<'a> Iterator for TokenIterator<'a> {
     }
 }
 
-fn line<'a, E>(input: &'a str) -> IResult<&'a str, Token<'a>, E>
+/// Checks whether the line is section head, key/value pair or comment
+pub fn is_valid_line(input: &str, comment_prefixes: &[&str]) -> bool {
+    line::<Error<&str>>(input, comment_prefixes, false).is_ok()
+}
+
+fn line<'a, E>(
+    input: &'a str,
+    comment_prefixes: &[&str],
+    spaced_comments: bool,
+) -> IResult<&'a str, Token<'a>, E>
 where
     E: ParseError<&'a str> + std::fmt::Debug + FromExternalError<&'a str, nom::Err<char>>,
 {
-    alt((head::<E>, key_value::<E>, comment::<E>)).parse(input)
+    alt((
+        |i| head::<E>(i, comment_prefixes),
+        |i| key_value::<E>(i, comment_prefixes, spaced_comments),
+        |i| comment::<E>(i, comment_prefixes),
+    ))
+    .parse(input)
 }
 
-fn head<'a, E>(input: &'a str) -> IResult<&'a str, Token<'a>, E>
+fn head<'a, E>(input: &'a str, comment_prefixes: &[&str]) -> IResult<&'a str, Token<'a>, E>
 where
     E: ParseError<&'a str> + std::fmt::Debug + FromExternalError<&'a str, nom::Err<char>>,
 {
-    let parser = sequence::preceded(complete::char('['), is_not("\n\r;#"));
+    let parser = sequence::preceded(complete::char('['), |i| {
+        till_comment(i, "\n\r", comment_prefixes, false)
+    });
 
     //  capture data until last ] to support brackets inside section head
     combinator::map_res(parser, |val: &str| match val.rfind(']') {
@@ -103,15 +138,19 @@ where
     .parse(input)
 }
 
-fn key_value<'a, E>(input: &'a str) -> IResult<&'a str, Token<'a>, E>
+fn key_value<'a, E>(
+    input: &'a str,
+    comment_prefixes: &[&str],
+    spaced_comments: bool,
+) -> IResult<&'a str, Token<'a>, E>
 where
     E: ParseError<&'a str> + std::fmt::Debug,
 {
-    const COMMENT_START_AND_SEPARATOR_CHARS: &str = "=;#";
+    const SEPARATOR_CHARS: &str = "=";
     let parser = sequence::separated_pair(
-        is_not(COMMENT_START_AND_SEPARATOR_CHARS),
+        |i| till_comment(i, SEPARATOR_CHARS, comment_prefixes, false),
         complete::char('='),
-        is_not(COMMENT_START_AND_SEPARATOR_CHARS),
+        |i| till_comment(i, SEPARATOR_CHARS, comment_prefixes, spaced_comments),
     );
 
     combinator::map(parser, |(k, v): (&str, &str)| {
@@ -120,20 +159,51 @@ where
     .parse(input)
 }
 
-fn comment<'a, E>(input: &'a str) -> IResult<&'a str, Token<'a>, E>
+fn comment<'a, E>(input: &'a str, comment_prefixes: &[&str]) -> IResult<&'a str, Token<'a>, E>
 where
     E: ParseError<&'a str> + std::fmt::Debug,
 {
+    let prefix = comment_prefixes
+        .iter()
+        .find(|p| !p.is_empty() && input.starts_with(**p))
+        .ok_or_else(|| nom::Err::Error(E::from_error_kind(input, ErrorKind::Tag)))?;
     combinator::map(
-        combinator::recognize(sequence::preceded(
-            alt((complete::char('#'), complete::char(';'))),
-            is_not("\n\r"),
-        )),
+        combinator::recognize(sequence::preceded(tag(*prefix), is_not("\n\r"))),
         Token::Comment,
     )
     .parse(input)
 }
 
+/// Takes at least one character until any of `stop_chars` or any comment prefix.
+/// If `spaced` is set comment prefix counts only after whitespace that follows some text
+fn till_comment<'a, E>(
+    input: &'a str,
+    stop_chars: &str,
+    comment_prefixes: &[&str],
+    spaced: bool,
+) -> IResult<&'a str, &'a str, E>
+where
+    E: ParseError<&'a str>,
+{
+    let starts_comment = |ix: usize| {
+        let before = &input[..ix];
+        !spaced || (before.ends_with([' ', '\t']) && !before.trim().is_empty())
+    };
+    let end = input
+        .char_indices()
+        .find(|(ix, c)| {
+            stop_chars.contains(*c)
+                || comment_prefixes
+                    .iter()
+                    .any(|p| !p.is_empty() && input[*ix..].starts_with(p) && starts_comment(*ix))
+        })
+        .map_or(input.len(), |(ix, _)| ix);
+    if end == 0 {
+        return Err(nom::Err::Error(E::from_error_kind(input, ErrorKind::IsNot)));
+    }
+    Ok((&input[end..], &input[..end]))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,6 +227,17 @@ mod tests {
             ("[a]", vec![Token::Head("a")]),
             ("[a]\r\n", vec![Token::Head("a")]),
             ("[a]\nk=v", vec![Token::Head("a"), Token::Pair("k", "v")]),
+            ("[a]\r", vec![Token::Head("a")]),
+            ("k=v\r", vec![Token::Pair("k", "v")]),
+            ("[a]\rk=v", vec![Token::Head("a"), Token::Pair("k", "v")]),
+            (
+                "[a]\rk=v\r\r[b]\r",
+                vec![Token::Head("a"), Token::Pair("k", "v"), Token::Head("b")],
+            ),
+            (
+                "[a]\r\nk=v\r[b]\n",
+                vec![Token::Head("a"), Token::Pair("k", "v"), Token::Head("b")],
+            ),
             (
                 "[a]\nk=v ; test",
                 vec![
@@ -241,7 +322,7 @@ mod tests {
 
         // Act & Assert
         for (validator, input, expected) in table_test!(cases) {
-            let actual: Vec<Token> = tokenize(input).collect();
+            let actual: Vec<Token> = tokenize(input, DEFAULT_COMMENT_PREFIXES, false).collect();
 
             validator
                 .given(input)
@@ -270,7 +351,7 @@ trim_trailing_whitespace = false
 "#;
 
         // Act
-        let result: Vec<Token> = tokenize(s).collect();
+        let result: Vec<Token> = tokenize(s, DEFAULT_COMMENT_PREFIXES, false).collect();
 
         // Assert
         let expected = vec![
@@ -288,4 +369,92 @@ trim_trailing_whitespace = false
         ];
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn tokenize_custom_comment_prefix() {
+        // Arrange
+        let s = "// generated\n[*]\nk = v // inline\nx = http://a\n# not a comment\n";
+
+        // Act
+        let result: Vec<Token> = tokenize(s, &["//"], false).collect();
+
+        // Assert
+        let expected = vec![
+            Token::Comment("// generated"),
+            Token::Head("*"),
+            Token::Pair("k", "v"),
+            Token::Comment("// inline"),
+            Token::Pair("x", "http:"),
+            Token::Comment("//a"),
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn tokenize_spaced_comments() {
+        // Arrange
+        let cases = vec![
+            ("color = #fff", vec![Token::Pair("color", "#fff")]),
+            (
+                "a = b # comment",
+                vec![Token::Pair("a", "b"), Token::Comment("# comment")],
+            ),
+            (
+                "a = b;c ; comment",
+                vec![Token::Pair("a", "b;c"), Token::Comment("; comment")],
+            ),
+            ("a = x#y", vec![Token::Pair("a", "x#y")]),
+            ("# a = b", vec![Token::Comment("# a = b")]),
+            (
+                "[*] # head",
+                vec![Token::Head("*"), Token::Comment("# head")],
+            ),
+        ];
+
+        // Act & Assert
+        for (validator, input, expected) in table_test!(cases) {
+            let actual: Vec<Token> = tokenize(input, DEFAULT_COMMENT_PREFIXES, true).collect();
+
+            validator
+                .given(input)
+                .when("tokenize with spaced comments")
+                .then(&format!("it should be {expected:#?}"))
+                .assert_eq(expected, actual);
+        }
+    }
+
+    #[test]
+    fn tokenize_custom_and_default_comment_prefixes() {
+        // Arrange
+        let s = "[*] // head\n; semicolon\n# hash\nk = v\n";
+
+        // Act
+        let result: Vec<Token> = tokenize(s, &["#", ";", "//"], false).collect();
+
+        // Assert
+        let expected = vec![
+            Token::Head("*"),
+            Token::Comment("// head"),
+            Token::Comment("; semicolon"),
+            Token::Comment("# hash"),
+            Token::Pair("k", "v"),
+        ];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn tokenize_huge_single_line() {
+        // Arrange
+        let value = "\u{e9}".repeat(512 * 1024);
+        let pair = format!("k = {value}");
+        let junk = "a".repeat(1024 * 1024);
+
+        // Act
+        let pairs: Vec<Token> = tokenize(&pair, DEFAULT_COMMENT_PREFIXES, false).collect();
+        let junks: Vec<Token> = tokenize(&junk, DEFAULT_COMMENT_PREFIXES, false).collect();
+
+        // Assert
+        assert_eq!(pairs, vec![Token::Pair("k", &value)]);
+        assert!(junks.is_empty());
+    }
 }
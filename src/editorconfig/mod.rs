@@ -1,31 +1,255 @@
 mod lexer;
 
-use lexer::Token;
+use std::ops::Range;
+
+use crate::properties::{self, TypedValue};
+
+pub use lexer::{Token, DEFAULT_COMMENT_PREFIXES};
 
 /// Named container of properties
 #[derive(Default)]
 pub struct Section<'a> {
     /// any data between square brackets (i.e. [])
     pub title: &'a str,
+    /// Byte offsets of the title in the source. Empty for implicit root section.
+    pub span: Range<usize>,
     pub properties: Vec<Property<'a>>,
+    /// comments found inside section including start character (i.e. # or ;)
+    pub comments: Vec<&'a str>,
+}
+
+impl Section<'_> {
+    /// Whether section has at least one comment with some text besides start character
+    pub fn has_meaningful_comments(&self) -> bool {
+        self.comments
+            .iter()
+            .any(|c| !c.trim_start_matches(['#', ';']).trim().is_empty())
+    }
 }
 
 /// Property represents name/value pair
 pub struct Property<'input> {
     pub name: &'input str,
     pub value: &'input str,
+    /// Byte offsets of key and value in the source. Empty if property isn't parsed from source.
+    pub span: Range<usize>,
+    /// Whether property is defined before the first section head, i.e. in the implicit root section
+    pub in_preamble: bool,
+}
+
+impl<'input> Property<'input> {
+    /// Interprets the value according to the type the specification defines for the property
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use editorconfiger::TypedValue;
+    ///
+    /// let (sections, _) = editorconfiger::parse_with_errors("root = true\n[*]\nindent_size = 4\n");
+    ///
+    /// assert_eq!(TypedValue::Bool(true), sections[0].properties[0].typed_value());
+    /// assert_eq!(TypedValue::Int(4), sections[1].properties[0].typed_value());
+    /// ```
+    #[must_use]
+    pub fn typed_value(&self) -> TypedValue<'input> {
+        properties::typed_value(self.name, self.value)
+    }
+}
+
+/// Lexical token along with its location in the source
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SpannedToken<'a> {
+    pub token: Token<'a>,
+    /// Byte offsets of the token data in the source. Covers title without square brackets for
+    /// section heads, key and value for pairs and the whole comment including its start character.
+    pub span: Range<usize>,
+}
+
+/// Splits content into tokens keeping byte offsets of each token
+pub fn tokens(content: &str) -> impl Iterator<Item = SpannedToken<'_>> {
+    lexer::tokenize(content, DEFAULT_COMMENT_PREFIXES, false).map(move |token| {
+        let span = match token {
+            Token::Head(data) | Token::Comment(data) => range_of(content, data),
+            Token::Pair(k, v) => pair_range(content, k, v),
+        };
+        SpannedToken { token, span }
+    })
+}
+
+/// Longest byte range reported for a line that cannot be recognized. Such long lines are
+/// usually generated junk so pointing at their beginning is enough to find them
+pub const MAX_SPAN_LEN: usize = 1024;
+
+/// Non blank line that lexer cannot recognize as section head, key/value pair or comment
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LexerError {
+    /// Line number (1-based)
+    pub line: usize,
+    /// Byte range of the line. Covers at most [`MAX_SPAN_LEN`] bytes
+    /// of the line and always ends at character boundary
+    pub span: Range<usize>,
+}
+
+/// Finds the first non blank line that lexer cannot recognize and returns its number (1-based)
+/// along with byte range of the line. The range covers at most [`MAX_SPAN_LEN`] bytes
+/// of the line and always ends at character boundary
+pub fn find_invalid_line(content: &str) -> Option<(usize, Range<usize>)> {
+    invalid_lines(content).next().map(|e| (e.line, e.span))
+}
+
+/// Finds all non blank lines that lexer cannot recognize
+pub fn find_invalid_lines(content: &str) -> Vec<LexerError> {
+    invalid_lines(content).collect()
+}
+
+fn invalid_lines(content: &str) -> impl Iterator<Item = LexerError> + '_ {
+    lines(content).filter_map(|(number, range)| {
+        let line = &content[range.clone()];
+        (!line.trim().is_empty() && !lexer::is_valid_line(line, DEFAULT_COMMENT_PREFIXES)).then(
+            || LexerError {
+                line: number,
+                span: range.start..capped_end(content, range.start, range.end),
+            },
+        )
+    })
+}
+
+/// Finds section head lines with unbalanced square brackets and returns their numbers (1-based)
+/// along with trimmed lines. It's either head without closing bracket like `[*.md` that
+/// isn't recognized at all or head like `[[*]` whose title has unbalanced brackets.
+pub fn find_unbalanced_heads(content: &str) -> Vec<(usize, &str)> {
+    lines(content)
+        .map(|(number, range)| (number, content[range].trim()))
+        .filter(|(_, line)| line.starts_with('['))
+        .filter(
+            |(_, line)| match lexer::tokenize(line, DEFAULT_COMMENT_PREFIXES, false).next() {
+                Some(Token::Head(title)) => !is_balanced(title),
+                _ => true,
+            },
+        )
+        .collect()
+}
+
+/// Checks whether every opening square bracket has closing pair after it and vice versa.
+/// Brackets escaped by backslash, like `\[`, are literal the same way [`crate::glob::matches`]
+/// treats them so they aren't counted
+fn is_balanced(title: &str) -> bool {
+    let mut depth = 0usize;
+    let mut chars = title.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => depth += 1,
+            ']' => match depth.checked_sub(1) {
+                Some(d) => depth = d,
+                None => return false,
+            },
+            _ => {}
+        }
+    }
+    depth == 0
+}
+
+/// Splits content into lines and returns their numbers (1-based) along with byte ranges
+/// without line endings. `\n`, `\r\n` and lone `\r` end lines.
+fn lines(content: &str) -> impl Iterator<Item = (usize, Range<usize>)> + '_ {
+    let mut number = 0;
+    let mut start = 0;
+    let mut chars = content.char_indices().peekable();
+    let mut finished = false;
+    std::iter::from_fn(move || {
+        while !finished {
+            let next = chars.next();
+            let end = match next {
+                Some((ix, '\r' | '\n')) => ix,
+                Some(_) => continue,
+                None => content.len(),
+            };
+            number += 1;
+            let line_start = start;
+            match next {
+                Some((ix, c)) => {
+                    start = ix + 1;
+                    if c == '\r' && chars.next_if(|(_, c)| *c == '\n').is_some() {
+                        start += 1;
+                    }
+                }
+                None => finished = true,
+            }
+            return Some((number, line_start..end));
+        }
+        None
+    })
+}
+
+/// Moves the end of the range closer to its start if range is longer than [`MAX_SPAN_LEN`]
+fn capped_end(content: &str, start: usize, end: usize) -> usize {
+    if end - start <= MAX_SPAN_LEN {
+        return end;
+    }
+    (start..=start + MAX_SPAN_LEN)
+        .rev()
+        .find(|ix| content.is_char_boundary(*ix))
+        .unwrap_or(end)
+}
+
+/// Calculates byte range that covers both key and value of a pair
+fn pair_range(source: &str, key: &str, value: &str) -> Range<usize> {
+    let key = range_of(source, key);
+    let value = range_of(source, value);
+    key.start..value.end.max(key.end)
+}
+
+/// Calculates byte range of `part` which must be a subslice of `source`
+pub fn range_of(source: &str, part: &str) -> Range<usize> {
+    let start = (part.as_ptr() as usize).saturating_sub(source.as_ptr() as usize);
+    start..start + part.len()
 }
 
 /// Parses input str to [`Section`] vector (array).
 /// Sections order matches original file sections order.
 pub fn parse(content: &str) -> Vec<Section<'_>> {
-    let tokens = lexer::tokenize(content);
+    parse_with_comment_prefixes(content, DEFAULT_COMMENT_PREFIXES)
+}
+
+/// Parses input str to [`Section`] vector (array) the same way as [`parse`] does
+/// and also returns all lines that cannot be recognized and so are skipped
+pub fn parse_with_errors(content: &str) -> (Vec<Section<'_>>, Vec<LexerError>) {
+    (parse(content), find_invalid_lines(content))
+}
+
+/// Parses input str to [`Section`] vector (array) the same way as [`parse`] does
+/// but comments start with any of `comment_prefixes` instead of `#` and `;`.
+/// This isn't standard but some tools generate configs with `//` comments.
+pub fn parse_with_comment_prefixes<'a>(
+    content: &'a str,
+    comment_prefixes: &'a [&'a str],
+) -> Vec<Section<'a>> {
+    parse_tokens(content, lexer::tokenize(content, comment_prefixes, false))
+}
+
+/// Parses input str to [`Section`] vector (array) the same way as [`parse`] does
+/// but `#` and `;` inside values start comments only if whitespace and some value text
+/// precede them, so `color = #fff` keeps `#fff` as the value
+pub fn parse_with_spaced_comments(content: &str) -> Vec<Section<'_>> {
+    parse_tokens(
+        content,
+        lexer::tokenize(content, DEFAULT_COMMENT_PREFIXES, true),
+    )
+}
+
+fn parse_tokens<'a>(content: &'a str, tokens: impl Iterator<Item = Token<'a>>) -> Vec<Section<'a>> {
+    let mut has_preamble = false;
 
     tokens.fold(vec![], |mut result, token| {
         match token {
             Token::Head(h) => {
                 let section = Section::<'_> {
                     title: h,
+                    span: range_of(content, h),
                     ..Default::default()
                 };
                 result.push(section);
@@ -35,16 +259,28 @@ pub fn parse(content: &str) -> Vec<Section<'_>> {
                 // so we add section with empty title
                 if result.is_empty() {
                     result.push(Section::default());
+                    has_preamble = true;
                 }
+                let in_preamble = has_preamble && result.len() == 1;
                 // because tokens stream has order as in original file
                 // it's safe to add key/value pair into the last found section defined
                 // by Token::Head or fake root section added before
                 if let Some(section) = result.last_mut() {
-                    section.properties.push(Property { name: k, value: v });
+                    section.properties.push(Property {
+                        name: k,
+                        value: v,
+                        span: pair_range(content, k, v),
+                        in_preamble,
+                    });
+                }
+            }
+            // Comments before the first section are skipped so that they
+            // don't produce fake root section
+            Token::Comment(c) => {
+                if let Some(section) = result.last_mut() {
+                    section.comments.push(c);
                 }
             }
-            // Skip comments so far
-            Token::Comment(_) => {}
         }
 
         result
@@ -54,6 +290,7 @@ pub fn parse(content: &str) -> Vec<Section<'_>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     #[test]
     fn map_several_sections_len_and_content_as_expected() {
@@ -78,6 +315,146 @@ e = f"#;
         );
     }
 
+    #[test]
+    fn parse_collects_section_comments() {
+        // Arrange
+        let config = "# file comment\n[*.md]\n\n# note\n[*.txt]\n\n[*]\na = b ; inline";
+
+        // Act
+        let contents = parse(config);
+
+        // Assert
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[0].comments, vec!["# note"]);
+        assert!(contents[0].has_meaningful_comments());
+        assert!(contents[1].comments.is_empty());
+        assert!(!contents[1].has_meaningful_comments());
+        assert_eq!(contents[2].comments, vec!["; inline"]);
+    }
+
+    #[rstest]
+    #[case("*.[ch]", true)]
+    #[case("[*", false)]
+    #[case("*]", false)]
+    #[case("\\[abc", true)]
+    #[case("abc\\]", true)]
+    #[case("[a\\]]", true)]
+    #[case("[a\\]", false)]
+    #[case("\\\\[a", false)]
+    #[trace]
+    fn is_balanced_tests(#[case] title: &str, #[case] expected: bool) {
+        // Arrange
+
+        // Act
+        let actual = is_balanced(title);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("\\[abc", "[abc")]
+    #[case("abc\\]", "abc]")]
+    #[case("[a\\]]", "]")]
+    #[case("[a\\]]", "a")]
+    #[case("\\[a\\]", "[a]")]
+    #[trace]
+    fn is_balanced_escapes_agree_with_glob(#[case] title: &str, #[case] path: &str) {
+        // Arrange
+
+        // Act
+        let balanced = is_balanced(title);
+
+        // Assert
+        assert!(balanced);
+        assert!(crate::glob::matches(title, path));
+    }
+
+    #[rstest]
+    #[case("[*.md\na = b\n", vec![(1, "[*.md")])]
+    #[case("[*]\n  [*.md # note\r\na = b\n", vec![(2, "[*.md # note")])]
+    #[case("[[*]]\na = b\n", vec![])]
+    #[case("[*.[ch]]\n[*.{md,txt}]\n", vec![])]
+    #[case("[[*]\n[*]]\n[]*[]\n", vec![(1, "[[*]"), (2, "[*]]"), (3, "[]*[]")])]
+    #[case("a = [b\n# [c\n", vec![])]
+    #[case("[\\[abc]\n[a\\]]\n[\\\\[a]\n", vec![(3, "[\\\\[a]")])]
+    #[trace]
+    fn find_unbalanced_heads_tests(#[case] content: &str, #[case] expected: Vec<(usize, &str)>) {
+        // Arrange
+
+        // Act
+        let actual = find_unbalanced_heads(content);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("[*]\na = b\n# c\n\n", None)]
+    #[case("", None)]
+    #[case("[*]\r\nab\r\n", Some((2, 5..7)))]
+    #[case("[*]\ra = b\rab", Some((3, 10..12)))]
+    #[trace]
+    fn find_invalid_line_tests(
+        #[case] content: &str,
+        #[case] expected: Option<(usize, Range<usize>)>,
+    ) {
+        // Act
+        let actual = find_invalid_line(content);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn find_invalid_line_caps_huge_line() {
+        // Arrange
+        let content = format!("[*]\n{}\nk = v\n", "\u{44b}".repeat(512 * 1024));
+
+        // Act
+        let actual = find_invalid_line(&content);
+
+        // Assert
+        assert_eq!(actual, Some((2, 4..4 + MAX_SPAN_LEN)));
+    }
+
+    #[test]
+    fn find_invalid_line_caps_at_char_boundary() {
+        // Arrange
+        let content = format!("a{}", "\u{44b}".repeat(MAX_SPAN_LEN));
+
+        // Act
+        let actual = find_invalid_line(&content);
+
+        // Assert
+        assert_eq!(actual, Some((1, 0..MAX_SPAN_LEN - 1)));
+    }
+
+    #[test]
+    fn tokens_spans_as_expected() {
+        // Arrange
+        let config = "[a]\nk = v ; c\n# x";
+
+        // Act
+        let spans: Vec<(Token, Range<usize>)> = tokens(config).map(|t| (t.token, t.span)).collect();
+
+        // Assert
+        assert_eq!(
+            spans,
+            vec![
+                (Token::Head("a"), 1..2),
+                (Token::Pair("k", "v"), 4..9),
+                (Token::Comment("; c"), 10..13),
+                (Token::Comment("# x"), 14..17),
+            ]
+        );
+        for (token, span) in spans {
+            if let Token::Head(data) | Token::Comment(data) = token {
+                assert_eq!(&config[span], data);
+            }
+        }
+    }
+
     #[test]
     fn map_test_properties_len_as_expected() {
         // Arrange
@@ -93,4 +470,73 @@ c = d"#;
         assert_eq!(contents.len(), 1);
         assert_eq!(contents[0].properties.len(), 2);
     }
+
+    #[test]
+    fn parse_with_custom_comment_prefixes() {
+        // Arrange
+        let config = "// generated\n[*]\n// indentation\na = b // inline\n";
+
+        // Act
+        let sections = parse_with_comment_prefixes(config, &["//"]);
+
+        // Assert
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].properties[0].value, "b");
+        assert_eq!(sections[0].comments, vec!["// indentation", "// inline"]);
+    }
+
+    #[rstest]
+    #[case("[*]\ncolor = #fff\n", "#fff", vec![])]
+    #[case("[*]\na = b # comment\n", "b", vec!["# comment"])]
+    #[case("[*]\na = b#c ;comment\n", "b#c", vec![";comment"])]
+    #[trace]
+    fn parse_with_spaced_comments_tests(
+        #[case] config: &str,
+        #[case] value: &str,
+        #[case] comments: Vec<&str>,
+    ) {
+        // Act
+        let sections = parse_with_spaced_comments(config);
+
+        // Assert
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].properties[0].value, value);
+        assert_eq!(sections[0].comments, comments);
+    }
+
+    #[rstest]
+    #[case("root = true\n[*]\nk = v\n", vec![true, false])]
+    #[case("[*]\nroot = true\n", vec![false])]
+    #[case("[]\nroot = true\n", vec![false])]
+    #[case("a = b\nroot = true\n[]\nc = d\n", vec![true, true, false])]
+    #[trace]
+    fn parse_marks_preamble_properties(#[case] content: &str, #[case] expected: Vec<bool>) {
+        // Act
+        let sections = parse(content);
+
+        // Assert
+        let actual: Vec<bool> = sections
+            .iter()
+            .flat_map(|s| s.properties.iter().map(|p| p.in_preamble))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_keeps_spans() {
+        // Arrange
+        let config = "root = true\n[*.md]\nk = v\n";
+
+        // Act
+        let sections = parse(config);
+
+        // Assert
+        assert_eq!(sections[0].span, 0..0);
+        assert_eq!(
+            &config[sections[0].properties[0].span.clone()],
+            "root = true"
+        );
+        assert_eq!(&config[sections[1].span.clone()], "*.md");
+        assert_eq!(&config[sections[1].properties[0].span.clone()], "k = v");
+    }
 }
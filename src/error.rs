@@ -0,0 +1,74 @@
+use std::fmt;
+use std::io;
+use std::ops::Range;
+
+/// Errors that may occur while reading or parsing .editorconfig files
+#[derive(Debug)]
+pub enum Error {
+    /// File cannot be opened or read
+    Io(io::Error),
+    /// Line that is neither section head nor key/value pair nor comment
+    Syntax {
+        /// 1-based number of the line
+        line: usize,
+        /// Byte offsets of the line in the source. At most [`crate::MAX_SPAN_LEN`] bytes long
+        span: Range<usize>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "Problem opening file or file syntax error - {e}"),
+            Error::Syntax { line, .. } => write!(f, "Invalid syntax at line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Syntax { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for Error {
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        match self {
+            Error::Io(_) => None,
+            Error::Syntax { span, .. } => Some(Box::new(std::iter::once(miette::LabeledSpan::at(
+                span.clone(),
+                "neither section, property nor comment",
+            )))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn syntax_error_display() {
+        // Arrange
+        let err = Error::Syntax {
+            line: 3,
+            span: 5..8,
+        };
+
+        // Act
+        let actual = err.to_string();
+
+        // Assert
+        assert_eq!(actual, "Invalid syntax at line 3");
+    }
+}
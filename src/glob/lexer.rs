@@ -0,0 +1,212 @@
+use std::convert::Infallible;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+/// Represents glob lexical token
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Token<'a> {
+    /// `{` that starts alternatives list
+    OpenBrace,
+    /// `}` that ends alternatives list
+    CloseBrace,
+    /// `,` that separates alternatives
+    Comma,
+    /// `[` that starts characters set
+    OpenBracket,
+    /// `]` that ends characters set
+    CloseBracket,
+    /// `*` wildcard
+    Star,
+    /// `**` wildcard that also matches path separators
+    GlobStar,
+    /// `?` single character wildcard
+    Question,
+    /// Any other characters including ones escaped by backslash that are kept along with it
+    Text(&'a str),
+}
+
+/// Token with its start and end byte offsets as lalrpop parser expects
+pub type Spanned<'a> = Result<(usize, Token<'a>, usize), Infallible>;
+
+/// Splits glob into tokens. Whitespace around braces, brackets and commas is skipped
+/// while whitespace inside text and at the ends of the input is a part of the glob.
+/// Braces are literal text if some of them have no pair, commas are literal outside braces,
+/// `[` is literal if it isn't closed by `]` or there is `/` before `]` and everything
+/// among brackets is characters set text.
+pub struct Lexer<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    previous: Option<Token<'a>>,
+    braces_paired: bool,
+    /// Number of alternatives lists opened so far and not closed yet
+    depth: usize,
+    in_class: bool,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            chars: input.char_indices().peekable(),
+            previous: None,
+            braces_paired: braces_paired(input),
+            depth: 0,
+            in_class: false,
+        }
+    }
+
+    /// Token that character at `ix` starts if it isn't a part of text
+    fn special(&self, ix: usize, c: char) -> Option<Token<'a>> {
+        if self.in_class {
+            return (c == ']').then_some(Token::CloseBracket);
+        }
+        match c {
+            '{' if self.braces_paired => Some(Token::OpenBrace),
+            '}' if self.braces_paired && self.depth > 0 => Some(Token::CloseBrace),
+            ',' if self.depth > 0 => Some(Token::Comma),
+            '[' if super::class_end(&self.input[ix + 1..]).is_some() => Some(Token::OpenBracket),
+            '*' => Some(Token::Star),
+            '?' => Some(Token::Question),
+            _ => None,
+        }
+    }
+
+    /// Whether whitespace next to the token isn't a part of a glob
+    fn is_separator(token: Option<Token>) -> bool {
+        matches!(
+            token,
+            Some(
+                Token::OpenBrace
+                    | Token::CloseBrace
+                    | Token::Comma
+                    | Token::OpenBracket
+                    | Token::CloseBracket
+            )
+        )
+    }
+
+    fn skip_separating_whitespace(&mut self) {
+        if Self::is_separator(self.previous) {
+            while self.chars.next_if(|(_, c)| is_whitespace(*c)).is_some() {}
+        }
+    }
+
+    /// Consumes characters up to the next special one and returns end of the consumed text.
+    /// Backslash and the character after it are always consumed together
+    fn consume_text(&mut self, start: usize, first: char) -> usize {
+        let mut c = first;
+        let mut end = start + c.len_utf8();
+        loop {
+            if c == '\\' {
+                if let Some((ix, escaped)) = self.chars.next() {
+                    end = ix + escaped.len_utf8();
+                }
+            }
+            match self.chars.peek().copied() {
+                Some((ix, next)) if self.special(ix, next).is_none() => {
+                    self.chars.next();
+                    c = next;
+                    end = ix + next.len_utf8();
+                }
+                _ => return end,
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Spanned<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.skip_separating_whitespace();
+        let (start, c) = self.chars.next()?;
+        let token =
+            if !self.in_class && c == '*' && self.chars.next_if(|(_, c)| *c == '*').is_some() {
+                (start, Token::GlobStar, start + 2)
+            } else if let Some(token) = self.special(start, c) {
+                (start, token, start + c.len_utf8())
+            } else {
+                let end = self.consume_text(start, c);
+                let mut text = &self.input[start..end];
+                // trailing whitespace before separator isn't a part of a glob
+                let next = self
+                    .chars
+                    .peek()
+                    .copied()
+                    .and_then(|(ix, c)| self.special(ix, c));
+                if Self::is_separator(next) {
+                    text = text.trim_end_matches(is_whitespace);
+                }
+                (start, Token::Text(text), start + text.len())
+            };
+        match token.1 {
+            Token::OpenBrace => self.depth += 1,
+            Token::CloseBrace => self.depth -= 1,
+            Token::OpenBracket => self.in_class = true,
+            Token::CloseBracket => self.in_class = false,
+            _ => {}
+        }
+        self.previous = Some(token.1);
+        Some(Ok(token))
+    }
+}
+
+/// Whether there are as many opening braces as closing ones not counting escaped braces
+fn braces_paired(input: &str) -> bool {
+    let mut balance = 0isize;
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '{' => balance += 1,
+            '}' => balance -= 1,
+            _ => {}
+        }
+    }
+    balance == 0
+}
+
+fn is_whitespace(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("?", vec![Token::Question])]
+    #[case("a?b", vec![Token::Text("a"), Token::Question, Token::Text("b")])]
+    #[case("*?", vec![Token::Star, Token::Question])]
+    #[case("?.md", vec![Token::Question, Token::Text(".md")])]
+    #[case("**", vec![Token::GlobStar])]
+    #[case("**/x", vec![Token::GlobStar, Token::Text("/x")])]
+    #[case("a/**/b", vec![Token::Text("a/"), Token::GlobStar, Token::Text("/b")])]
+    #[case("***", vec![Token::GlobStar, Token::Star])]
+    #[case("*.{e1, e2}", vec![Token::Star, Token::Text("."), Token::OpenBrace, Token::Text("e1"), Token::Comma, Token::Text("e2"), Token::CloseBrace])]
+    #[case("*.[ch]", vec![Token::Star, Token::Text("."), Token::OpenBracket, Token::Text("ch"), Token::CloseBracket])]
+    #[case(" a b ", vec![Token::Text(" a b ")])]
+    #[case("{ a , b }", vec![Token::OpenBrace, Token::Text("a"), Token::Comma, Token::Text("b"), Token::CloseBrace])]
+    #[case("a\\*b", vec![Token::Text("a\\*b")])]
+    #[case("{a\\,b,c}", vec![Token::OpenBrace, Token::Text("a\\,b"), Token::Comma, Token::Text("c"), Token::CloseBrace])]
+    #[case("{.f", vec![Token::Text("{.f")])]
+    #[case("{},b}", vec![Token::Text("{},b}")])]
+    #[case("a,b", vec![Token::Text("a,b")])]
+    #[case("[*,]", vec![Token::OpenBracket, Token::Text("*,"), Token::CloseBracket])]
+    #[case("[\\]a]", vec![Token::OpenBracket, Token::Text("\\]a"), Token::CloseBracket])]
+    #[case("a[e/]c", vec![Token::Text("a[e/]c")])]
+    #[case("a[/c", vec![Token::Text("a[/c")])]
+    #[case("", vec![])]
+    #[trace]
+    fn tokenize_tests(#[case] input: &str, #[case] expected: Vec<Token>) {
+        // Arrange
+
+        // Act
+        let actual: Vec<Token> = Lexer::new(input).map(|t| t.unwrap().1).collect();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+}
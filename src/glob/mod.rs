@@ -0,0 +1,803 @@
+lalrpop_mod!(
+    #[allow(clippy::all)]
+    #[allow(unused)]
+    #[allow(clippy::unwrap_in_result)]
+    #[allow(clippy::unwrap_used)]
+    #[allow(clippy::no_effect_underscore_binding)]
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    #[allow(clippy::cloned_instead_of_copied)]
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::too_many_lines)]
+    #[allow(clippy::match_same_arms)]
+    #[allow(clippy::uninlined_format_args)]
+    #[allow(clippy::unused_self)]
+    #[allow(clippy::needless_pass_by_value)]
+    #[allow(clippy::unnested_or_patterns)]
+    #[allow(clippy::needless_raw_string_hashes)]
+    grammar,
+    "/glob/grammar.rs"
+);
+
+mod lexer;
+
+/// Parses .editorconfig section title string and extracts all extensions into
+/// Vec. Each extension as separate element if any
+///
+/// Examples:
+///
+/// ```
+/// use editorconfiger::glob;
+///
+/// let result = glob::parse("*.{e1, e2}");
+/// assert_eq!(2, result.len());
+/// assert_eq!("*.e1", result[0]);
+/// assert_eq!("*.e2", result[1]);
+///
+/// let result = glob::parse("*.[ch]");
+/// assert_eq!(2, result.len());
+/// assert_eq!("*.c", result[0]);
+/// assert_eq!("*.h", result[1]);
+///
+/// let result = glob::parse("*");
+/// assert_eq!(1, result.len());
+/// assert_eq!("*", result[0]);
+///
+/// let result = glob::parse("test/{p1,p2}/*");
+/// assert_eq!(2, result.len());
+/// assert_eq!("test/p1/*", result[0]);
+/// assert_eq!("test/p2/*", result[1]);
+/// ```
+#[must_use]
+pub fn parse(string: &str) -> Vec<String> {
+    let parser = grammar::DefinesParser::new();
+    parser
+        .parse(lexer::Lexer::new(string))
+        .ok()
+        .filter(|globs| globs.len() <= MAX_GLOBS)
+        .unwrap_or_default()
+}
+
+/// Titles that expand to more globs are treated as ones that can't be parsed
+/// so as a few lists in a row don't multiply into enormous number of globs
+const MAX_GLOBS: usize = 1024;
+
+/// Concatenates every expansion of every segment with every expansion of segments after it.
+/// Expansions beyond [`MAX_GLOBS`] are dropped since such title is rejected by [`parse`] anyway
+fn product(segments: Vec<Vec<String>>) -> Vec<String> {
+    segments
+        .into_iter()
+        .fold(vec![String::new()], |prefixes, segment| {
+            prefixes
+                .iter()
+                .flat_map(|prefix| segment.iter().map(move |s| format!("{prefix}{s}")))
+                .take(MAX_GLOBS + 1)
+                .collect()
+        })
+}
+
+/// Joins expansions of alternatives of the same list keeping no more than [`product`] does
+fn join(mut first: Vec<String>, rest: Vec<String>) -> Vec<String> {
+    first.extend(rest);
+    first.truncate(MAX_GLOBS + 1);
+    first
+}
+
+/// Checks whether file matches section title the way editors apply sections to files.
+/// `file_path` is relative to the directory of .editorconfig file. Titles without `/`
+/// match as if prefixed with `**/`, other ones match the whole path. `*` and `?`
+/// don't match `/` but `**` does and `**/` also matches no directory at all.
+/// Classes and alternatives are expanded by [`parse`] and negated classes like `[!ab]`
+/// match any single character except listed ones and `/`. Classes too large to be expanded
+/// match any single listed character. `{n..m}` matches any integer from `n` to `m` written
+/// without leading zeros. Braces with single alternative like `{s1}`, braces without pair
+/// and brackets with `/` among them match literally. Backslash makes the next character literal.
+///
+/// Examples:
+///
+/// ```
+/// use editorconfiger::glob;
+///
+/// assert!(glob::matches("*.{js,ts}", "src/app.ts"));
+/// assert!(glob::matches("src/*.js", "src/app.js"));
+/// assert!(!glob::matches("src/*.js", "src/lib/app.js"));
+/// assert!(glob::matches("src/**.js", "src/lib/app.js"));
+/// assert!(glob::matches("{src,lib}/*.{js,ts}", "lib/app.ts"));
+/// assert!(glob::matches("{1..3}.txt", "2.txt"));
+/// assert!(glob::matches("a\\*b", "a*b"));
+/// ```
+#[must_use]
+pub fn matches(section_title: &str, file_path: &str) -> bool {
+    matches_globs(&parse(section_title), file_path)
+}
+
+/// Same as [`matches`] but for section title already expanded by [`parse`]
+fn matches_globs(globs: &[String], file_path: &str) -> bool {
+    let path = file_path.strip_prefix("./").unwrap_or(file_path);
+    globs.iter().any(|glob| {
+        if glob.contains('/') {
+            wildcard_match(glob.strip_prefix('/').unwrap_or(glob), path)
+        } else {
+            wildcard_match(&format!("**/{glob}"), path)
+        }
+    })
+}
+
+/// Checks whether every file matched by `narrow` section title is matched by `wide` one too.
+/// Every wildcard of `narrow` is replaced by text it matches and [`matches`] decides
+/// whether `wide` matches the result. Titles that expand to no globs cover nothing.
+///
+/// Examples:
+///
+/// ```
+/// use editorconfiger::glob;
+///
+/// assert!(glob::covers("*", "*.md"));
+/// assert!(glob::covers("*.{js,ts}", "src/*.js"));
+/// assert!(!glob::covers("*.md", "*"));
+/// assert!(!glob::covers("src/*", "src/**"));
+/// ```
+#[must_use]
+pub fn covers(wide: &str, narrow: &str) -> bool {
+    covers_globs(&parse(wide), &parse(narrow))
+}
+
+/// Same as [`covers`] but for section titles already expanded by [`parse`]
+/// so as titles compared with many others are parsed once
+pub(crate) fn covers_globs(wide: &[String], narrow: &[String]) -> bool {
+    !narrow.is_empty()
+        && narrow
+            .iter()
+            .flat_map(|glob| witnesses(glob))
+            .all(|path| matches_globs(wide, &path))
+}
+
+/// Character that [`witnesses`] replace wildcards with. It never appears in titles
+/// so only wildcards are able to match it
+const WITNESS: char = '\u{1}';
+
+/// Builds paths matched by the glob where every wildcard is replaced by the shortest and by a longer
+/// text it matches. Globs without slash match in any directory so they're put into one more directory too
+fn witnesses(glob: &str) -> Vec<String> {
+    let glob = glob.strip_prefix('/').unwrap_or(glob);
+    let mut result = vec![substitute(glob, false), substitute(glob, true)];
+    if !glob.contains('/') {
+        let nested: Vec<String> = result.iter().map(|p| format!("{WITNESS}/{p}")).collect();
+        result.extend(nested);
+    }
+    result
+}
+
+fn substitute(glob: &str, long: bool) -> String {
+    let mut result = String::with_capacity(glob.len());
+    for wildcard in wildcards(glob) {
+        match wildcard {
+            Wildcard::Literal(c) => result.push(c),
+            Wildcard::Any => result.push(WITNESS),
+            Wildcard::Many { cross_dirs: true } if long => {
+                result.extend([WITNESS, '/', WITNESS]);
+            }
+            Wildcard::Many { cross_dirs: false } if long => result.extend([WITNESS; 3]),
+            Wildcard::Dirs if long => result.extend([WITNESS, '/']),
+            Wildcard::Many { .. } | Wildcard::Dirs => {}
+            Wildcard::Class { negated: true, .. } => result.push(WITNESS),
+            // class too large to be expanded is replaced by its first member
+            Wildcard::Class { ranges, .. } => {
+                result.extend(ranges.first().map(|(start, _)| *start));
+            }
+            Wildcard::Number { min, .. } => result.push_str(&min.to_string()),
+        }
+    }
+    result
+}
+
+enum Wildcard {
+    Literal(char),
+    /// `?` that matches any character except `/`
+    Any,
+    /// `*` that matches any characters except `/` or `**` that matches any characters at all
+    Many {
+        cross_dirs: bool,
+    },
+    /// `**/` at the start or after `/` that matches any directories including none
+    Dirs,
+    /// `[seq]` that matches any character listed or `[!seq]` that matches any character
+    /// except ones listed. Neither matches `/`
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+    /// `{n..m}` that matches integers from `n` to `m`
+    Number {
+        min: i64,
+        max: i64,
+    },
+}
+
+/// Splits glob expanded by [`parse`] into wildcards and literal characters
+fn wildcards(glob: &str) -> Vec<Wildcard> {
+    let mut result = Vec::new();
+    let mut glob = glob;
+    if let Some(rest) = glob.strip_prefix("**/") {
+        result.push(Wildcard::Dirs);
+        glob = rest;
+    }
+    let mut chars = glob.char_indices().peekable();
+    while let Some((ix, c)) = chars.next() {
+        let rest = &glob[ix + c.len_utf8()..];
+        if c == '/' && rest.starts_with("**/") {
+            chars.nth(2);
+            result.push(Wildcard::Literal('/'));
+            result.push(Wildcard::Dirs);
+            continue;
+        }
+        let wildcard = match c {
+            '\\' => Wildcard::Literal(chars.next().map_or(c, |(_, escaped)| escaped)),
+            '*' => Wildcard::Many {
+                cross_dirs: chars.next_if(|(_, c)| *c == '*').is_some(),
+            },
+            '?' => Wildcard::Any,
+            '[' => match class_end(rest) {
+                Some(end) => {
+                    let class = &rest[..end];
+                    let skip = class.chars().count() + 1;
+                    chars.nth(skip - 1);
+                    let (class, negated) = match class.strip_prefix('!') {
+                        Some(class) => (class, true),
+                        None => (class, false),
+                    };
+                    Wildcard::Class {
+                        ranges: class_ranges(class),
+                        negated,
+                    }
+                }
+                None => Wildcard::Literal(c),
+            },
+            '{' => match numeric_range(rest) {
+                Some((min, max, end)) => {
+                    chars.nth(rest[..end].chars().count());
+                    Wildcard::Number { min, max }
+                }
+                None => Wildcard::Literal(c),
+            },
+            c => Wildcard::Literal(c),
+        };
+        result.push(wildcard);
+    }
+    result
+}
+
+/// Finds `]` that closes characters class started right before `rest` and returns its byte offset.
+/// There is no class if `]` isn't found or there is `/` before it. Escaped characters are skipped
+fn class_end(rest: &str) -> Option<usize> {
+    let mut chars = rest.char_indices();
+    while let Some((ix, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            ']' => return Some(ix),
+            '/' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses `n..m}` that follows `{` into range bounds and byte offset of `}`
+fn numeric_range(rest: &str) -> Option<(i64, i64, usize)> {
+    let end = rest.find('}')?;
+    let (first, second) = rest[..end].split_once("..")?;
+    let first: i64 = first.parse().ok()?;
+    let second: i64 = second.parse().ok()?;
+    Some((first.min(second), first.max(second), end))
+}
+
+/// Whether text is an integer from the range written without leading zeros and plus sign
+fn is_number_in(text: &[char], min: i64, max: i64) -> bool {
+    let digits = text.strip_prefix(&['-']).unwrap_or(text);
+    !digits.is_empty()
+        && digits.iter().all(char::is_ascii_digit)
+        && (digits.len() == 1 || digits[0] != '0')
+        && text
+            .iter()
+            .collect::<String>()
+            .parse::<i64>()
+            .is_ok_and(|n| (min..=max).contains(&n))
+}
+
+/// Matches path against glob expanded by [`parse`] so the only classes left are negated ones
+/// and ones too large to be expanded. File names are matched by [`crate::WalkOptions`] with it too
+pub(crate) fn wildcard_match(glob: &str, path: &str) -> bool {
+    let wildcards = wildcards(glob);
+    let path: Vec<char> = path.chars().collect();
+
+    // matched[j] is whether glob part processed so far matches first j path characters
+    let mut matched = vec![false; path.len() + 1];
+    matched[0] = true;
+    for wildcard in &wildcards {
+        let mut next = vec![false; path.len() + 1];
+        let mut matched_before = false;
+        for j in 0..=path.len() {
+            next[j] = match wildcard {
+                Wildcard::Literal(c) => j > 0 && matched[j - 1] && path[j - 1] == *c,
+                Wildcard::Any => j > 0 && matched[j - 1] && path[j - 1] != '/',
+                Wildcard::Many { cross_dirs } => {
+                    matched[j] || (j > 0 && next[j - 1] && (*cross_dirs || path[j - 1] != '/'))
+                }
+                Wildcard::Dirs => matched[j] || (j > 0 && matched_before && path[j - 1] == '/'),
+                Wildcard::Class { ranges, negated } => {
+                    j > 0
+                        && matched[j - 1]
+                        && path[j - 1] != '/'
+                        && ranges
+                            .iter()
+                            .any(|(start, end)| (*start..=*end).contains(&path[j - 1]))
+                            != *negated
+                }
+                Wildcard::Number { min, max } => {
+                    (0..j).any(|i| matched[i] && is_number_in(&path[i..j], *min, *max))
+                }
+            };
+            matched_before |= matched[j];
+        }
+        matched = next;
+    }
+    matched[path.len()]
+}
+
+/// Finds alternatives that repeat within the same braces list of section title,
+/// like `md` in `*.{md,md,txt}`. Nested lists are checked separately and every
+/// repeated alternative is returned once in order of its first repetition.
+///
+/// Examples:
+///
+/// ```
+/// use editorconfiger::glob;
+///
+/// assert_eq!(vec!["md"], glob::duplicate_members("*.{md,txt,md}"));
+/// assert!(glob::duplicate_members("{a,b}/{a,b}.c").is_empty());
+/// ```
+#[must_use]
+pub fn duplicate_members(section_title: &str) -> Vec<&str> {
+    // start of the current alternative and alternatives seen so far for every open list
+    let mut lists: Vec<(usize, Vec<&str>)> = Vec::new();
+    let mut result = Vec::new();
+    for (start, token, end) in lexer::Lexer::new(section_title).flatten() {
+        match token {
+            lexer::Token::OpenBrace => lists.push((end, Vec::new())),
+            lexer::Token::Comma | lexer::Token::CloseBrace => {
+                let Some((member_start, members)) = lists.last_mut() else {
+                    continue;
+                };
+                let member = section_title[*member_start..start].trim();
+                if members.contains(&member) && !result.contains(&member) {
+                    result.push(member);
+                }
+                members.push(member);
+                *member_start = end;
+                if token == lexer::Token::CloseBrace {
+                    lists.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+/// Checks whether section title can never match any file, i.e. it's blank or contains
+/// empty characters class `[]`. Empty braces `{}` aren't reported because they're literal
+/// and [`matches`] matches them as such.
+///
+/// Examples:
+///
+/// ```
+/// use editorconfiger::glob;
+///
+/// assert!(glob::never_matches(""));
+/// assert!(glob::never_matches("*.[]"));
+/// assert!(!glob::never_matches("*.{}"));
+/// assert!(glob::matches("*.{}", "a.{}"));
+/// assert!(!glob::never_matches("*.md"));
+/// ```
+#[must_use]
+pub fn never_matches(section_title: &str) -> bool {
+    if section_title.trim().is_empty() {
+        return true;
+    }
+    let tokens: Vec<lexer::Token> = lexer::Lexer::new(section_title)
+        .flatten()
+        .map(|(_, token, _)| token)
+        .collect();
+    tokens
+        .windows(2)
+        .any(|pair| pair == [lexer::Token::OpenBracket, lexer::Token::CloseBracket])
+}
+
+/// Classes that have more members are kept as is instead of being expanded
+const MAX_CLASS_LEN: usize = 256;
+
+/// Expands characters class (everything among square brackets) into its members.
+/// Negated class (starting with `!`) can't be expanded so it's kept as is
+/// as well as class with more than [`MAX_CLASS_LEN`] members.
+/// Members that have special meaning in globs are escaped by backslash
+fn expand_class(class: &str) -> Vec<String> {
+    if class.starts_with('!') {
+        return vec![format!("[{class}]")];
+    }
+    let ranges = class_ranges(class);
+    let len: usize = ranges
+        .iter()
+        .map(|(start, end)| *end as usize - *start as usize + 1)
+        .sum();
+    if len > MAX_CLASS_LEN {
+        return vec![format!("[{class}]")];
+    }
+    ranges
+        .into_iter()
+        .flat_map(|(start, end)| start..=end)
+        .map(|c| match c {
+            '\\' | '*' | '?' | '[' | '{' => format!("\\{c}"),
+            c => String::from(c),
+        })
+        .collect()
+}
+
+/// Splits characters class into inclusive ranges of its members.
+/// Ranges like `a-c` include all characters between bounds
+/// but ones with start greater than end (like `z-a`) are left as literal characters.
+/// Backslash makes the next character a literal member so `\-` never makes range.
+fn class_ranges(class: &str) -> Vec<(char, char)> {
+    // every member along with whether it's escaped
+    let mut chars = Vec::new();
+    let mut iter = class.chars();
+    while let Some(c) = iter.next() {
+        match c {
+            '\\' => chars.push((iter.next().unwrap_or(c), true)),
+            c => chars.push((c, false)),
+        }
+    }
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars.get(i..i + 3) {
+            Some(&[(start, _), ('-', false), (end, _)]) if start <= end => {
+                result.push((start, end));
+                i += 3;
+            }
+            _ => {
+                result.push((chars[i].0, chars[i].0));
+                i += 1;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("*", "*.md", true)]
+    #[case("*.md", "*", false)]
+    #[case("*", "**", true)]
+    #[case("**", "*", true)]
+    #[case("*.md", "src/*.md", true)]
+    #[case("src/*.md", "*.md", false)]
+    #[case("**/*.md", "*.md", true)]
+    #[case("src/**", "src/*", true)]
+    #[case("src/*", "src/**", false)]
+    #[case("src/**/*.js", "src/*.js", true)]
+    #[case("*.md", "?.md", true)]
+    #[case("?.md", "*.md", false)]
+    #[case("??.md", "*.md", false)]
+    #[case("*.{js,ts}", "*.js", true)]
+    #[case("*.js", "*.{js,ts}", false)]
+    #[case("*.[ch]", "*.c", true)]
+    #[case("*.[!c]", "*.h", true)]
+    #[case("*.h", "*.[!c]", false)]
+    #[case("*", "*.{}", true)]
+    #[case("*.md", "*.md", true)]
+    #[case("*.{js,ts}", "{src,lib}/*.{js,ts}", true)]
+    #[case("*.txt", "{1..3}.txt", true)]
+    #[case("{1..3}.txt", "*.txt", false)]
+    #[case("a\\*", "a\\*", true)]
+    #[case("a\\*", "a*", false)]
+    #[trace]
+    fn covers_tests(#[case] wide: &str, #[case] narrow: &str, #[case] expected: bool) {
+        // Act
+        let actual = covers(wide, narrow);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("", true)]
+    #[case("  ", true)]
+    #[case("*.{}", false)]
+    #[case("*.{ }", false)]
+    #[case("*.[]", true)]
+    #[case("*.[ ]", true)]
+    #[case("{a,{}}/*.c", false)]
+    #[case("*.md", false)]
+    #[case("*.{md,txt}", false)]
+    #[case("*.[ch]", false)]
+    #[case("{src,tests}/**/*.{cs,fs}", false)]
+    #[trace]
+    fn never_matches_tests(#[case] title: &str, #[case] expected: bool) {
+        // Act
+        let actual = never_matches(title);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("", "a")]
+    #[case("*.[]", "a.[]")]
+    #[case("*.[ ]", "a. ")]
+    #[case("*.{}", "a.{}")]
+    #[case("{a,{}}/*.c", "{}/b.c")]
+    #[case("*.md", "a.md")]
+    #[trace]
+    fn never_matches_agrees_with_matches(#[case] title: &str, #[case] path: &str) {
+        // Act
+        let actual = never_matches(title);
+
+        // Assert
+        assert_eq!(actual, !matches(title, path));
+    }
+
+    #[rstest]
+    #[case("*.{md,md}", vec!["md"])]
+    #[case("*.{md,txt,md,txt,md}", vec!["md", "txt"])]
+    #[case("*.{ md ,md}", vec!["md"])]
+    #[case("{a,{b,b}}/*.c", vec!["b"])]
+    #[case("{a,{a,b}}/*.c", vec![])]
+    #[case("{a,b}/{a,b}.c", vec![])]
+    #[case("*.{md,txt}", vec![])]
+    #[case("*.md", vec![])]
+    #[case("*.{md,md", vec![])]
+    #[trace]
+    fn duplicate_members_tests(#[case] title: &str, #[case] expected: Vec<&str>) {
+        // Act
+        let actual = duplicate_members(title);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("22", vec!["22"])]
+    #[case("*.e1", vec!["*.e1"])]
+    #[case("**.e1", vec!["**.e1"])]
+    #[case("*.{e1}", vec!["*.{e1}"])]
+    #[case("*.[ch]", vec!["*.c", "*.h"])]
+    #[case("f.e1", vec!["f.e1"])]
+    #[case("f1", vec!["f1"])]
+    #[case("*.*", vec!["*.*"])]
+    #[case("*", vec!["*"])]
+    #[case("**", vec!["**"])]
+    #[case("**/x", vec!["**/x"])]
+    #[case("a/**/b", vec!["a/**/b"])]
+    #[case("{a,b}/**/*.md", vec!["a/**/*.md", "b/**/*.md"])]
+    #[case("?.md", vec!["?.md"])]
+    #[case("a?b", vec!["a?b"])]
+    #[case("*?", vec!["*?"])]
+    #[case("{?.md,*.t?t}", vec!["?.md", "*.t?t"])]
+    #[case("*.{e1,e2}", vec!["*.e1", "*.e2"])]
+    #[case("*.{e1,e2,f1.e1}", vec!["*.e1", "*.e2", "*.f1.e1"])]
+    #[case("{f1.e1,{f1.e2, f1.e3}}", vec!["f1.e1", "f1.e2", "f1.e3"])]
+    #[case("{f1.e1,{f1.e2, {f1.e3, f1.e4}}}", vec!["f1.e1", "f1.e2", "f1.e3", "f1.e4"])]
+    #[case("{f1.e1,{f1.e2, *.{f1.e3, f1.e4}}}", vec!["f1.e1", "f1.e2", "*.f1.e3", "*.f1.e4"])]
+    #[case("{f1.e1,f1.[ch]}", vec!["f1.e1", "f1.c", "f1.h"])]
+    #[case("{*.e1,*.e2}", vec!["*.e1", "*.e2"])]
+    #[case("{f1,f2}.e1", vec!["f1.e1", "f2.e1"])]
+    #[case("{f1,f2}", vec!["f1", "f2"])]
+    #[case("{f1,.f2}", vec!["f1", ".f2"])]
+    #[case("{f1.e1,*.e1}", vec!["f1.e1", "*.e1"])]
+    #[case("{f1.e1,*.f1.e1}", vec!["f1.e1", "*.f1.e1"])]
+    #[case("{f1.e1,.f1.e1}", vec!["f1.e1", ".f1.e1"])]
+    #[case("test/*.{e1, e2}", vec!["test/*.e1", "test/*.e2"])]
+    #[case("test/*", vec!["test/*"])]
+    #[case("test/**/*", vec!["test/**/*"])]
+    #[case("test/{p1,p2}/*", vec!["test/p1/*", "test/p2/*"])]
+    #[case("*.[a-c]", vec!["*.a", "*.b", "*.c"])]
+    #[case("[0-9]", vec!["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"])]
+    #[case("f[a-c0-2]", vec!["fa", "fb", "fc", "f0", "f1", "f2"])]
+    #[case("f[xa-b]", vec!["fx", "fa", "fb"])]
+    #[case("f[z-a]", vec!["fz", "f-", "fa"])]
+    #[case("f[a-]", vec!["fa", "f-"])]
+    #[case("*.[!ch]", vec!["*.[!ch]"])]
+    #[case("f[!0-9].c", vec!["f[!0-9].c"])]
+    #[case("f[\u{0}-\u{10FFFF}]", vec!["f[\u{0}-\u{10FFFF}]"])]
+    #[case("a\\*b", vec!["a\\*b"])]
+    #[case("* .md", vec!["* .md"])]
+    #[case("{a b, c}.md", vec!["a b.md", "c.md"])]
+    #[case("{a,b}{c,d}", vec!["ac", "ad", "bc", "bd"])]
+    #[case("{src,lib}/*.{js,ts}", vec!["src/*.js", "src/*.ts", "lib/*.js", "lib/*.ts"])]
+    #[case("[ab][0-1]", vec!["a0", "a1", "b0", "b1"])]
+    #[case("*.{md,}", vec!["*.md", "*."])]
+    #[case("a{,b}", vec!["a", "ab"])]
+    #[case("{}", vec!["{}"])]
+    #[case("{single}.b", vec!["{single}.b"])]
+    #[case("{3..120}", vec!["{3..120}"])]
+    #[case("{a\\,b,cd}", vec!["a\\,b", "cd"])]
+    #[case("[\\]a]", vec!["]", "a"])]
+    #[case("[*?]", vec!["\\*", "\\?"])]
+    #[case("{.f", vec!["{.f"])]
+    #[case("ab[e/]cd", vec!["ab[e/]cd"])]
+    #[case("[]", vec![])]
+    #[trace]
+    fn parse_cases(#[case] input_str: &str, #[case] expected: Vec<&str>) {
+        // Act
+        let actual = parse(input_str);
+        let actual: Vec<&str> = actual.iter().map(|s| &**s).collect();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("*", "a.c", true)]
+    #[case("*", "dir/a.c", true)]
+    #[case("*.c", "dir/sub/a.c", true)]
+    #[case("a*e.c", "abcde.c", true)]
+    #[case("a?e.c", "ace.c", true)]
+    #[case("a?e.c", "ae.c", false)]
+    #[case("dir/*.c", "dir/a.c", true)]
+    #[case("/dir/*.c", "dir/a.c", true)]
+    #[case("dir/*.c", "dir/sub/a.c", false)]
+    #[case("dir/**.c", "dir/sub/a.c", true)]
+    #[case("dir/**/a.c", "dir/x/y/a.c", true)]
+    #[case("dir/**/a.c", "dir/a.c", true)]
+    #[case("dir/**/a.c", "dira.c", false)]
+    #[case("**/a.c", "a.c", true)]
+    #[case("**/a.c", "x/a.c", true)]
+    #[case("*.c", "a.c/b", false)]
+    #[case("a*", "a/b", false)]
+    #[case("a**", "a/b", true)]
+    #[case("*/b", "a/b", true)]
+    #[case("*/b", "a/x/b", false)]
+    #[case("**/b", "a/x/b", true)]
+    #[case("dir/*", "dir/a", true)]
+    #[case("dir/*", "dir/a/b", false)]
+    #[case("dir/**", "dir/a/b", true)]
+    #[case("dir/**", "dir", false)]
+    #[case("dir/*/a.c", "dir/a.c", false)]
+    #[case("dir/*/a.c", "dir/x/a.c", true)]
+    #[case("dir/*/a.c", "dir/x/y/a.c", false)]
+    #[case("dir/**/a.c", "dir/x/ya.c", false)]
+    #[case("dir**a.c", "dir/x/a.c", true)]
+    #[case("dir*a.c", "dir/x/a.c", false)]
+    #[case("x/dir/*.c", "dir/a.c", false)]
+    #[case("dir/*.c", "x/dir/a.c", false)]
+    #[case("*.[ch]", "a.h", true)]
+    #[case("*.[ch]", "a.o", false)]
+    #[case("* .md", "a .md", true)]
+    #[case("* .md", "a.md", false)]
+    #[case(" *.md ", "foo.md", false)]
+    #[case(" *.md ", " foo.md ", true)]
+    #[case("*.md\t", "foo.md", false)]
+    #[case("*.[!ch]", "a.o", true)]
+    #[case("*.[!ch]", "a.c", false)]
+    #[case("*.[!a-c]", "a.d", true)]
+    #[case("*.[!a-c]", "a.b", false)]
+    #[case("a[!x]b", "a/b", false)]
+    #[case("*.{c,h}", "dir/a.h", true)]
+    #[case("{dir,src}/*.c", "src/a.c", true)]
+    #[case("{dir,src}/*.c", "lib/a.c", false)]
+    #[case("dir/*.c", "./dir/a.c", true)]
+    #[case("*.[ch]", "a.h", true)]
+    #[case("*.{js,ts}", "a.ts", true)]
+    #[case("*.{js,ts}", "a.rs", false)]
+    #[case("f[a-\u{FFFF}].c", "fx.c", true)]
+    #[case("f[b-\u{FFFF}].c", "fa.c", false)]
+    #[case("f[!b-\u{FFFF}].c", "fa.c", true)]
+    #[case("f[\u{0}-\u{FFFF}]", "f/", false)]
+    #[case("{src,lib}/*.{js,ts}", "src/a.js", true)]
+    #[case("{src,lib}/*.{js,ts}", "lib/a.ts", true)]
+    #[case("{src,lib}/*.{js,ts}", "doc/a.js", false)]
+    #[case("{src,lib}/*.{js,ts}", "src/a.rs", false)]
+    #[case("{a,b}{c,d}", "ac", true)]
+    #[case("{a,b}{c,d}", "bd", true)]
+    #[case("{a,b}{c,d}", "ab", false)]
+    #[case("a[a-z]b[a-z]", "axby", true)]
+    #[case("a[a-z]b[a-z]", "axb1", false)]
+    #[case("[a-c]/[0-9].md", "b/7.md", true)]
+    #[case("[a-c]/[0-9].md", "d/7.md", false)]
+    #[case("*.{md,}", "a.md", true)]
+    #[case("*.{md,}", "a.", true)]
+    #[case("*.{md,}", "a.txt", false)]
+    #[case("{a,{b,c}}.l", "c.l", true)]
+    #[case("{some,a{*c,b}[ef]}.j", "abcf.j", true)]
+    #[case("{some,a{*c,b}[ef]}.j", "abg.j", false)]
+    #[case("a\\*b", "a*b", true)]
+    #[case("a\\*b", "axb", false)]
+    #[case("{a\\,b,cd}.txt", "a,b.txt", true)]
+    #[case("{e,\\},f}.txt", "}.txt", true)]
+    #[case("{g,\\\\,i}.txt", "\\.txt", true)]
+    #[case("[\\]ab].g", "].g", true)]
+    #[case("[!\\]ab].g", "].g", false)]
+    #[case("a\\[b]", "a[b]", true)]
+    #[case("a\\[b]", "ab", false)]
+    #[case("{1..3}.txt", "2.txt", true)]
+    #[case("{1..3}.txt", "4.txt", false)]
+    #[case("{3..120}", "120", true)]
+    #[case("{3..120}", "060", false)]
+    #[case("{3..120}", "5a", false)]
+    #[case("{-5..-1}", "-3", true)]
+    #[case("f{1..3}.{c,h}", "f2.h", true)]
+    #[case("{single}.b", "{single}.b", true)]
+    #[case("{single}.b", "single.b", false)]
+    #[case("{.f", "{.f", true)]
+    #[case("ab[e/]cd.i", "ab[e/]cd.i", true)]
+    #[case("ab[e/]cd.i", "abecd.i", false)]
+    #[trace]
+    fn matches_cases(#[case] title: &str, #[case] path: &str, #[case] expected: bool) {
+        // Act
+        let actual = matches(title, path);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("[\u{0}-\u{FF}]", 256)]
+    #[case("[\u{0}-\u{100}]", 1)]
+    #[case("{[a-z],[\u{100}-\u{FFFF}]}", 27)]
+    #[case("[a-d][a-d][a-d][a-d][a-d]", 1024)]
+    #[case("[a-d][a-d][a-d][a-d][a-e]", 0)]
+    #[case("{a,b}{a,b}{a,b}{a,b}{a,b}{a,b}{a,b}{a,b}{a,b}{a,b}{a,b}", 0)]
+    #[trace]
+    fn parse_class_size_cap(#[case] input_str: &str, #[case] expected: usize) {
+        // Act
+        let actual = parse(input_str);
+
+        // Assert
+        assert_eq!(actual.len(), expected);
+    }
+
+    #[rstest]
+    #[case("test/{p1,p2}/*")]
+    #[case("src/**/{a,b}/*.md")]
+    #[case("{src,test}/*.c")]
+    #[trace]
+    fn parse_uses_forward_slashes(#[case] input_str: &str) {
+        // Act
+        let actual = parse(input_str);
+
+        // Assert
+        assert!(!actual.is_empty());
+        assert!(actual.iter().all(|s| s.contains('/') && !s.contains('\\')));
+    }
+
+    #[rstest]
+    #[case(".editorconfig", ".editorconfig", true)]
+    #[case(".editorconfig", "x.editorconfig", false)]
+    #[case(".editorconfig", ".editorconfig.template", false)]
+    #[case("*.editorconfig", "x.editorconfig", true)]
+    #[case("*.editorconfig", ".editorconfig", true)]
+    #[case(".editorconfig*", ".editorconfig.template", true)]
+    #[case("editorconfig.?ample", "editorconfig.sample", true)]
+    #[case("*config*", ".editorconfig.sample", true)]
+    #[case("*.sample", "editorconfig.template", false)]
+    #[case("?", "", false)]
+    #[case("*", "", true)]
+    #[case("a\\*b", "a*b", true)]
+    #[case("a\\*b", "axb", false)]
+    #[case("ab[e/]cd", "ab[e/]cd", true)]
+    #[trace]
+    fn wildcard_match_tests(#[case] pattern: &str, #[case] name: &str, #[case] expected: bool) {
+        // Arrange
+
+        // Act
+        let actual = wildcard_match(pattern, name);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+}
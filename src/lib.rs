@@ -6,13 +6,17 @@
 pub mod console;
 mod editorconfig;
 mod enumerable;
+mod error;
 pub mod glob;
+mod properties;
+pub mod severity;
 pub mod similar;
 
 use std::fs::File;
 use std::io::prelude::*;
-use std::io::BufReader;
-use std::path::Path;
+use std::ops::{ControlFlow, Deref, Range};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 #[macro_use]
 extern crate lalrpop_util;
@@ -24,16 +28,29 @@ extern crate table_test;
 #[cfg(test)] // <-- not needed in integration tests
 extern crate rstest;
 
-use editorconfig::Section;
+pub use editorconfig::{LexerError, Property, Section, SpannedToken, Token, MAX_SPAN_LEN};
 use enumerable::IteratorExt;
+pub use error::Error;
 use jwalk::{Parallelism, WalkDir};
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+pub use properties::TypedValue;
+use severity::{Category, Severity};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 const EDITOR_CONFIG: &str = ".editorconfig";
+/// Maximum property key length in characters according to editorconfig specification
+const MAX_KEY_LEN: usize = 1024;
+/// Maximum property value length in characters according to editorconfig specification
+const MAX_VALUE_LEN: usize = 4096;
+/// Byte order mark and zero-width characters that make keys and titles silently mismatch
+const INVISIBLE_CHARS: [char; 5] = ['\u{feff}', '\u{200b}', '\u{200c}', '\u{200d}', '\u{2060}'];
+/// Inline comment directive that suppresses findings of listed categories for the property
+/// on the same line, like `a = b # editorconfiger-ignore: similar, unknown`
+const IGNORE_DIRECTIVE: &str = "editorconfiger-ignore:";
 
 /// A trait for reporting errors related to configuration validation.
 ///
-/// This trait defines a single method, `error`, which is used to report errors encountered
+/// This trait defines the `error` method, which is used to report errors encountered
 /// during the validation of configuration files. Implementors of this trait can define
 /// custom behavior for error reporting, such as logging the error to a file, printing it
 /// to the console, or sending it to an external monitoring service.
@@ -41,6 +58,8 @@ const EDITOR_CONFIG: &str = ".editorconfig";
 /// # Methods
 ///
 /// * `error` - Reports an error encountered during validation.
+/// * `recover` - Reports an error and decides whether processing of remaining files should continue.
+///   By default reports the error using `error` and continues.
 ///
 /// # Parameters
 ///
@@ -48,27 +67,93 @@ const EDITOR_CONFIG: &str = ".editorconfig";
 /// * `err` - A string slice that holds the error message.
 pub trait Errorer {
     fn error(&self, path: &str, err: &str);
+
+    fn recover(&self, path: &str, err: &str) -> ControlFlow<()> {
+        self.error(path, err);
+        ControlFlow::Continue(())
+    }
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CompareItem<'input> {
     pub key: &'input str,
     pub first_value: Option<&'input str>,
     pub second_value: Option<&'input str>,
+    pub presence: Presence,
 }
 
 impl<'input> CompareItem<'input> {
+    /// Creates item of the property with values from both files where `None`
+    /// means that the file has no such property
     #[must_use]
-    pub fn only_second(key: &'input str, second_value: &'input str) -> Self {
+    pub fn new(
+        key: &'input str,
+        first_value: Option<&'input str>,
+        second_value: Option<&'input str>,
+    ) -> Self {
         CompareItem {
             key,
-            first_value: None,
-            second_value: Some(second_value),
+            first_value,
+            second_value,
+            presence: Presence {
+                in_first: first_value.is_some(),
+                in_second: second_value.is_some(),
+            },
         }
     }
+
+    #[must_use]
+    pub fn only_second(key: &'input str, second_value: &'input str) -> Self {
+        Self::new(key, None, Some(second_value))
+    }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// Tells in which of compared files the property is defined at all,
+/// so that missing property can be told apart from the one with empty value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Presence {
+    pub in_first: bool,
+    pub in_second: bool,
+}
+
+impl Presence {
+    /// Whether the property is defined in both files
+    #[must_use]
+    pub fn in_both(&self) -> bool {
+        self.in_first && self.in_second
+    }
+}
+
+/// Property values from several compared files. Values are in the order of compared files
+/// and `None` means that the file has no such property in the section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NCompareItem<'input> {
+    pub key: &'input str,
+    pub values: Vec<Option<&'input str>>,
+}
+
+/// Owned version of [`CompareItem`] that doesn't borrow compared contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedCompareItem {
+    pub key: String,
+    pub first_value: Option<String>,
+    pub second_value: Option<String>,
+}
+
+impl From<CompareItem<'_>> for OwnedCompareItem {
+    fn from(item: CompareItem<'_>) -> Self {
+        Self {
+            key: item.key.to_string(),
+            first_value: item.first_value.map(str::to_string),
+            second_value: item.second_value.map(str::to_string),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ValidationState {
     Valid,
     Invalid,
@@ -84,31 +169,201 @@ pub enum ValidationState {
 /// # Fields
 ///
 /// * `path` - The path of the configuration file being validated.
+/// * `content` - The validated content so that formatters are able to point at problem locations.
+/// * `is_root` - Whether the file declares `root = true` before the first section so editors stop searching parent directories.
 /// * `duplicate_sections` - A list of sections that are duplicated within the file.
 /// * `duplicate_properties` - A map where the keys are property names and the values are vectors of sections in which the properties are duplicated.
 /// * `ext_problems` - A list of extended validation results containing details about duplicates and similar properties found in external files.
 /// * `similar_properties` - A map where the keys are property names and the values are vectors of tuples, each containing a pair of similar properties.
+/// * `overlapping_sections` - A list of section pairs where one section restricts the other to some directory so both match the same files.
+/// * `shadowed_sections` - A list of section pairs where the first section comes earlier and the second one matches all files the first one matches and some more, so its properties override ones of the first section, like `[*.md]` followed by `[*]`. This is informational and not a problem.
+/// * `equivalent_sections` - A list of section pairs that have different titles but expand to the same set of globs, like `[*.{c,h}]` and `[*.[ch]]`. A section split into several ones, like `[*.{js,jsx}]` along with `[*.js]` and `[*.jsx]`, is paired with each of its parts.
+/// * `whitespace_titles` - A list of section titles with leading or trailing whitespace that is part of the glob so such sections match only file names with the same whitespace, i.e. probably no file.
+/// * `empty_sections` - A list of sections that define no properties and contain no comments.
+/// * `invalid_values` - A map where the keys are section titles and the values are property and value pairs where the value isn't allowed by the specification.
+/// * `oversized_properties` - A list of section and property key pairs where either key or value exceeds the length limit defined by the specification.
+/// * `lexer_errors` - A list of lines that cannot be recognized as section heads, properties or comments and so are skipped by the parser. Section heads with unbalanced brackets are reported by `unbalanced_brackets` instead.
+/// * `unbalanced_brackets` - A list of line numbers (1-based) and section head lines with unbalanced square brackets, like `[*.md` that is skipped by the parser or `[[*]`.
+/// * `inconsistent_assignment_style` - A list of section and property key pairs assigned in the style used less often in the file, like `a=b` among `c = d` assignments. Filled only if enabled by [`ValidationConfig::assignment_style`].
+/// * `invalid_key_chars` - A list of property keys that contain control or other non-printable characters, like escape or replacement character left by broken UTF-8.
+/// * `tab_in_value` - A list of section and property key pairs where the value contains tab characters. Filled only if enabled by [`ValidationConfig::tab_in_value`].
+/// * `encoding_warnings` - A list of messages about encoding anomalies like BOM or zero-width characters inside section titles or property names and mixed line endings.
+/// * `strict` - Whether any problem makes the file invalid. Set from [`ValidationConfig::strict`].
+/// * `deprecated_properties` - A list of deprecated or non-standard properties paired with the properties that replace them.
+/// * `findings` - A flat list of duplicate sections, duplicate properties, invalid values and similar properties along with their positions. Positions are filled only if enabled by [`ValidationConfig::track_spans`].
+/// * `section_property_counts` - A map where the keys are section titles and the values are numbers of properties they declare. Sections with the same title are counted together. This is statistics and not a problem.
+/// * `override_conflicts` - A map where the keys are section titles and the values are tuples of property name, the value set by the closest `[*]` section before the section and the different value set by the section itself. This is informational and not a problem.
+/// * `non_canonical_keys` - A map where the keys are section titles and the values are property keys that aren't lowercase snake case paired with normalized keys, like `indentSize` and `indent_size`. Filled only if enabled by [`ValidationConfig::canonical_keys`].
+/// * `boolean_flips` - A map where the keys are section titles and the values are tuples of property name, the value set by the closest earlier section matching all files of the section, like `[*.md]` for `[docs/*.md]`, and the opposite value set by the section itself for `insert_final_newline` and `trim_trailing_whitespace`. This is informational and not a problem.
+/// * `commented_overrides` - A list of comments that look like commented out assignments of properties set earlier in the same section, like `# indent_size = 4` along with `indent_size = 2`. This is informational and not a problem.
+/// * `indent_size_tab_without_width` - A list of section titles that set `indent_size = tab` while neither the section nor `[*]` sets `tab_width`, so editors fall back to their default width. This is informational and not a problem. Filled only if enabled by [`ValidationConfig::tab_width_fallback`].
+/// * `misplaced_root` - A list of section titles and line numbers (1-based) of `root` properties defined inside sections where editors ignore them instead of before the first section.
+/// * `dead_sections` - A list of section titles that can never match any file, like `[]` or `[*.[]]`.
+/// * `duplicate_glob_members` - A map where the keys are section titles and the values are alternatives repeated within the same braces list, like `md` in `[*.{md,md,txt}]`.
+/// * `tab_width_conflicts` - A map where the keys are section titles and the values are `indent_size` and `tab_width` pairs that differ while effective `indent_style` is `tab`, so it's unclear what indentation width is meant.
+/// * `useless_properties` - A map where the keys are section titles and the values are properties that have no effect, like `tab_width` with `indent_style = space`. Filled only if enabled by [`ValidationConfig::useless_properties`].
+/// * `unknown_properties` - A map where the keys are section titles and the values are properties not defined by the specification. Filled only if enabled by [`ValidationConfig::unknown_properties`].
+/// * `incomplete_sections` - A map where the keys are section titles and the values are indentation properties defined without `indent_style`. Filled only if enabled by [`ValidationConfig::completeness`].
 ///
 /// # Example
 ///
 /// ```
-/// use std::collections::BTreeMap;
 /// use editorconfiger::ValidationResult;
 ///
 /// let validation_result = ValidationResult {
 ///     path: "path/to/config.file",
 ///     duplicate_sections: vec!["section1", "section2"],
-///     duplicate_properties: BTreeMap::new(),
-///     ext_problems: vec![],
-///     similar_properties: BTreeMap::new(),
+///     ..Default::default()
 /// };
 /// ```
+#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ValidationResult<'input> {
     pub path: &'input str,
+    pub content: &'input str,
+    pub is_root: bool,
     pub duplicate_sections: Vec<&'input str>,
     pub duplicate_properties: BTreeMap<&'input str, Vec<&'input str>>,
     pub ext_problems: Vec<ExtValidationResult<'input>>,
     pub similar_properties: BTreeMap<&'input str, Vec<(&'input str, &'input str)>>,
+    pub invalid_values: BTreeMap<&'input str, Vec<(&'input str, &'input str)>>,
+    pub overlapping_sections: Vec<(&'input str, &'input str)>,
+    pub equivalent_sections: Vec<(&'input str, &'input str)>,
+    pub incomplete_sections: BTreeMap<&'input str, Vec<&'input str>>,
+    pub oversized_properties: Vec<(&'input str, &'input str)>,
+    pub tab_in_value: Vec<(&'input str, &'input str)>,
+    pub inconsistent_assignment_style: Vec<(&'input str, &'input str)>,
+    pub invalid_key_chars: Vec<&'input str>,
+    pub lexer_errors: Vec<LexerError>,
+    pub unbalanced_brackets: Vec<(usize, &'input str)>,
+    pub empty_sections: Vec<&'input str>,
+    pub whitespace_titles: Vec<&'input str>,
+    pub encoding_warnings: Vec<String>,
+    pub unknown_properties: BTreeMap<&'input str, Vec<UnknownProperty<'input>>>,
+    pub useless_properties: BTreeMap<&'input str, Vec<&'input str>>,
+    pub tab_width_conflicts: BTreeMap<&'input str, Vec<(&'input str, &'input str)>>,
+    pub duplicate_glob_members: BTreeMap<&'input str, Vec<&'input str>>,
+    pub dead_sections: Vec<&'input str>,
+    pub misplaced_root: Vec<(&'input str, usize)>,
+    pub non_canonical_keys: BTreeMap<&'input str, Vec<(&'input str, String)>>,
+    pub deprecated_properties: Vec<(&'input str, &'static str)>,
+    pub section_property_counts: BTreeMap<&'input str, usize>,
+    pub override_conflicts: BTreeMap<&'input str, Vec<(&'input str, &'input str, &'input str)>>,
+    pub boolean_flips: BTreeMap<&'input str, Vec<(&'input str, &'input str, &'input str)>>,
+    pub commented_overrides: Vec<&'input str>,
+    pub shadowed_sections: Vec<(&'input str, &'input str)>,
+    pub indent_size_tab_without_width: Vec<&'input str>,
+    pub findings: Vec<Finding<'input>>,
+    pub strict: bool,
+}
+
+/// Kind of a [`Finding`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FindingKind {
+    DuplicateSection,
+    DuplicateProperty,
+    InvalidValue,
+    SimilarProperty,
+}
+
+/// Single validation finding that editors are able to point at
+///
+/// # Fields
+///
+/// * `kind` - What is wrong.
+/// * `section` - The title of the section the finding relates to.
+/// * `name` - The section title for duplicate sections or the property name otherwise.
+///   For similar properties it's the second property of the pair.
+/// * `span` - Position of the repeated section title or property, of the invalid value
+///   or of the similar property. `None` unless enabled by [`ValidationConfig::track_spans`].
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Finding<'input> {
+    pub kind: FindingKind,
+    pub section: &'input str,
+    pub name: &'input str,
+    pub span: Option<Span>,
+}
+
+/// Position of some text in the validated content
+///
+/// # Fields
+///
+/// * `line` - The line number (1-based).
+/// * `col` - The column number in characters (1-based).
+/// * `len` - The text length in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Span {
+    pub line: usize,
+    pub col: usize,
+    pub len: usize,
+}
+
+impl Span {
+    /// Converts byte range of `content` into line and column position
+    fn locate(content: &str, range: Range<usize>) -> Self {
+        let mut line = 1;
+        let mut line_start = 0;
+        let mut prev = None;
+        for (ix, c) in content[..range.start].char_indices() {
+            match c {
+                // \r\n is counted once when \r is met
+                '\n' if prev == Some('\r') => line_start = ix + 1,
+                '\r' | '\n' => {
+                    line += 1;
+                    line_start = ix + 1;
+                }
+                _ => {}
+            }
+            prev = Some(c);
+        }
+        Self {
+            line,
+            col: content[line_start..range.start].chars().count() + 1,
+            len: content[range].chars().count(),
+        }
+    }
+}
+
+/// Property that isn't defined by the editorconfig specification
+///
+/// # Fields
+///
+/// * `name` - The property name as it's written in the file.
+/// * `suggestion` - The well known property that was probably meant if there is a close one.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UnknownProperty<'input> {
+    pub name: &'input str,
+    pub suggestion: Option<&'static str>,
+}
+
+/// Represents the result of validating a single section in isolation
+///
+/// # Fields
+///
+/// * `title` - The section title, i.e. everything between square brackets.
+/// * `duplicate_properties` - A list of properties defined more than once.
+/// * `similar_properties` - A list of tuples, each containing a pair of similar properties.
+/// * `invalid_values` - A list of property and value pairs where the value isn't allowed by the specification.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SectionReport<'input> {
+    pub title: &'input str,
+    pub duplicate_properties: Vec<&'input str>,
+    pub similar_properties: Vec<(&'input str, &'input str)>,
+    pub invalid_values: Vec<(&'input str, &'input str)>,
+}
+
+impl SectionReport<'_> {
+    /// Whether section has no problems at all
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.duplicate_properties.is_empty()
+            && self.similar_properties.is_empty()
+            && self.invalid_values.is_empty()
+    }
 }
 
 /// Represents the result of an extensions validation process.
@@ -118,49 +373,247 @@ pub struct ValidationResult<'input> {
 /// # Fields
 ///
 /// * `ext` - A string representing the extension being validated.
-/// * `duplicates` - A list of properties that are duplicated within extension section.
+/// * `duplicates` - A list of properties that are duplicated within extension section
+///   along with values each section sets.
 /// * `similar` - A list of tuples, each containing a pair of similar properties found.
 ///
 /// # Example
 ///
 /// ```
-/// use editorconfiger::ExtValidationResult;
+/// use editorconfiger::{ExtDuplicate, ExtValidationResult};
 ///
 /// let ext_validation_result = ExtValidationResult {
 ///     ext: "extension".to_string(),
-///     duplicates: vec!["property1", "property2"],
+///     duplicates: vec![ExtDuplicate {
+///         name: "property1",
+///         values: vec![("*.{md,txt}", "value1"), ("*.md", "value2")],
+///     }],
 ///     similar: vec![("property1", "property1_similar")],
 /// };
+/// assert!(ext_validation_result.duplicates[0].is_conflicting());
 /// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ExtValidationResult<'input> {
     pub ext: String,
-    pub duplicates: Vec<&'input str>,
+    pub duplicates: Vec<ExtDuplicate<'input>>,
     pub similar: Vec<(&'input str, &'input str)>,
 }
 
+/// Property defined in several sections that match the same extension
+///
+/// # Fields
+///
+/// * `name` - The property name.
+/// * `values` - A list of tuples, each containing a section title and the property value it sets.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ExtDuplicate<'input> {
+    pub name: &'input str,
+    pub values: Vec<(&'input str, &'input str)>,
+}
+
+impl ExtDuplicate<'_> {
+    /// Whether sections set different values, i.e. the result depends on the sections order.
+    /// Otherwise the duplicate is redundant but harmless. Values of well known properties
+    /// are case-insensitive while values of other properties are compared as is.
+    #[must_use]
+    pub fn is_conflicting(&self) -> bool {
+        let first = self.values[0].1;
+        if properties::is_well_known(self.name) {
+            self.values
+                .iter()
+                .any(|(_, value)| !value.eq_ignore_ascii_case(first))
+        } else {
+            self.values.iter().any(|(_, value)| *value != first)
+        }
+    }
+}
+
 /// Property section assotiation, i.e. property and section that contain it
 struct ExtendedProperty<'input> {
     pub name: &'input str,
+    pub value: &'input str,
     pub section: &'input str,
 }
 
-impl ValidationResult<'_> {
+impl<'input> ValidationResult<'input> {
+    /// Properties set to different values by several sections that match the same extension
+    /// grouped by the extension. Unlike [`ExtValidationResult::duplicates`] it leaves out
+    /// redundant duplicates that set the same value.
+    #[must_use]
+    pub fn ext_conflicts(&self) -> BTreeMap<&str, Vec<&ExtDuplicate<'input>>> {
+        self.ext_problems
+            .iter()
+            .map(|e| {
+                let conflicts: Vec<&ExtDuplicate> =
+                    e.duplicates.iter().filter(|d| d.is_conflicting()).collect();
+                (e.ext.as_str(), conflicts)
+            })
+            .filter(|(_, conflicts)| !conflicts.is_empty())
+            .collect()
+    }
+
     #[must_use]
     pub fn state(&self) -> ValidationState {
-        ValidationState::from(self)
+        match ValidationState::from(self) {
+            ValidationState::SomeProblems if self.strict => ValidationState::Invalid,
+            state => state,
+        }
     }
 
-    fn is_ok(&self) -> bool {
-        self.duplicate_properties.is_empty()
-            && self.duplicate_sections.is_empty()
-            && self.similar_properties.is_empty()
-            && self.ext_problems.is_empty()
+    /// Whether the file is invalid, i.e. [`ValidationResult::state`] is [`ValidationState::Invalid`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use editorconfiger::ValidationResult;
+    ///
+    /// let result = ValidationResult {
+    ///     duplicate_sections: vec!["*.md"],
+    ///     ..Default::default()
+    /// };
+    /// assert!(result.has_errors());
+    /// assert!(!result.has_warnings());
+    /// ```
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.state() == ValidationState::Invalid
+    }
+
+    /// Whether the file has some problems that don't make it invalid,
+    /// i.e. [`ValidationResult::state`] is [`ValidationState::SomeProblems`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use editorconfiger::ValidationResult;
+    ///
+    /// let result = ValidationResult {
+    ///     empty_sections: vec!["*.md"],
+    ///     ..Default::default()
+    /// };
+    /// assert!(result.has_warnings());
+    /// assert!(!result.has_errors());
+    /// ```
+    #[must_use]
+    pub fn has_warnings(&self) -> bool {
+        self.state() == ValidationState::SomeProblems
+    }
+
+    /// Categories of findings present in the result. Informational fields, like
+    /// [`ValidationResult::override_conflicts`], don't belong to any category.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use editorconfiger::severity::Category;
+    /// use editorconfiger::ValidationResult;
+    ///
+    /// let result = ValidationResult {
+    ///     duplicate_sections: vec!["*.md"],
+    ///     empty_sections: vec!["*.txt"],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(vec![Category::DuplicateSections, Category::EmptySections], result.categories());
+    /// ```
+    #[must_use]
+    pub fn categories(&self) -> Vec<Category> {
+        let ext_conflicts = self
+            .ext_problems
+            .iter()
+            .any(|e| e.duplicates.iter().any(ExtDuplicate::is_conflicting));
+        [
+            (
+                Category::DuplicateSections,
+                !self.duplicate_sections.is_empty(),
+            ),
+            (
+                Category::DuplicateProperties,
+                !self.duplicate_properties.is_empty(),
+            ),
+            (Category::ExtensionConflicts, ext_conflicts),
+            (Category::ExtensionProblems, !self.ext_problems.is_empty()),
+            (
+                Category::SimilarProperties,
+                !self.similar_properties.is_empty(),
+            ),
+            (Category::InvalidValues, !self.invalid_values.is_empty()),
+            (
+                Category::OverlappingSections,
+                !self.overlapping_sections.is_empty(),
+            ),
+            (
+                Category::EquivalentSections,
+                !self.equivalent_sections.is_empty(),
+            ),
+            (
+                Category::IncompleteSections,
+                !self.incomplete_sections.is_empty(),
+            ),
+            (
+                Category::OversizedProperties,
+                !self.oversized_properties.is_empty(),
+            ),
+            (Category::TabInValue, !self.tab_in_value.is_empty()),
+            (
+                Category::InconsistentAssignmentStyle,
+                !self.inconsistent_assignment_style.is_empty(),
+            ),
+            (
+                Category::InvalidKeyChars,
+                !self.invalid_key_chars.is_empty(),
+            ),
+            (Category::LexerErrors, !self.lexer_errors.is_empty()),
+            (
+                Category::UnbalancedBrackets,
+                !self.unbalanced_brackets.is_empty(),
+            ),
+            (Category::EmptySections, !self.empty_sections.is_empty()),
+            (
+                Category::WhitespaceTitles,
+                !self.whitespace_titles.is_empty(),
+            ),
+            (
+                Category::EncodingWarnings,
+                !self.encoding_warnings.is_empty(),
+            ),
+            (
+                Category::UnknownProperties,
+                !self.unknown_properties.is_empty(),
+            ),
+            (
+                Category::UselessProperties,
+                !self.useless_properties.is_empty(),
+            ),
+            (
+                Category::TabWidthConflicts,
+                !self.tab_width_conflicts.is_empty(),
+            ),
+            (
+                Category::DuplicateGlobMembers,
+                !self.duplicate_glob_members.is_empty(),
+            ),
+            (Category::DeadSections, !self.dead_sections.is_empty()),
+            (Category::MisplacedRoot, !self.misplaced_root.is_empty()),
+            (
+                Category::NonCanonicalKeys,
+                !self.non_canonical_keys.is_empty(),
+            ),
+            (
+                Category::DeprecatedProperties,
+                !self.deprecated_properties.is_empty(),
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, present)| *present)
+        .map(|(category, _)| category)
+        .collect()
     }
 
-    fn is_invalid(&self) -> bool {
-        !self.duplicate_properties.is_empty()
-            || !self.duplicate_sections.is_empty()
-            || self.ext_problems.iter().any(|e| !e.duplicates.is_empty())
+    #[cfg(test)]
+    fn is_ok(&self) -> bool {
+        self.categories().is_empty()
     }
 }
 
@@ -170,17 +623,277 @@ impl ValidationState {
         matches!(self, ValidationState::Valid)
     }
 
-    fn from(result: &ValidationResult) -> ValidationState {
-        if result.is_ok() {
-            ValidationState::Valid
-        } else if result.is_invalid() {
-            ValidationState::Invalid
+    /// Returns the worse of two states where invalid is worse than having some problems
+    /// and having some problems is worse than valid
+    #[must_use]
+    pub fn worst(self, other: ValidationState) -> ValidationState {
+        if other.rank() > self.rank() {
+            other
         } else {
-            ValidationState::SomeProblems
+            self
+        }
+    }
+
+    fn rank(self) -> u8 {
+        match self {
+            ValidationState::Valid => 0,
+            ValidationState::SomeProblems => 1,
+            ValidationState::Invalid => 2,
+        }
+    }
+
+    /// Folds the worst severity of present finding categories into the state
+    fn from(result: &ValidationResult) -> ValidationState {
+        match result
+            .categories()
+            .into_iter()
+            .map(Category::severity)
+            .max()
+        {
+            None => ValidationState::Valid,
+            Some(Severity::Warning) => ValidationState::SomeProblems,
+            Some(Severity::Error) => ValidationState::Invalid,
         }
     }
 }
 
+/// Options that enable optional validation checks that are off by default
+/// because they're mostly stylistic.
+///
+/// # Example
+///
+/// ```
+/// use editorconfiger::ValidationConfig;
+///
+/// let config = ValidationConfig::new().completeness(true);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ValidationConfig {
+    completeness: bool,
+    unknown_properties: bool,
+    case_insensitive_globs: bool,
+    canonical_duplicate_sections: bool,
+    useless_properties: bool,
+    canonical_keys: bool,
+    tab_in_value: bool,
+    assignment_style: bool,
+    tab_width_fallback: bool,
+    line_endings: bool,
+    strict: bool,
+    track_spans: bool,
+    spaced_comments: bool,
+    deprecated_properties: Vec<(&'static str, &'static str)>,
+    recommended_properties: Option<Vec<&'static str>>,
+    min_similarity_length: Option<usize>,
+    ignore_sections: Vec<String>,
+}
+
+impl ValidationConfig {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables reporting sections that define `indent_size` or `tab_width` but lack `indent_style`
+    /// taking into account `indent_style` inherited from `[*]` section
+    #[must_use]
+    pub fn completeness(mut self, enabled: bool) -> Self {
+        self.completeness = enabled;
+        self
+    }
+
+    /// Enables reporting properties not defined by the editorconfig specification
+    /// along with the closest well known property name suggestion
+    #[must_use]
+    pub fn unknown_properties(mut self, enabled: bool) -> Self {
+        self.unknown_properties = enabled;
+        self
+    }
+
+    /// Makes cross section checks treat globs that differ only in case (like `*.md` and `*.MD`)
+    /// as the same one, which is how case insensitive file systems match files
+    #[must_use]
+    pub fn case_insensitive_globs(mut self, enabled: bool) -> Self {
+        self.case_insensitive_globs = enabled;
+        self
+    }
+
+    /// Makes sections whose titles expand to the same globs, like `[*.{a,b}]` and `[*.{b,a}]`,
+    /// reported as duplicate sections instead of equivalent ones. Duplicates are reported
+    /// under the title met first. Only literally equal titles are duplicates by default.
+    #[must_use]
+    pub fn canonical_duplicate_sections(mut self, enabled: bool) -> Self {
+        self.canonical_duplicate_sections = enabled;
+        self
+    }
+
+    /// Enables reporting `tab_width` in sections where effective `indent_style` is `space`
+    /// taking into account `indent_style` inherited from `[*]` section
+    #[must_use]
+    pub fn useless_properties(mut self, enabled: bool) -> Self {
+        self.useless_properties = enabled;
+        self
+    }
+
+    /// Enables reporting property keys that aren't lowercase snake case, like `indentSize`
+    /// or `indent-size`, along with normalized keys
+    #[must_use]
+    pub fn canonical_keys(mut self, enabled: bool) -> Self {
+        self.canonical_keys = enabled;
+        self
+    }
+
+    /// Enables reporting property values that contain tab characters inside, which usually
+    /// come from pasted content and confuse editors and diffs
+    #[must_use]
+    pub fn tab_in_value(mut self, enabled: bool) -> Self {
+        self.tab_in_value = enabled;
+        self
+    }
+
+    /// Enables reporting files that mix spaced `key = value` and unspaced `key=value` assignments.
+    /// Assignments in the style used less often are reported.
+    #[must_use]
+    pub fn assignment_style(mut self, enabled: bool) -> Self {
+        self.assignment_style = enabled;
+        self
+    }
+
+    /// Enables reporting sections that set `indent_size = tab` without `tab_width` either
+    /// in the section itself or in `[*]` section
+    #[must_use]
+    pub fn tab_width_fallback(mut self, enabled: bool) -> Self {
+        self.tab_width_fallback = enabled;
+        self
+    }
+
+    /// Enables reporting files that mix different line endings (`\n`, `\r\n` or `\r`)
+    /// which often is a merge artifact
+    #[must_use]
+    pub fn line_endings(mut self, enabled: bool) -> Self {
+        self.line_endings = enabled;
+        self
+    }
+
+    /// Makes files that have any problem invalid instead of having some problems
+    #[must_use]
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Enables calculating line and column of every [`Finding`]. It's off by default
+    /// because it requires scanning content again for each finding
+    #[must_use]
+    pub fn track_spans(mut self, enabled: bool) -> Self {
+        self.track_spans = enabled;
+        self
+    }
+
+    /// Makes `#` and `;` inside values start comments only if whitespace and some value text
+    /// precede them, so `color = #fff` keeps `#fff` as the value while `a = b # note` is still
+    /// `b` followed by a comment
+    #[must_use]
+    pub fn spaced_comments(mut self, enabled: bool) -> Self {
+        self.spaced_comments = enabled;
+        self
+    }
+
+    /// Sets deprecated properties along with their replacements and enables reporting them.
+    /// Nothing is reported as deprecated by default
+    #[must_use]
+    pub fn deprecated_properties(mut self, pairs: Vec<(&'static str, &'static str)>) -> Self {
+        self.deprecated_properties = pairs;
+        self
+    }
+
+    /// Replaces the baseline of properties that [`missing_recommended_with`] expects `[*]` section
+    /// to set. It's `charset`, `end_of_line`, `insert_final_newline`, `indent_style` and `indent_size` by default.
+    #[must_use]
+    pub fn recommended_properties(mut self, names: Vec<&'static str>) -> Self {
+        self.recommended_properties = Some(names);
+        self
+    }
+
+    /// Sets minimum length in characters of the property name that is the suffix of another one
+    /// for them to be reported as similar. It's [`similar::DEFAULT_MIN_SUFFIX_LEN`] by default.
+    #[must_use]
+    pub fn min_similarity_length(mut self, len: usize) -> Self {
+        self.min_similarity_length = Some(len);
+        self
+    }
+
+    /// Sets section title patterns to skip during validation. A section is skipped if its title
+    /// equals a pattern or all globs the title expands to are among ones the pattern expands to.
+    /// Skipped sections take part in no check at all.
+    #[must_use]
+    pub fn ignore_sections(mut self, patterns: Vec<String>) -> Self {
+        self.ignore_sections = patterns;
+        self
+    }
+
+    fn is_ignored<'a>(&'a self, title: &'a str, cache: &mut GlobCache<'a>) -> bool {
+        self.ignore_sections.iter().any(|pattern| {
+            if pattern == title {
+                return true;
+            }
+            let globs = cache.expand(title).to_vec();
+            let ignored = cache.expand(pattern);
+            !globs.is_empty() && globs.iter().all(|g| ignored.contains(g))
+        })
+    }
+}
+
+/// Options of configurations comparison
+///
+/// # Example
+///
+/// ```
+/// use editorconfiger::CompareConfig;
+///
+/// let config = CompareConfig::new().normalize_whitespace(true);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct CompareConfig {
+    normalize_whitespace: bool,
+    ignore_keys: Vec<String>,
+}
+
+impl CompareConfig {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Makes values that differ only in internal whitespace (like `b c` and `b  c`)
+    /// be considered equal by collapsing whitespace runs into single space
+    #[must_use]
+    pub fn normalize_whitespace(mut self, enabled: bool) -> Self {
+        self.normalize_whitespace = enabled;
+        self
+    }
+
+    /// Sets property keys to leave out of comparison. A key ending with `*` matches
+    /// all keys that start with the rest of it, like `ij_*`. Keys are case insensitive.
+    /// Sections that have no properties left are left out too.
+    #[must_use]
+    pub fn ignore_keys(mut self, keys: Vec<String>) -> Self {
+        self.ignore_keys = keys;
+        self
+    }
+
+    fn is_ignored_key(&self, key: &str) -> bool {
+        self.ignore_keys
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => key
+                    .get(..prefix.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(prefix)),
+                None => key.eq_ignore_ascii_case(pattern),
+            })
+    }
+}
+
 /// Trait for formatting the results of a validation process.
 ///
 /// This trait defines a method that must be implemented to format and display the results
@@ -233,16 +946,168 @@ pub trait ValidationFormatter {
 /// }
 /// ```
 ///
-/// # Method
+/// # Methods
 ///
 /// * `format` - Formats the comparison result.
+/// * `format_labeled` - Formats the comparison result of files labeled by their paths.
+///   Labels are ignored and `format` is called by default.
 ///
 /// # Parameters
 ///
 /// * `result` - A `BTreeMap` where the keys are section names and the values are vectors of `CompareItem`
 ///              structs, each containing details about the differences found during the comparison.
+/// * `labels` - Labels of the first and the second compared files.
 pub trait ComparisonFormatter {
     fn format(&self, result: BTreeMap<&str, Vec<CompareItem>>);
+
+    fn format_labeled(&self, result: BTreeMap<&str, Vec<CompareItem>>, _labels: [&str; 2]) {
+        self.format(result);
+    }
+}
+
+/// A trait for formatting comparison results of any number of files.
+///
+/// # Method
+///
+/// * `format` - Formats the comparison result.
+///
+/// # Parameters
+///
+/// * `result` - A `BTreeMap` where the keys are section names and the values are vectors of [`NCompareItem`]
+///   structs, each containing property values from all compared files.
+pub trait NCompareFormatter {
+    fn format(&self, result: BTreeMap<&str, Vec<NCompareItem>>);
+}
+
+/// Options that control which files are picked up while traversing a directory
+/// and which directory level findings are collected
+///
+/// # Example
+///
+/// ```
+/// use editorconfiger::WalkOptions;
+///
+/// let options = WalkOptions::new().names(vec![".editorconfig".to_string(), "*.editorconfig".to_string()]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WalkOptions {
+    names: Vec<String>,
+    max_depth: Option<usize>,
+    relative_paths: bool,
+    missing_root: bool,
+    nested_root: bool,
+    timings: bool,
+    property_frequency: bool,
+    #[cfg(feature = "gzip")]
+    compressed: bool,
+}
+
+impl WalkOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets file name patterns to validate. Patterns may contain `*` (any characters sequence)
+    /// and `?` (any single character) wildcards. Only `.editorconfig` files validated by default.
+    /// A single plain name, like `.my-editorconfig`, retargets validation to files with that name.
+    #[must_use]
+    pub fn names(mut self, names: Vec<String>) -> Self {
+        self.names = names;
+        self
+    }
+
+    /// Retargets validation to files with a single plain `name`, like `.my-editorconfig`,
+    /// instead of `.editorconfig`. It's the same as passing the only name into [`WalkOptions::names`]
+    #[must_use]
+    pub fn file_name(self, name: &str) -> Self {
+        self.names(vec![name.to_string()])
+    }
+
+    /// Limits how deep directories are traversed: `0` means only files of the given directory,
+    /// `1` also files of its immediate subdirectories and so on. Traversal is unlimited by default.
+    #[must_use]
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Makes paths passed into formatters and errorers, as well as paths of directory level findings,
+    /// relative to the traversed directory. Paths are reported as walked by default.
+    #[must_use]
+    pub fn relative_paths(mut self, enabled: bool) -> Self {
+        self.relative_paths = enabled;
+        self
+    }
+
+    /// Enables reporting top-most configuration files of a walked directory tree that lack
+    /// `root = true` so editors keep searching for configuration in parent directories
+    #[must_use]
+    pub fn missing_root(mut self, enabled: bool) -> Self {
+        self.missing_root = enabled;
+        self
+    }
+
+    /// Enables reporting configuration files of a walked directory tree that declare `root = true`
+    /// although some configuration file in their parent directories declares it too,
+    /// so the nested one prematurely stops the cascade
+    #[must_use]
+    pub fn nested_root(mut self, enabled: bool) -> Self {
+        self.nested_root = enabled;
+        self
+    }
+
+    /// Enables measuring how long walking directory tree and validating each file takes.
+    /// Nothing is measured if it's disabled
+    #[must_use]
+    pub fn timings(mut self, enabled: bool) -> Self {
+        self.timings = enabled;
+        self
+    }
+
+    /// Enables counting how many times each property with each value is defined
+    /// across all configuration files of a walked directory tree.
+    /// Sections skipped by [`ValidationConfig::ignore_sections`] aren't counted
+    #[must_use]
+    pub fn property_frequency(mut self, enabled: bool) -> Self {
+        self.property_frequency = enabled;
+        self
+    }
+
+    /// Makes files that match name patterns after `.gz` extension removed validated too,
+    /// like `.editorconfig.gz`. Such files are decompressed before validation.
+    #[cfg(feature = "gzip")]
+    #[must_use]
+    pub fn compressed(mut self, enabled: bool) -> Self {
+        self.compressed = enabled;
+        self
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        let name = path.file_name().and_then(|n| n.to_str());
+        #[cfg(feature = "gzip")]
+        let name = name.map(|n| match n.strip_suffix(".gz") {
+            Some(stem) if self.compressed => stem,
+            _ => n,
+        });
+        name.is_some_and(|n| self.names.iter().any(|p| glob::wildcard_match(p, n)))
+    }
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            names: vec![EDITOR_CONFIG.to_string()],
+            max_depth: None,
+            relative_paths: false,
+            missing_root: false,
+            nested_root: false,
+            timings: false,
+            property_frequency: false,
+            #[cfg(feature = "gzip")]
+            compressed: false,
+        }
+    }
 }
 
 /// Validates all .editorconfig files in a given directory and its subdirectories.
@@ -262,8 +1127,8 @@ pub trait ComparisonFormatter {
 ///
 /// # Returns
 ///
-/// * `usize` - The number of configuration files that were validated.
-///
+/// * [`FileCounts`] - The numbers of configuration files found, validated and not read
+///   because of errors. Dereferences into the number of found files.
 ///
 /// # Implementation Details
 ///
@@ -275,23 +1140,305 @@ pub fn validate_all<V: ValidationFormatter, E: Errorer>(
     path: &str,
     formatter: &V,
     err: &E,
-) -> usize {
-    let parallelism = Parallelism::RayonNewPool(num_cpus::get_physical());
+) -> FileCounts {
+    validate_all_with(
+        path,
+        &WalkOptions::default(),
+        &ValidationConfig::default(),
+        formatter,
+        err,
+    )
+    .counts()
+}
+
+/// Validates all files that match `options` in a given directory and its subdirectories.
+///
+/// Works the same way as [`validate_all`] but files to validate are selected using
+/// name patterns from [`WalkOptions`] instead of the fixed .editorconfig file name
+/// and each file is validated using [`validate_one_with`].
+///
+/// # Parameters
+///
+/// * `path` - A string slice that holds the path to the directory to be traversed.
+/// * `options` - A reference to [`WalkOptions`] that defines which files to validate.
+/// * `config` - A reference to [`ValidationConfig`] that defines which optional checks to run.
+/// * `formatter` - A reference to an implementation of the [`ValidationFormatter`] trait,
+///   which will be used to format the validation results.
+/// * `err` - A reference to an implementation of the `Errorer` trait, which will be used
+///   to handle any errors that occur during file reading or validation.
+///   Traversal stops as soon as [`Errorer::recover`] returns [`ControlFlow::Break`].
+///
+/// # Returns
+///
+/// * [`DirectoryReport`] - The number of configuration files that were validated
+///   and directory level findings.
+pub fn validate_all_with<V: ValidationFormatter, E: Errorer>(
+    path: &str,
+    options: &WalkOptions,
+    config: &ValidationConfig,
+    formatter: &V,
+    err: &E,
+) -> DirectoryReport {
+    let mut files = Vec::new();
+    let mut worst_state = None;
+    let mut errors = 0;
+    let walk_start = options.timings.then(Instant::now);
+    let listed = list_config_files(path, options);
+    let found = listed.len();
+    let root = options.relative_paths.then(|| Path::new(path));
+    let mut timings = walk_start.map(|start| Timings {
+        walk: start.elapsed(),
+        ..Default::default()
+    });
+    let mut frequency = options.property_frequency.then(HashMap::new);
+    let mut roots = HashSet::new();
+    for p in listed {
+        let validation_start = timings.is_some().then(Instant::now);
+        let flow = try_validate_one(&p, root, config, formatter, err, frequency.as_mut());
+        if let (Some(t), Some(start)) = (timings.as_mut(), validation_start) {
+            let elapsed = start.elapsed();
+            t.validation += elapsed;
+            t.files.push((
+                relative_to(&p, root).to_string_lossy().into_owned(),
+                elapsed,
+            ));
+        }
+        if let ControlFlow::Continue(Some((_, true))) = flow {
+            roots.insert(p.clone());
+        }
+        files.push(p);
+        match flow {
+            ControlFlow::Continue(Some((state, _))) => {
+                worst_state = Some(worst_state.map_or(state, |w: ValidationState| w.worst(state)));
+            }
+            ControlFlow::Continue(None) => errors += 1,
+            ControlFlow::Break(()) => {
+                errors += 1;
+                break;
+            }
+        }
+    }
+
+    let is_root_file = |p: &Path| roots.contains(p);
+
+    let missing_root = if options.missing_root {
+        find_top_most(&files)
+            .into_iter()
+            .filter(|p| !is_root_file(p))
+            .map(|p| relative_to(p, root).to_string_lossy().into_owned())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let nested_root = if options.nested_root {
+        let roots: Vec<&Path> = files
+            .iter()
+            .map(PathBuf::as_path)
+            .filter(|p| is_root_file(p))
+            .collect();
+        find_nested(&roots)
+            .into_iter()
+            .map(|p| relative_to(p, root).to_string_lossy().into_owned())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    if let Some(t) = timings.as_mut() {
+        t.files
+            .sort_by_key(|(_, duration)| std::cmp::Reverse(*duration));
+    }
+
+    let property_frequency = frequency.map(sort_frequency).unwrap_or_default();
+
+    DirectoryReport {
+        found,
+        files: files.len(),
+        errors,
+        worst_state,
+        missing_root,
+        nested_root,
+        property_frequency,
+        timings,
+    }
+}
+
+/// Counts properties of the file by their name and value. Names and values of well known
+/// properties are case-insensitive so they are counted lowercased.
+/// Files are validated one by one after the walk is done so counts need no synchronization.
+fn count_properties(sections: &[Section], counts: &mut HashMap<(String, String), usize>) {
+    for sec in sections {
+        for prop in &sec.properties {
+            let name = prop.name.to_lowercase();
+            let value = if properties::is_well_known(&name) {
+                prop.value.to_lowercase()
+            } else {
+                prop.value.to_owned()
+            };
+            *counts.entry((name, value)).or_default() += 1;
+        }
+    }
+}
+
+/// Orders counted properties from the most frequent one and then by name and value
+fn sort_frequency(counts: HashMap<(String, String), usize>) -> Vec<(String, String, usize)> {
+    let mut result: Vec<(String, String, usize)> = counts
+        .into_iter()
+        .map(|((name, value), count)| (name, value, count))
+        .collect();
+    result.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| (&a.0, &a.1).cmp(&(&b.0, &b.1))));
+    result
+}
+
+/// Selects files that have no other file from `files` in any of their parent directories
+fn find_top_most(files: &[PathBuf]) -> Vec<&Path> {
+    let dirs: HashSet<&Path> = files.iter().filter_map(|f| f.parent()).collect();
+    files
+        .iter()
+        .filter(|f| {
+            f.parent()
+                .is_some_and(|dir| !dir.ancestors().skip(1).any(|a| dirs.contains(a)))
+        })
+        .map(PathBuf::as_path)
+        .collect()
+}
+
+/// Selects files that have some other file from `files` in any of their parent directories
+fn find_nested<'a>(files: &[&'a Path]) -> Vec<&'a Path> {
+    let dirs: HashSet<&Path> = files.iter().filter_map(|f| f.parent()).collect();
+    files
+        .iter()
+        .filter(|f| {
+            f.parent()
+                .is_some_and(|dir| dir.ancestors().skip(1).any(|a| dirs.contains(a)))
+        })
+        .copied()
+        .collect()
+}
+
+/// Lists all files that match `options` in a given directory and its subdirectories
+/// without reading or validating them. This is the set of files [`validate_all_with`] validates.
+///
+/// # Parameters
+///
+/// * `path` - A string slice that holds the path to the directory to be traversed.
+/// * `options` - A reference to [`WalkOptions`] that defines which files to list.
+///
+/// # Returns
+///
+/// * `Vec<PathBuf>` - Paths of the matching files.
+#[must_use]
+pub fn list_config_files(path: &str, options: &WalkOptions) -> Vec<PathBuf> {
+    let parallelism = Parallelism::RayonNewPool(num_cpus::get_physical());
 
     let root = decorate_path(path);
 
-    let iter = WalkDir::new(root)
+    let mut iter = WalkDir::new(root)
         .skip_hidden(false)
         .follow_links(false)
         .parallelism(parallelism);
+    // files of the root directory itself are at depth 1 for WalkDir
+    if let Some(depth) = options.max_depth {
+        iter = iter.max_depth(depth + 1);
+    }
     iter.into_iter()
         .filter_map(Result::ok)
         .filter(|f| f.file_type().is_file())
         .map(|f| f.path())
-        .filter(|p| p.ends_with(EDITOR_CONFIG))
-        .map(|f| f.to_str().unwrap_or("").to_string())
-        .inspect(|p| validate_one(p, formatter, err))
-        .count()
+        .filter(|p| options.matches(p))
+        .collect()
+}
+
+/// Results of validating all configuration files in a directory tree
+/// that do not belong to any single file.
+///
+/// # Fields
+///
+/// * `found` - The number of configuration files found while walking the directory tree.
+/// * `files` - The number of configuration files processed including ones that could not be read.
+///   Less than `found` only if traversal was stopped by [`Errorer::recover`].
+/// * `errors` - The number of configuration files that could not be read.
+/// * `worst_state` - The worst state among validated files or `None` if no file was validated.
+/// * `missing_root` - Paths of top-most configuration files, i.e. ones that have no other
+///   configuration file in parent directories of the walked tree, that lack `root = true`.
+///   Filled only if [`WalkOptions::missing_root`] check is enabled.
+/// * `nested_root` - Paths of configuration files that declare `root = true` while some
+///   configuration file in their parent directories of the walked tree declares it too.
+///   Filled only if [`WalkOptions::nested_root`] check is enabled.
+/// * `property_frequency` - Property names and values along with the number of their definitions
+///   in all validated files from the most frequent one.
+///   Filled only if [`WalkOptions::property_frequency`] is enabled.
+/// * `timings` - How long walking and validation took or `None` unless [`WalkOptions::timings`] is enabled.
+#[derive(Debug, Default)]
+pub struct DirectoryReport {
+    pub found: usize,
+    pub files: usize,
+    pub errors: usize,
+    pub worst_state: Option<ValidationState>,
+    pub missing_root: Vec<String>,
+    pub nested_root: Vec<String>,
+    pub property_frequency: Vec<(String, String, usize)>,
+    pub timings: Option<Timings>,
+}
+
+impl DirectoryReport {
+    /// Numbers of found, validated and not read files
+    #[must_use]
+    pub fn counts(&self) -> FileCounts {
+        FileCounts {
+            found: self.found,
+            validated: self.files - self.errors,
+            errored: self.errors,
+        }
+    }
+}
+
+/// Numbers of configuration files met while validating a directory tree
+///
+/// # Fields
+///
+/// * `found` - The number of configuration files found while walking the directory tree.
+/// * `validated` - The number of configuration files that were read and validated.
+/// * `errored` - The number of configuration files that could not be read.
+///
+/// Dereferences into `found` so it can be used as a plain number of files.
+///
+/// # Example
+///
+/// ```
+/// use editorconfiger::FileCounts;
+///
+/// let counts = FileCounts { found: 3, validated: 2, errored: 1 };
+/// assert_eq!(3, *counts);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FileCounts {
+    pub found: usize,
+    pub validated: usize,
+    pub errored: usize,
+}
+
+impl Deref for FileCounts {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.found
+    }
+}
+
+/// Wall-clock time spent validating a directory tree
+///
+/// # Fields
+///
+/// * `walk` - Time spent walking the directory tree to find configuration files.
+/// * `validation` - Cumulative time spent reading and validating the files, including formatting results.
+/// * `files` - Paths of validated files along with the time each one took, slowest first.
+#[derive(Debug, Default)]
+pub struct Timings {
+    pub walk: Duration,
+    pub validation: Duration,
+    pub files: Vec<(String, Duration)>,
 }
 
 /// Validates a single .editorconfig file.
@@ -307,9 +1454,83 @@ pub fn validate_all<V: ValidationFormatter, E: Errorer>(
 ///                 which will be used to format the validation results.
 /// * `err` - A reference to an implementation of the [`Errorer`] trait, which will be used
 ///           to handle any errors that occur during file reading or validation.
-pub fn validate_one<V: ValidationFormatter, E: Errorer>(path: &str, formatter: &V, err: &E) {
-    if let Some(c) = read_from_file(path, err) {
-        validate(&c, path, formatter);
+///
+/// # Returns
+///
+/// * `Option<ValidationState>` - The state of the validated file or `None` if it cannot be read.
+pub fn validate_one<V: ValidationFormatter, E: Errorer>(
+    path: &str,
+    formatter: &V,
+    err: &E,
+) -> Option<ValidationState> {
+    validate_one_with(path, &ValidationConfig::default(), formatter, err)
+}
+
+/// Validates a single .editorconfig file the same way as [`validate_one`] does
+/// but also runs optional checks enabled in `config`.
+///
+/// # Parameters
+///
+/// * `path` - A string slice that holds the path to the configuration file to be validated.
+/// * `config` - A reference to [`ValidationConfig`] that defines which optional checks to run.
+/// * `formatter` - A reference to an implementation of the `ValidationFormatter` trait,
+///   which will be used to format the validation results.
+/// * `err` - A reference to an implementation of the [`Errorer`] trait, which will be used
+///   to handle any errors that occur during file reading or validation.
+pub fn validate_one_with<V: ValidationFormatter, E: Errorer>(
+    path: &str,
+    config: &ValidationConfig,
+    formatter: &V,
+    err: &E,
+) -> Option<ValidationState> {
+    validate_path(path, config, formatter, err)
+}
+
+/// Validates a single .editorconfig file the same way as [`validate_one_with`] does
+/// but accepts any path including ones that aren't valid UTF-8.
+/// Such paths are converted lossily only to be passed into `formatter` and `err`.
+///
+/// # Parameters
+///
+/// * `path` - The path to the configuration file to be validated.
+/// * `config` - A reference to [`ValidationConfig`] that defines which optional checks to run.
+/// * `formatter` - A reference to an implementation of the `ValidationFormatter` trait,
+///   which will be used to format the validation results.
+/// * `err` - A reference to an implementation of the [`Errorer`] trait, which will be used
+///   to handle any errors that occur during file reading or validation.
+pub fn validate_path<P: AsRef<Path>, V: ValidationFormatter, E: Errorer>(
+    path: P,
+    config: &ValidationConfig,
+    formatter: &V,
+    err: &E,
+) -> Option<ValidationState> {
+    match try_validate_one(path.as_ref(), None, config, formatter, err, None) {
+        ControlFlow::Continue(state) => state.map(|(state, _)| state),
+        ControlFlow::Break(()) => None,
+    }
+}
+
+/// Validates a single file and tells whether validation of other files should continue.
+/// Continues with the file state and whether the file is root or with `None` if the file cannot be read.
+/// The file is reported relative to `root` if it's set.
+/// Properties of validated sections are added to `counts` if they're set.
+fn try_validate_one<V: ValidationFormatter, E: Errorer>(
+    path: &Path,
+    root: Option<&Path>,
+    config: &ValidationConfig,
+    formatter: &V,
+    err: &E,
+    counts: Option<&mut HashMap<(String, String), usize>>,
+) -> ControlFlow<(), Option<(ValidationState, bool)>> {
+    let display = display_path(relative_to(path, root));
+    match read_config(path) {
+        Ok(c) => ControlFlow::Continue(Some(validate_counting(
+            &c, &display, config, formatter, counts,
+        ))),
+        Err(e) => match err.recover(&display, &e.to_string()) {
+            ControlFlow::Continue(()) => ControlFlow::Continue(None),
+            ControlFlow::Break(()) => ControlFlow::Break(()),
+        },
     }
 }
 
@@ -332,44 +1553,264 @@ pub fn compare_files<E: Errorer, F: ComparisonFormatter>(
     path2: &str,
     err: &E,
     formatter: &F,
+) {
+    compare_files_with(path1, path2, &CompareConfig::default(), err, formatter);
+}
+
+/// Compares two .editorconfig files the same way as [`compare_files`] does
+/// but using options from `config`.
+///
+/// # Parameters
+///
+/// * `path1` - A string slice that holds the path to the first .editorconfig file to be compared.
+/// * `path2` - A string slice that holds the path to the second .editorconfig file to be compared.
+/// * `config` - A reference to [`CompareConfig`] that defines how values are compared.
+/// * `err` - A reference to an implementation of the [`Errorer`] trait, which will be used
+///   to handle any errors that occur during file reading.
+/// * `formatter` - A reference to an implementation of the [`ComparisonFormatter`] trait,
+///   which will be used to format the comparison results.
+pub fn compare_files_with<E: Errorer, F: ComparisonFormatter>(
+    path1: &str,
+    path2: &str,
+    config: &CompareConfig,
+    err: &E,
+    formatter: &F,
 ) {
     if let Some(c1) = read_from_file(path1, err) {
         if let Some(c2) = read_from_file(path2, err) {
-            compare(&c1, &c2, formatter);
+            compare_labeled_with(&c1, &c2, [path1, path2], config, formatter);
+        }
+    }
+}
+
+/// Compares any number of .editorconfig files and formats the comparison results.
+/// All properties from all files are included and values follow `paths` order.
+/// Nothing is compared if any of the files cannot be read.
+///
+/// # Parameters
+///
+/// * `paths` - Paths to the .editorconfig files to be compared.
+/// * `err` - A reference to an implementation of the [`Errorer`] trait, which will be used
+///   to handle any errors that occur during file reading.
+/// * `formatter` - A reference to an implementation of the [`NCompareFormatter`] trait,
+///   which will be used to format the comparison results.
+pub fn compare_many<E: Errorer, F: NCompareFormatter>(paths: &[&str], err: &E, formatter: &F) {
+    let contents: Option<Vec<String>> = paths.iter().map(|p| read_from_file(p, err)).collect();
+    if let Some(contents) = contents {
+        let contents: Vec<&str> = contents.iter().map(String::as_str).collect();
+        compare_many_contents(&contents, formatter);
+    }
+}
+
+/// Compares any number of .editorconfig contents the same way as [`compare_many`] does.
+///
+/// # Example
+///
+/// ```
+/// use editorconfiger::{NCompareFormatter, NCompareItem};
+/// use std::collections::BTreeMap;
+///
+/// struct Checker;
+///
+/// impl NCompareFormatter for Checker {
+///     fn format(&self, result: BTreeMap<&str, Vec<NCompareItem>>) {
+///         assert_eq!(vec![Some("b"), None, Some("c")], result["*"][0].values);
+///     }
+/// }
+///
+/// editorconfiger::compare_many_contents(&["[*]\na = b", "[*.md]\nd = e", "[*]\na = c"], &Checker);
+/// ```
+pub fn compare_many_contents<F: NCompareFormatter>(contents: &[&str], formatter: &F) {
+    let parsed: Vec<Vec<Section>> = contents.iter().map(|c| editorconfig::parse(c)).collect();
+    let maps: Vec<SectionsMap> = parsed
+        .iter()
+        .map(|sections| map_sections(sections, &CompareConfig::default()))
+        .collect();
+
+    let mut result: BTreeMap<&str, BTreeMap<&str, Vec<Option<&str>>>> = BTreeMap::new();
+    for (i, map) in maps.iter().enumerate() {
+        for (title, properties) in map {
+            let section = result.entry(title).or_default();
+            for (key, value) in properties {
+                section.entry(key).or_insert_with(|| vec![None; maps.len()])[i] = Some(value);
+            }
+        }
+    }
+    let result = result
+        .into_iter()
+        .map(|(title, properties)| {
+            let items = properties
+                .into_iter()
+                .map(|(key, values)| NCompareItem { key, values })
+                .collect();
+            (title, items)
+        })
+        .collect();
+    formatter.format(result);
+}
+
+/// Compares .editorconfig file with the effective configuration defined by .editorconfig files
+/// in its parent directories and formats the comparison results.
+///
+/// Parent directories are visited from the nearest one up to the file system root and the walk stops
+/// at the first file that declares `root = true` (that file is included) just like editors do.
+/// No parent directory is visited if the target file declares `root = true` itself.
+/// Found files are merged so that properties from nearer files override the same properties
+/// from farther ones within sections with the same title.
+///
+/// The merged (effective) configuration is passed to `formatter` as the first one and the target file
+/// as the second one.
+///
+/// # Parameters
+///
+/// * `target_path` - A string slice that holds the path to the .editorconfig file to be compared.
+/// * `err` - A reference to an implementation of the [`Errorer`] trait, which will be used
+///   to handle any errors that occur during file reading.
+/// * `formatter` - A reference to an implementation of the [`ComparisonFormatter`] trait,
+///   which will be used to format the comparison results.
+pub fn compare_effective<E: Errorer, F: ComparisonFormatter>(
+    target_path: &str,
+    err: &E,
+    formatter: &F,
+) {
+    let Some(target) = read_from_file(target_path, err) else {
+        return;
+    };
+    let target_sections = editorconfig::parse(&target);
+    let parents = if is_root(&target_sections) {
+        Vec::new()
+    } else {
+        match std::fs::canonicalize(target_path) {
+            Ok(full_path) => read_parent_configs(&full_path, err),
+            Err(e) => {
+                err.error(target_path, &format!("Problem resolving file path - {e}"));
+                return;
+            }
+        }
+    };
+
+    // the farthest first so as nearer properties override farther ones
+    let mut effective = SectionsMap::new();
+    for content in parents.iter().rev() {
+        let sections = editorconfig::parse(content);
+        for (title, properties) in map_sections(&sections, &CompareConfig::default()) {
+            effective.entry(title).or_default().extend(properties);
+        }
+    }
+
+    let target = map_sections(&target_sections, &CompareConfig::default());
+    formatter.format(compare_sections(&effective, &target));
+}
+
+/// Reads .editorconfig files from parent directories of the file specified
+/// starting from the nearest one until the file that declares `root = true`
+fn read_parent_configs<E: Errorer>(file: &Path, err: &E) -> Vec<String> {
+    let mut result = Vec::new();
+    let Some(dir) = file.parent() else {
+        return result;
+    };
+    for parent in dir.ancestors().skip(1) {
+        let config = parent.join(EDITOR_CONFIG);
+        if !config.is_file() {
+            continue;
+        }
+        let Some(content) = read_from_file(&config.to_string_lossy(), err) else {
+            continue;
+        };
+        let root = is_root(&editorconfig::parse(&content));
+        result.push(content);
+        if root {
+            break;
         }
     }
+    result
 }
 
 fn read_from_file<E: Errorer>(path: &str, err: &E) -> Option<String> {
-    let conf = read_file_content(path);
+    let conf = read_config(path);
     match conf {
         Ok(c) => return Some(c),
-        Err(e) => err.error(
-            path,
-            &format!("Problem opening file or file syntax error - {e}"),
-        ),
+        Err(e) => err.error(path, &e.to_string()),
     }
     None
 }
 
-/// Reads whole file content into String
-fn read_file_content<P: AsRef<Path>>(filename: P) -> Result<String, std::io::Error> {
-    let file = File::open(filename)?;
-    let mut reader = BufReader::new(file);
+/// Reads .editorconfig file content skipping UTF-8 BOM if any
+///
+/// # Errors
+///
+/// Returns [`Error::Io`] if the file cannot be opened or read
+pub fn read_config<P: AsRef<Path>>(path: P) -> Result<String, Error> {
+    Ok(read_file_content(path)?)
+}
+
+/// Checks that every non blank line of the content is either section head,
+/// key/value pair or comment. Such lines are skipped silently by validation otherwise.
+///
+/// # Errors
+///
+/// Returns [`Error::Syntax`] describing the first line that cannot be recognized
+///
+/// # Example
+///
+/// ```
+/// let result = editorconfiger::check_syntax("[*]\nindent_size\n");
+///
+/// assert_eq!("Invalid syntax at line 2", result.unwrap_err().to_string());
+/// ```
+pub fn check_syntax(content: &str) -> Result<(), Error> {
+    match editorconfig::find_invalid_line(content) {
+        Some((line, span)) => Err(Error::Syntax { line, span }),
+        None => Ok(()),
+    }
+}
 
-    // Check if file starts with a BOM marker
-    // UTF-8: EF BB BF
-    let mut bom = [0u8; 3];
-    if reader.read_exact(&mut bom).is_ok() && &bom != b"\xEF\xBB\xBF" {
-        // No BOM so reset file pointer back to start
-        reader.rewind()?;
+/// Reads whole file content into String. Files with `.gz` extension are decompressed
+/// if `gzip` feature is enabled.
+fn read_file_content<P: AsRef<Path>>(filename: P) -> Result<String, std::io::Error> {
+    let file = File::open(&filename)?;
+    #[cfg(feature = "gzip")]
+    if is_gzip(filename.as_ref()) {
+        return read_content(flate2::read::GzDecoder::new(file));
     }
+    read_content(file)
+}
 
+/// Reads whole content into String skipping UTF-8 BOM if any
+fn read_content<R: Read>(mut reader: R) -> Result<String, std::io::Error> {
     let mut contents = String::new();
     reader.read_to_string(&mut contents)?;
+    // UTF-8 BOM is EF BB BF bytes that are decoded into single U+FEFF character
+    if contents.starts_with('\u{feff}') {
+        contents.drain(..'\u{feff}'.len_utf8());
+    }
     Ok(contents)
 }
 
+#[cfg(feature = "gzip")]
+fn is_gzip(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"))
+}
+
+/// Strips `root` from the path if it's set and the path is inside it
+fn relative_to<'a>(path: &'a Path, root: Option<&Path>) -> &'a Path {
+    root.and_then(|r| path.strip_prefix(r).ok()).unwrap_or(path)
+}
+
+/// Path shown in validation results. Compressed files are labeled with decompressed file name
+fn display_path(path: &Path) -> Cow<'_, str> {
+    #[cfg(feature = "gzip")]
+    if is_gzip(path) {
+        return path
+            .with_extension("")
+            .to_string_lossy()
+            .into_owned()
+            .into();
+    }
+    path.to_string_lossy()
+}
+
 /// Validates the content of an .editorconfig file.
 ///
 /// This function parses the provided content of an .editorconfig file, checks for duplicate
@@ -387,12 +1828,15 @@ fn read_file_content<P: AsRef<Path>>(filename: P) -> Result<String, std::io::Err
 ///
 /// 1. Parses the content into sections.
 /// 2. Iterates over each section to collect properties and their associated section titles.
-/// 3. Checks for duplicate properties within each section and stores them.
+/// 3. Checks for duplicate properties and values not allowed by the specification within each section and stores them.
 /// 4. Checks for similar properties within each section and stores them.
 /// 5. Validates properties with extended glob patterns and checks for duplicate and similar properties.
 /// 6. Checks for duplicate section titles.
-/// 7. Constructs a [`ValidationResult`] with all the gathered information.
-/// 8. Uses the provided `formatter` to format the validation results.
+/// 7. Checks for sections that match the same files because one restricts the other to some directory.
+/// 8. Checks for sections that expand to the same globs although their titles differ.
+/// 9. Constructs a [`ValidationResult`] with all the gathered information.
+/// 10. Uses the provided `formatter` to format the validation results.
+/// 11. Returns the state of the validated content.
 ///
 /// The [`ValidationResult`] includes:
 ///
@@ -401,22 +1845,254 @@ fn read_file_content<P: AsRef<Path>>(filename: P) -> Result<String, std::io::Err
 /// * A map of duplicate properties by section.
 /// * A map of similar properties by section.
 /// * A list of problems with properties that have extended glob patterns.
-pub fn validate<V: ValidationFormatter>(content: &str, path: &str, formatter: &V) {
-    let mut dup_props = BTreeMap::new();
-    let mut sim_props = BTreeMap::new();
-    let mut all_ext_props = BTreeMap::new();
+/// * A list of overlapping section pairs.
+/// * A list of equivalent section pairs.
+pub fn validate<V: ValidationFormatter>(
+    content: &str,
+    path: &str,
+    formatter: &V,
+) -> ValidationState {
+    validate_with(content, path, &ValidationConfig::default(), formatter)
+}
 
-    let sections = editorconfig::parse(content);
-    let mut section_heads = Vec::new();
+/// Validates .editorconfig snippets embedded into markdown as fenced code blocks
+/// with `editorconfig` info string (like ```` ```editorconfig ````). Each block is validated
+/// the same way as [`validate`] does and labeled by the line (1-based) its fence starts on,
+/// like `<embedded:12>`.
+///
+/// # Returns
+///
+/// The worst state among validated blocks or `None` if markdown has no such block.
+///
+/// # Example
+///
+/// ```
+/// use editorconfiger::{ValidationFormatter, ValidationResult, ValidationState};
+///
+/// struct Formatter;
+///
+/// impl ValidationFormatter for Formatter {
+///     fn format(&self, result: ValidationResult) {
+///         assert_eq!("<embedded:2>", result.path);
+///     }
+/// }
+///
+/// let markdown = "# Setup\n```editorconfig\n[*]\nindent_size = 4\n```\n";
+/// let state = editorconfiger::validate_embedded(markdown, &Formatter);
+///
+/// assert_eq!(Some(ValidationState::Valid), state);
+/// ```
+pub fn validate_embedded<V: ValidationFormatter>(
+    markdown: &str,
+    formatter: &V,
+) -> Option<ValidationState> {
+    embedded_configs(markdown)
+        .into_iter()
+        .map(|(line, content)| validate(content, &format!("<embedded:{line}>"), formatter))
+        .reduce(ValidationState::worst)
+}
 
-    for sec in &sections {
-        let props_fn = || {
-            sec.properties.iter().map(|x| ExtendedProperty {
-                name: x.name,
-                section: sec.title,
-            })
-        };
-        for e in glob::parse(sec.title) {
+/// Validates all entries of zip archive whose names end with `.editorconfig`. Every entry is
+/// validated the same way as [`validate`] does and is labeled as `archive.zip!entry/path`.
+/// Available only if `archive` feature is enabled.
+///
+/// # Parameters
+///
+/// * `path` - A string slice that holds the path to the zip archive.
+/// * `formatter` - A reference to an implementation of the `ValidationFormatter` trait,
+///   which will be used to format the validation results.
+/// * `err` - A reference to an implementation of the [`Errorer`] trait, which will be used
+///   to handle entries that cannot be read.
+///
+/// # Returns
+///
+/// The worst state among validated entries or `None` if the archive cannot be read.
+/// Entries that cannot be read are reported to `err` and don't affect the state.
+#[cfg(feature = "archive")]
+pub fn validate_archive<V: ValidationFormatter, E: Errorer>(
+    path: &str,
+    formatter: &V,
+    err: &E,
+) -> Option<ValidationState> {
+    match File::open(path) {
+        Ok(file) => validate_zip(path, file, formatter, err),
+        Err(e) => {
+            err.error(path, &e.to_string());
+            None
+        }
+    }
+}
+
+#[cfg(feature = "archive")]
+fn validate_zip<R: Read + Seek, V: ValidationFormatter, E: Errorer>(
+    path: &str,
+    reader: R,
+    formatter: &V,
+    err: &E,
+) -> Option<ValidationState> {
+    let mut archive = match zip::ZipArchive::new(reader) {
+        Ok(archive) => archive,
+        Err(e) => {
+            err.error(path, &e.to_string());
+            return None;
+        }
+    };
+    let mut state = ValidationState::Valid;
+    for ix in 0..archive.len() {
+        let entry = match archive.by_index(ix) {
+            Ok(entry) => entry,
+            Err(e) => {
+                err.error(path, &e.to_string());
+                continue;
+            }
+        };
+        if !entry.is_file() || !entry.name().ends_with(EDITOR_CONFIG) {
+            continue;
+        }
+        let label = format!("{path}!{}", entry.name());
+        match read_content(entry) {
+            Ok(content) => {
+                state = state.worst(validate(&content, &label, formatter));
+            }
+            Err(e) => err.error(&label, &e.to_string()),
+        }
+    }
+    Some(state)
+}
+
+/// Extracts contents of fenced code blocks marked as `editorconfig` along with lines (1-based)
+/// their opening fences are on. Unclosed block lasts till the end of markdown.
+fn embedded_configs(markdown: &str) -> Vec<(usize, &str)> {
+    let mut result = Vec::new();
+    // opening fence (like ``` or ~~~~) along with its line and byte offset where block
+    // content starts if the block is editorconfig one
+    let mut open: Option<(&str, Option<(usize, usize)>)> = None;
+    let mut offset = 0;
+    for (ix, line) in markdown.split_inclusive('\n').enumerate() {
+        let trimmed = line.trim();
+        let fence_len = trimmed
+            .find(|c| c != '`' && c != '~')
+            .unwrap_or(trimmed.len());
+        let fence = &trimmed[..fence_len];
+        let is_fence = fence.len() >= 3
+            && (fence.chars().all(|c| c == '`') || fence.chars().all(|c| c == '~'));
+        match open {
+            None if is_fence => {
+                let info = trimmed[fence_len..]
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default();
+                let config = info
+                    .eq_ignore_ascii_case("editorconfig")
+                    .then_some((ix + 1, offset + line.len()));
+                open = Some((fence, config));
+            }
+            Some((opening, config))
+                if is_fence && fence_len == trimmed.len() && fence.starts_with(opening) =>
+            {
+                if let Some((number, start)) = config {
+                    result.push((number, &markdown[start..offset]));
+                }
+                open = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some((_, Some((number, start)))) = open {
+        result.push((number, &markdown[start..]));
+    }
+    result
+}
+
+/// Validates the content of an .editorconfig file the same way as [`validate`] does
+/// but also runs optional checks enabled in `config`.
+///
+/// # Parameters
+///
+/// * `content` - A string slice that holds the content of the .editorconfig file to be validated.
+/// * `path` - A string slice that holds the path to the configuration file, used for reporting purposes.
+/// * `config` - A reference to [`ValidationConfig`] that defines which optional checks to run.
+/// * `formatter` - A reference to an implementation of the `ValidationFormatter` trait,
+///   which will be used to format the validation results.
+pub fn validate_with<V: ValidationFormatter>(
+    content: &str,
+    path: &str,
+    config: &ValidationConfig,
+    formatter: &V,
+) -> ValidationState {
+    validate_counting(content, path, config, formatter, None).0
+}
+
+/// Validates the content like [`validate_with`] does and adds properties of sections
+/// that aren't ignored to `counts` if they're set. Returns the state along with
+/// whether the file is root so directory level checks don't parse it once again
+fn validate_counting<V: ValidationFormatter>(
+    content: &str,
+    path: &str,
+    config: &ValidationConfig,
+    formatter: &V,
+    counts: Option<&mut HashMap<(String, String), usize>>,
+) -> (ValidationState, bool) {
+    let mut dup_props = BTreeMap::new();
+    let mut sim_props = BTreeMap::new();
+    let mut invalid_values = BTreeMap::new();
+    let mut findings = Vec::new();
+    let locate = |range: &Range<usize>| {
+        config
+            .track_spans
+            .then(|| Span::locate(content, range.clone()))
+    };
+    let mut all_ext_props = BTreeMap::new();
+    let mut oversized_properties = Vec::new();
+    let mut tab_in_value = Vec::new();
+    let mut encoding_warnings = Vec::new();
+    let mut unknown_properties = BTreeMap::new();
+    let mut non_canonical_keys = BTreeMap::new();
+    let mut deprecated_properties = Vec::new();
+
+    if config.line_endings {
+        if let Some(msg) = find_mixed_line_endings(content) {
+            encoding_warnings.push(msg);
+        }
+    }
+
+    let min_similarity_length = config
+        .min_similarity_length
+        .unwrap_or(similar::DEFAULT_MIN_SUFFIX_LEN);
+    let mut globs = GlobCache::default();
+    let (mut sections, mut lexer_errors) = if config.spaced_comments {
+        (
+            editorconfig::parse_with_spaced_comments(content),
+            editorconfig::find_invalid_lines(content),
+        )
+    } else {
+        editorconfig::parse_with_errors(content)
+    };
+    let unbalanced_brackets = editorconfig::find_unbalanced_heads(content);
+    lexer_errors.retain(|e| !unbalanced_brackets.iter().any(|(line, _)| *line == e.line));
+    if !config.ignore_sections.is_empty() {
+        sections.retain(|sec| !config.is_ignored(sec.title, &mut globs));
+    }
+    if let Some(counts) = counts {
+        count_properties(&sections, counts);
+    }
+    let mut section_heads = Vec::new();
+
+    for sec in &sections {
+        let props_fn = || {
+            sec.properties.iter().map(|x| ExtendedProperty {
+                name: x.name,
+                value: x.value,
+                section: sec.title,
+            })
+        };
+        for e in globs.expand(sec.title) {
+            let e = if config.case_insensitive_globs {
+                e.to_lowercase()
+            } else {
+                e.clone()
+            };
             all_ext_props
                 .entry(e)
                 .or_insert_with(Vec::new)
@@ -426,33 +2102,377 @@ pub fn validate<V: ValidationFormatter>(content: &str, path: &str, formatter: &V
 
         let names_fn = || sec.properties.iter().map(|item| item.name);
 
-        let mut duplicate_pops: Vec<&str> = names_fn().only_duplicates().collect();
+        let suppressions = Suppressions::new(content, sec);
+        let mut report = check_section(sec, min_similarity_length);
+        suppressions.apply(&mut report);
+        findings.extend(section_findings(sec, &report, locate));
+        append_to_btree(&mut dup_props, sec.title, &mut report.duplicate_properties);
+        append_to_btree(&mut sim_props, sec.title, &mut report.similar_properties);
+        append_to_btree(&mut invalid_values, sec.title, &mut report.invalid_values);
+
+        oversized_properties.extend(
+            sec.properties
+                .iter()
+                .filter(|p| {
+                    p.name.chars().count() > MAX_KEY_LEN || p.value.chars().count() > MAX_VALUE_LEN
+                })
+                .map(|p| (sec.title, p.name)),
+        );
+        if config.tab_in_value {
+            tab_in_value.extend(
+                sec.properties
+                    .iter()
+                    .filter(|p| p.value.contains('\t'))
+                    .map(|p| (sec.title, p.name)),
+            );
+        }
+
+        if let Some(c) = find_invisible_char(sec.title) {
+            encoding_warnings.push(format!(
+                "Section [{}] title contains U+{:04X} character",
+                sec.title.escape_debug(),
+                u32::from(c)
+            ));
+        }
+        for p in &sec.properties {
+            if let Some(c) = find_invisible_char(p.name) {
+                encoding_warnings.push(format!(
+                    "Property {} in section [{}] contains U+{:04X} character",
+                    p.name.escape_debug(),
+                    sec.title.escape_debug(),
+                    u32::from(c)
+                ));
+            }
+        }
 
-        append_to_btree(&mut dup_props, sec.title, &mut duplicate_pops);
+        let deprecated_start = deprecated_properties.len();
+        deprecated_properties.extend(names_fn().unique().filter_map(|name| {
+            properties::replacement(name, &config.deprecated_properties).map(|r| (name, r))
+        }));
+        let deprecated = &deprecated_properties[deprecated_start..];
 
-        let unique_props: Vec<&str> = names_fn().unique().collect();
+        if config.unknown_properties {
+            let mut unknown: Vec<UnknownProperty> = names_fn()
+                .unique()
+                .filter(|name| !properties::is_well_known(name))
+                // deprecated ones are reported with replacement already
+                .filter(|name| !deprecated.iter().any(|(d, _)| d == name))
+                .filter(|name| !suppressions.contains("unknown", name))
+                .map(|name| UnknownProperty {
+                    name,
+                    suggestion: properties::suggest(name),
+                })
+                .collect();
+            append_to_btree(&mut unknown_properties, sec.title, &mut unknown);
+        }
 
-        let mut similar = similar::find_suffix_pairs(&unique_props);
-        append_to_btree(&mut sim_props, sec.title, &mut similar);
+        if config.canonical_keys {
+            let mut keys: Vec<(&str, String)> = names_fn()
+                .unique()
+                .filter_map(|name| properties::canonical_key(name).map(|key| (name, key)))
+                .collect();
+            append_to_btree(&mut non_canonical_keys, sec.title, &mut keys);
+        }
     }
 
     let ext_problems = all_ext_props
         .into_iter()
-        .map(|(ext, props)| validate_extension(ext, props))
+        .map(|(ext, props)| validate_extension(ext, props, min_similarity_length))
         .filter(|r| !r.duplicates.is_empty() || !r.similar.is_empty())
         .collect();
 
-    let dup_sect = section_heads.into_iter().only_duplicates().collect();
+    let representatives = if config.canonical_duplicate_sections {
+        canonical_titles(&section_heads, &mut globs)
+    } else {
+        HashMap::new()
+    };
+    let representative = |title| representatives.get(title).copied().unwrap_or(title);
+    let mut seen_titles = HashSet::new();
+    findings.extend(
+        sections
+            .iter()
+            .filter(|sec| !seen_titles.insert(representative(sec.title)))
+            .map(|sec| Finding {
+                kind: FindingKind::DuplicateSection,
+                section: sec.title,
+                name: sec.title,
+                span: locate(&sec.span),
+            }),
+    );
+
+    let is_root = is_root(&sections);
+    let misplaced_root = find_misplaced_root(content, &sections);
+    // implicit root section has no title and exists only if there are properties before the first section
+    let empty_sections = sections
+        .iter()
+        .filter(|sec| {
+            !sec.title.is_empty() && sec.properties.is_empty() && !sec.has_meaningful_comments()
+        })
+        .map(|sec| sec.title)
+        .unique()
+        .collect();
+    let whitespace_titles = sections
+        .iter()
+        .map(|sec| sec.title)
+        .filter(|title| title.trim() != *title)
+        .unique()
+        .collect();
+    let overlapping_sections = find_overlapping_sections(&section_heads, &mut globs);
+    let shadowed_sections = find_shadowed_sections(&section_heads, &mut globs);
+    // equivalent sections are the same as canonical duplicates so they're reported once
+    let equivalent_sections = if config.canonical_duplicate_sections {
+        Vec::new()
+    } else {
+        find_equivalent_sections(&section_heads, &mut globs)
+    };
+    let dup_sect = section_heads
+        .into_iter()
+        .map(representative)
+        .only_duplicates()
+        .collect();
+    let incomplete_sections = if config.completeness {
+        find_incomplete_sections(&sections)
+    } else {
+        BTreeMap::new()
+    };
+    let section_property_counts = sections.iter().fold(BTreeMap::new(), |mut counts, sec| {
+        *counts.entry(sec.title).or_default() += sec.properties.len();
+        counts
+    });
+    let useless_properties = if config.useless_properties {
+        find_useless_properties(&sections)
+    } else {
+        BTreeMap::new()
+    };
+    let override_conflicts = find_override_conflicts(&sections);
+    let boolean_flips = find_boolean_flips(&sections, &mut globs);
+    let commented_overrides = find_commented_overrides(content, &sections);
+    let indent_size_tab_without_width = if config.tab_width_fallback {
+        find_tab_without_width(&sections)
+    } else {
+        Vec::new()
+    };
+    let tab_width_conflicts = find_tab_width_conflicts(&sections);
+    let inconsistent_assignment_style = if config.assignment_style {
+        find_inconsistent_assignments(content, &sections)
+    } else {
+        Vec::new()
+    };
+    let invalid_key_chars = sections
+        .iter()
+        .flat_map(|sec| &sec.properties)
+        .map(|p| p.name)
+        .filter(|name| name.chars().any(is_non_printable))
+        .unique()
+        .collect();
+    let duplicate_glob_members = sections
+        .iter()
+        .map(|sec| sec.title)
+        .unique()
+        .map(|title| (title, glob::duplicate_members(title)))
+        .filter(|(_, members)| !members.is_empty())
+        .collect();
+    // implicit root section has no title too but it always starts from preamble property
+    let dead_sections = sections
+        .iter()
+        .filter(|sec| !sec.properties.first().is_some_and(|p| p.in_preamble))
+        .map(|sec| sec.title)
+        .filter(|title| glob::never_matches(title))
+        .unique()
+        .collect();
 
     let result = ValidationResult {
         path,
+        content,
+        is_root,
         duplicate_sections: dup_sect,
         duplicate_properties: dup_props,
         similar_properties: sim_props,
+        invalid_values,
         ext_problems,
+        overlapping_sections,
+        equivalent_sections,
+        incomplete_sections,
+        oversized_properties,
+        tab_in_value,
+        inconsistent_assignment_style,
+        invalid_key_chars,
+        lexer_errors,
+        unbalanced_brackets,
+        empty_sections,
+        whitespace_titles,
+        encoding_warnings,
+        unknown_properties,
+        useless_properties,
+        tab_width_conflicts,
+        duplicate_glob_members,
+        dead_sections,
+        misplaced_root,
+        non_canonical_keys,
+        deprecated_properties,
+        section_property_counts,
+        override_conflicts,
+        boolean_flips,
+        commented_overrides,
+        shadowed_sections,
+        indent_size_tab_without_width,
+        findings,
+        strict: config.strict,
     };
 
+    let state = result.state();
     formatter.format(result);
+    (state, is_root)
+}
+
+/// Validates a single section in isolation, i.e. without parsing the whole file.
+/// Finds duplicate and similar properties and values that aren't allowed by the specification
+///
+/// # Parameters
+///
+/// * `title` - The section title, i.e. everything between square brackets.
+/// * `properties` - Section property name and value pairs in the order they're defined.
+///
+/// # Example
+///
+/// ```
+/// use editorconfiger::validate_section;
+///
+/// let report = validate_section("*.md", &[("indent_style", "spaces"), ("indent_size", "2")]);
+/// assert_eq!(vec![("indent_style", "spaces")], report.invalid_values);
+/// assert!(report.duplicate_properties.is_empty());
+/// ```
+#[must_use]
+pub fn validate_section<'a>(
+    title: &'a str,
+    properties: &[(&'a str, &'a str)],
+) -> SectionReport<'a> {
+    let section = Section {
+        title,
+        properties: properties
+            .iter()
+            .map(|(name, value)| Property {
+                name,
+                value,
+                span: 0..0,
+                in_preamble: false,
+            })
+            .collect(),
+        ..Default::default()
+    };
+    check_section(&section, similar::DEFAULT_MIN_SUFFIX_LEN)
+}
+
+/// Converts section report into findings pointing at repeated properties, invalid values
+/// and the second property of each similar pair
+fn section_findings<'a>(
+    sec: &Section<'a>,
+    report: &SectionReport<'a>,
+    locate: impl Fn(&Range<usize>) -> Option<Span>,
+) -> Vec<Finding<'a>> {
+    let finding = |kind, p: &Property<'a>, range: &Range<usize>| Finding {
+        kind,
+        section: sec.title,
+        name: p.name,
+        span: locate(range),
+    };
+    let mut result = Vec::new();
+    let mut seen = HashSet::new();
+    for p in &sec.properties {
+        if !seen.insert(p.name) && report.duplicate_properties.contains(&p.name) {
+            result.push(finding(FindingKind::DuplicateProperty, p, &p.span));
+        }
+    }
+    for (name, value) in &report.invalid_values {
+        if let Some(p) = sec
+            .properties
+            .iter()
+            .find(|p| p.name == *name && p.value == *value)
+        {
+            let value_span = p.span.end - p.value.len()..p.span.end;
+            result.push(finding(FindingKind::InvalidValue, p, &value_span));
+        }
+    }
+    for (_, second) in &report.similar_properties {
+        if let Some(p) = sec.properties.iter().find(|p| p.name == *second) {
+            result.push(finding(FindingKind::SimilarProperty, p, &p.span));
+        }
+    }
+    result
+}
+
+fn check_section<'a>(sec: &Section<'a>, min_similarity_length: usize) -> SectionReport<'a> {
+    SectionReport {
+        title: sec.title,
+        duplicate_properties: sec
+            .properties
+            .iter()
+            .map(|p| p.name)
+            .only_duplicates()
+            .collect(),
+        similar_properties: similar::similar_in_section(sec, min_similarity_length),
+        invalid_values: sec
+            .properties
+            .iter()
+            .filter(|p| !properties::is_valid_value(p.name, p.value))
+            .map(|p| (p.name, p.value))
+            .collect(),
+    }
+}
+
+/// Properties whose findings are suppressed by inline [`IGNORE_DIRECTIVE`] comments.
+/// Supported categories are `duplicate`, `similar`, `invalid-value` and `unknown`
+struct Suppressions<'a> {
+    /// Category and property name pairs
+    items: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Suppressions<'a> {
+    fn new(content: &str, sec: &Section<'a>) -> Self {
+        let mut items = Vec::new();
+        for comment in &sec.comments {
+            let Some((_, categories)) = comment.split_once(IGNORE_DIRECTIVE) else {
+                continue;
+            };
+            let start = editorconfig::range_of(content, comment).start;
+            // directive applies to the property that ends on the same line before the comment
+            let property = sec.properties.iter().find(|p| {
+                p.span.end <= start && !content[p.span.end..start].contains(['\n', '\r'])
+            });
+            if let Some(p) = property {
+                items.extend(
+                    categories
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|c| !c.is_empty())
+                        .map(|c| (c, p.name)),
+                );
+            }
+        }
+        Self { items }
+    }
+
+    fn contains(&self, category: &str, name: &str) -> bool {
+        self.items
+            .iter()
+            .any(|(c, n)| c.eq_ignore_ascii_case(category) && n.eq_ignore_ascii_case(name))
+    }
+
+    /// Removes suppressed findings from the section report
+    fn apply(&self, report: &mut SectionReport<'a>) {
+        if self.items.is_empty() {
+            return;
+        }
+        report
+            .duplicate_properties
+            .retain(|name| !self.contains("duplicate", name));
+        report
+            .similar_properties
+            .retain(|(a, b)| !self.contains("similar", a) && !self.contains("similar", b));
+        report
+            .invalid_values
+            .retain(|(name, _)| !self.contains("invalid-value", name));
+    }
 }
 
 fn append_to_btree<'a, T>(bree: &mut BTreeMap<&'a str, Vec<T>>, key: &'a str, data: &mut Vec<T>) {
@@ -461,34 +2481,38 @@ fn append_to_btree<'a, T>(bree: &mut BTreeMap<&'a str, Vec<T>>, key: &'a str, da
     }
 }
 
-fn validate_extension(ext: String, props: Vec<ExtendedProperty>) -> ExtValidationResult {
-    let props_sections = props.into_iter().map(|p| (p.name, p.section)).fold(
+fn validate_extension(
+    ext: String,
+    props: Vec<ExtendedProperty>,
+    min_similarity_length: usize,
+) -> ExtValidationResult {
+    let props_sections = props.into_iter().fold(
         HashMap::new(),
-        |mut h: HashMap<&str, BTreeSet<&str>>, (prop, sect)| {
-            h.entry(prop).or_default().insert(sect);
+        |mut h: HashMap<&str, BTreeMap<&str, &str>>, p| {
+            h.entry(p.name).or_default().insert(p.section, p.value);
             h
         },
     );
 
-    let duplicates: Vec<&str> = props_sections
+    let duplicates: Vec<ExtDuplicate> = props_sections
         .iter()
-        .filter_map(|(p, sections)| {
-            if (*sections).len() > 1 {
-                Some(*p)
-            } else {
-                None
-            }
+        .filter(|(_, sections)| sections.len() > 1)
+        .map(|(p, sections)| ExtDuplicate {
+            name: p,
+            values: sections.iter().map(|(s, v)| (*s, *v)).collect(),
         })
         .collect();
 
     let props: Vec<&str> = props_sections.keys().copied().collect();
-    let similar = similar::find_suffix_pairs(&props)
+    let similar = similar::find_suffix_pairs(&props, min_similarity_length)
         .into_iter()
         .filter(|(first, second)| {
-            let empty = BTreeSet::new();
+            let empty = BTreeMap::new();
             let first_sections = props_sections.get(first).unwrap_or(&empty);
             let second_sections = props_sections.get(second).unwrap_or(&empty);
-            first_sections.intersection(second_sections).count() == 0
+            first_sections
+                .keys()
+                .all(|s| !second_sections.contains_key(s))
         })
         .collect();
 
@@ -499,393 +2523,3979 @@ fn validate_extension(ext: String, props: Vec<ExtendedProperty>) -> ExtValidatio
     }
 }
 
-/// Compares the properties of two .editorconfig files contents and formats the comparison result.
-///
-/// # Arguments
-///
-/// * `content1` - A string slice holding the first .editorconfig content.
-/// * `content2` - A string slice holding the second .editorconfig content.
-/// * `formatter` - A reference to an implementation of the [`ComparisonFormatter`] trait,
-///                 which will be used to format the comparison results.
-///
-/// The function performs the following steps:
-///
-/// 1. Parses the two configuration contents into sections.
-/// 2. Maps the sections to their properties for both contents.
-/// 3. Iterates over the sections of the first content and compares each property with the corresponding section in the second content.
-/// 4. Collects the comparison results, including properties that are only in the first content, only in the second content, or in both with different values.
-/// 5. Identifies sections that are missing in the first content but present in the second content and includes their properties in the result.
-/// 6. Constructs a `BTreeMap` where the key is the section title and the value is a list of [`]CompareItem`] representing the comparison results for each property.
-/// 7. Uses the provided `formatter` to format the comparison results.
-///
-/// The resulting `BTreeMap` includes:
-///
-/// * Section titles as keys.
-/// * Lists of [`CompareItem`] for each section, representing the property comparisons.
-///   - Each [`CompareItem`] includes the property key, its value in the first content (if any), and its value in the second content (if any).
-pub fn compare<F: ComparisonFormatter>(content1: &str, content2: &str, formatter: &F) {
-    let empty = BTreeMap::<&str, &str>::new();
-
-    let f1 = editorconfig::parse(content1);
-    let f2 = editorconfig::parse(content2);
-
-    let s1_props = map_sections(&f1);
-    let s2_props = map_sections(&f2);
+/// Checks whether the preamble, i.e. properties before the first section, has `root = true`
+fn is_root(sections: &[Section]) -> bool {
+    sections.first().is_some_and(|sec| {
+        sec.properties.iter().any(|p| {
+            p.in_preamble
+                && p.name.eq_ignore_ascii_case("root")
+                && p.value.eq_ignore_ascii_case("true")
+        })
+    })
+}
 
-    let result: BTreeMap<&str, Vec<CompareItem>> = s1_props
+/// Finds `root` properties defined inside sections instead of the preamble where they're ignored
+/// and returns section titles along with line numbers (1-based)
+fn find_misplaced_root<'a>(content: &str, sections: &[Section<'a>]) -> Vec<(&'a str, usize)> {
+    sections
         .iter()
-        .map(|s1| {
-            let props1 = s1.1;
-            let props2 = s2_props.get(s1.0).unwrap_or(&empty);
-            (s1, props1, props2)
-        })
-        .map(|(s1, props1, props2)| {
-            let items: Vec<CompareItem> = props1
+        .flat_map(|sec| {
+            sec.properties
                 .iter()
-                .map(|(k1, v1)| CompareItem {
-                    key: k1,
-                    first_value: Some(v1),
-                    second_value: props2.get(k1).copied(),
-                })
-                .chain(
-                    // Properties in the section that missing in the first
-                    props2
-                        .iter()
-                        .filter(|(k, _)| !props1.contains_key(*k))
-                        .map(|(k, v)| CompareItem::only_second(k, v)),
-                )
-                .collect();
-            (*s1.0, items)
+                .filter(|p| !p.in_preamble && p.name.eq_ignore_ascii_case("root"))
+                .map(|p| (sec.title, Span::locate(content, p.span.clone()).line))
         })
-        .chain(
-            // Sections missing in the first
-            s2_props
-                .iter()
-                .filter(|s| !s1_props.contains_key(s.0))
-                .map(|s| {
-                    let items: Vec<CompareItem> =
-                        s.1.iter()
-                            .map(|p| CompareItem::only_second(p.0, p.1))
-                            .collect();
-                    (*s.0, items)
-                }),
-        )
+        .collect()
+}
+
+/// Counts line endings of each kind and describes them if more than one kind is used
+fn find_mixed_line_endings(content: &str) -> Option<String> {
+    let (mut lf, mut crlf, mut cr) = (0, 0, 0);
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' if chars.next_if_eq(&'\n').is_some() => crlf += 1,
+            '\r' => cr += 1,
+            '\n' => lf += 1,
+            _ => {}
+        }
+    }
+    let kinds = [(lf, "LF"), (crlf, "CRLF"), (cr, "CR")];
+    if kinds.iter().filter(|(count, _)| *count > 0).count() < 2 {
+        return None;
+    }
+    let counts: Vec<String> = kinds
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, name)| format!("{name}: {count}"))
         .collect();
+    Some(format!("Mixed line endings ({})", counts.join(", ")))
+}
 
-    formatter.format(result);
+fn find_invisible_char(s: &str) -> Option<char> {
+    s.chars().find(|c| INVISIBLE_CHARS.contains(c))
 }
 
-fn map_properties<'a>(s1: &'a Section<'a>) -> BTreeMap<&'a str, &'a str> {
-    s1.properties.iter().map(|p| (p.name, p.value)).collect()
+/// Whether the character can't be printed: control characters, private use and
+/// noncharacter code points or replacement character that broken UTF-8 decodes into
+fn is_non_printable(c: char) -> bool {
+    c.is_control()
+        || matches!(c, '\u{e000}'..='\u{f8ff}' | '\u{fdd0}'..='\u{fdef}' | '\u{fffd}'..='\u{ffff}')
 }
 
-fn map_sections<'a>(sections: &'a [Section<'a>]) -> HashMap<&'a str, BTreeMap<&'a str, &'a str>> {
-    let mut result = HashMap::new();
-    for s in sections {
-        result
-            .entry(s.title)
-            .or_insert(map_properties(s))
-            .extend(map_properties(s));
+/// Finds sections that define `indent_size` or `tab_width` but have no `indent_style`
+/// neither in the section itself nor in `[*]` section of the same file
+fn find_incomplete_sections<'a>(sections: &[Section<'a>]) -> BTreeMap<&'a str, Vec<&'a str>> {
+    let has_indent_style = |sec: &Section| {
+        sec.properties
+            .iter()
+            .any(|p| p.name.eq_ignore_ascii_case("indent_style"))
+    };
+    let mut result = BTreeMap::new();
+    if sections
+        .iter()
+        .any(|sec| sec.title == "*" && has_indent_style(sec))
+    {
+        return result;
+    }
+
+    for sec in sections.iter().filter(|sec| !has_indent_style(sec)) {
+        let mut dependent: Vec<&str> = sec
+            .properties
+            .iter()
+            .map(|p| p.name)
+            .filter(|name| {
+                name.eq_ignore_ascii_case("indent_size") || name.eq_ignore_ascii_case("tab_width")
+            })
+            .unique()
+            .collect();
+        append_to_btree(&mut result, sec.title, &mut dependent);
     }
     result
 }
 
-/// On Windows added trailing back slash \ if volume and colon passed so as to paths look more pleasant
-#[cfg(target_os = "windows")]
-fn decorate_path(path: &str) -> String {
-    if path.len() == 2 && path.ends_with(':') {
-        format!("{path}\\")
-    } else {
-        String::from(path)
+/// Finds `tab_width` properties in sections where effective `indent_style` is `space`
+fn find_useless_properties<'a>(sections: &[Section<'a>]) -> BTreeMap<&'a str, Vec<&'a str>> {
+    let mut result = BTreeMap::new();
+    for sec in sections {
+        let style = effective_value(sections, sec, "indent_style");
+        if !style.is_some_and(|s| s.eq_ignore_ascii_case("space")) {
+            continue;
+        }
+        let mut useless: Vec<&str> = sec
+            .properties
+            .iter()
+            .map(|p| p.name)
+            .filter(|name| name.eq_ignore_ascii_case("tab_width"))
+            .unique()
+            .collect();
+        append_to_btree(&mut result, sec.title, &mut useless);
     }
+    result
 }
 
-/// On Unix just passthrough as is
-#[cfg(not(target_os = "windows"))]
-fn decorate_path(path: &str) -> String {
-    String::from(path)
+/// Finds comments like `# indent_size = 4` that follow assignments of the same property in their sections.
+/// Comment body must be a single `key = value` pair with key that has no whitespace
+fn find_commented_overrides<'a>(content: &str, sections: &[Section<'a>]) -> Vec<&'a str> {
+    sections
+        .iter()
+        .flat_map(|sec| {
+            sec.comments.iter().copied().filter(|comment| {
+                let start = editorconfig::range_of(content, comment).start;
+                let mut body = *comment;
+                while let Some(rest) = editorconfig::DEFAULT_COMMENT_PREFIXES
+                    .iter()
+                    .find_map(|prefix| body.strip_prefix(prefix))
+                {
+                    body = rest;
+                }
+                body.split_once('=').is_some_and(|(key, value)| {
+                    let key = key.trim();
+                    !key.is_empty()
+                        && !key.contains(char::is_whitespace)
+                        && !value.trim().is_empty()
+                        && sec.properties.iter().any(|p| {
+                            p.name.eq_ignore_ascii_case(key)
+                                && editorconfig::range_of(content, p.name).start < start
+                        })
+                })
+            })
+        })
+        .collect()
 }
 
-#[cfg(test)]
-mod tests {
-    #![allow(clippy::unwrap_in_result)]
-    #![allow(clippy::unwrap_used)]
-    use super::*;
-    use rstest::rstest;
-
-    struct TestFormatter<F>
-    where
-        F: Fn(ValidationResult),
-    {
-        assert: F,
+/// Finds sections where `indent_size` differs from `tab_width` while effective `indent_style` is `tab`.
+/// Only sections that set either of them are checked so values inherited from `[*]` section
+/// are reported once
+fn find_tab_width_conflicts<'a>(
+    sections: &[Section<'a>],
+) -> BTreeMap<&'a str, Vec<(&'a str, &'a str)>> {
+    let mut result = BTreeMap::new();
+    for sec in sections {
+        let style = effective_value(sections, sec, "indent_style");
+        if !style.is_some_and(|s| s.eq_ignore_ascii_case("tab")) {
+            continue;
+        }
+        if property_value(sec, "indent_size").is_none()
+            && property_value(sec, "tab_width").is_none()
+        {
+            continue;
+        }
+        let size = effective_value(sections, sec, "indent_size");
+        let width = effective_value(sections, sec, "tab_width");
+        if let (Some(size), Some(width)) = (size, width) {
+            // indent_size = tab means the same as tab_width
+            if !size.eq_ignore_ascii_case("tab") && size != width {
+                append_to_btree(&mut result, sec.title, &mut vec![(size, width)]);
+            }
+        }
     }
+    result
+}
 
-    struct TestCompareFormatter<F>
-    where
-        F: Fn(BTreeMap<&str, Vec<CompareItem>>),
+/// Finds sections that set `indent_size = tab` while `tab_width` is set neither by the section
+/// nor by `[*]` section so indentation width depends on editor defaults
+fn find_tab_without_width<'a>(sections: &[Section<'a>]) -> Vec<&'a str> {
+    sections
+        .iter()
+        .filter(|sec| {
+            property_value(sec, "indent_size").is_some_and(|v| v.eq_ignore_ascii_case("tab"))
+        })
+        .filter(|sec| effective_value(sections, sec, "tab_width").is_none())
+        .map(|sec| sec.title)
+        .unique()
+        .collect()
+}
+
+/// Finds properties which sections set to values different from ones set by the closest `[*]` section
+/// before them. Sections that repeat `[*]` values aren't reported here because values are the same
+/// and ones that `unset` them just remove the property so there is nothing to conflict with
+fn find_override_conflicts<'a>(
+    sections: &[Section<'a>],
+) -> BTreeMap<&'a str, Vec<(&'a str, &'a str, &'a str)>> {
+    let mut result = BTreeMap::new();
+    for (i, sec) in sections
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| !s.title.is_empty() && s.title != "*")
     {
-        assert: F,
+        let mut conflicts: Vec<(&str, &str, &str)> = sec
+            .properties
+            .iter()
+            .map(|p| p.name)
+            .unique()
+            .filter_map(|name| {
+                let parent = sections[..i]
+                    .iter()
+                    .rev()
+                    .filter(|s| s.title == "*")
+                    .find_map(|s| property_value(s, name))?;
+                let child = property_value(sec, name)?;
+                (!properties::is_unset(child) && !parent.eq_ignore_ascii_case(child))
+                    .then_some((name, parent, child))
+            })
+            .collect();
+        append_to_btree(&mut result, sec.title, &mut conflicts);
     }
+    result
+}
 
-    impl<F> TestFormatter<F>
-    where
-        F: Fn(ValidationResult),
+/// Finds assignments whose style, i.e. whether `=` is surrounded by whitespace or not, differs
+/// from the style most assignments of the file use. Spaced style wins a tie.
+fn find_inconsistent_assignments<'a>(
+    content: &str,
+    sections: &[Section<'a>],
+) -> Vec<(&'a str, &'a str)> {
+    // lexer trims key and value so the separator is only visible between their spans
+    let styles: Vec<(&str, &str, bool)> = sections
+        .iter()
+        .flat_map(|sec| {
+            sec.properties
+                .iter()
+                .filter(|p| !p.span.is_empty() && !p.value.is_empty())
+                .map(|p| {
+                    let key = editorconfig::range_of(content, p.name);
+                    let value = editorconfig::range_of(content, p.value);
+                    let spaced = content
+                        .get(key.end..value.start)
+                        .is_some_and(|separator| separator.trim() != separator);
+                    (sec.title, p.name, spaced)
+                })
+        })
+        .collect();
+    let spaced = styles.iter().filter(|(_, _, spaced)| *spaced).count();
+    let majority = spaced * 2 >= styles.len();
+    styles
+        .into_iter()
+        .filter(|(_, _, spaced)| *spaced != majority)
+        .map(|(title, name, _)| (title, name))
+        .collect()
+}
+
+/// Finds boolean toggles that a section sets to the opposite of the value set by the closest
+/// earlier section which matches all files of the section, like `true` by `[*.md]` and `false`
+/// by `[docs/*.md]`. Every title is expanded once through `cache`
+fn find_boolean_flips<'a>(
+    sections: &[Section<'a>],
+    cache: &mut GlobCache<'a>,
+) -> BTreeMap<&'a str, Vec<(&'a str, &'a str, &'a str)>> {
+    let mut result = BTreeMap::new();
+    for (i, sec) in sections.iter().enumerate() {
+        if sec.title.is_empty() {
+            continue;
+        }
+        let narrow = cache.expand(sec.title).to_vec();
+        let mut flips: Vec<(&str, &str, &str)> = sec
+            .properties
+            .iter()
+            .map(|p| p.name)
+            .unique()
+            .filter(|name| {
+                properties::BOOLEAN_TOGGLES
+                    .iter()
+                    .any(|t| t.eq_ignore_ascii_case(name))
+            })
+            .filter_map(|name| {
+                let child = property_value(sec, name)?;
+                let parent = sections[..i]
+                    .iter()
+                    .rev()
+                    .filter(|s| !s.title.is_empty() && s.title != sec.title)
+                    .filter(|s| glob::covers_globs(cache.expand(s.title), &narrow))
+                    .find_map(|s| property_value(s, name))?;
+                match (
+                    properties::typed_value(name, parent),
+                    properties::typed_value(name, child),
+                ) {
+                    (TypedValue::Bool(p), TypedValue::Bool(c)) if p != c => {
+                        Some((name, parent, child))
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
+        append_to_btree(&mut result, sec.title, &mut flips);
+    }
+    result
+}
+
+/// Gets the last value of the property in the section. Property names are case insensitive.
+fn property_value<'a>(sec: &Section<'a>, name: &str) -> Option<&'a str> {
+    sec.properties
+        .iter()
+        .rev()
+        .find(|p| p.name.eq_ignore_ascii_case(name))
+        .map(|p| p.value)
+}
+
+/// Gets the property value defined in the section itself or inherited from `[*]` section.
+/// The property that is `unset` by the section or by `[*]` section has no value.
+fn effective_value<'a>(sections: &[Section<'a>], sec: &Section<'a>, name: &str) -> Option<&'a str> {
+    property_value(sec, name)
+        .or_else(|| {
+            sections
+                .iter()
+                .rev()
+                .filter(|s| s.title == "*")
+                .find_map(|s| property_value(s, name))
+        })
+        .filter(|value| !properties::is_unset(value))
+}
+
+/// Finds section pairs whose globs match the same files because they have the same file name
+/// part and one of them is restricted to some directory, like `[*.js]` and `[src/*.js]`.
+/// Plain wildcard refinement (i.e. `[*]` and `[*.md]`) is normal cascading so it isn't reported.
+fn find_overlapping_sections<'a>(
+    titles: &[&'a str],
+    cache: &mut GlobCache<'a>,
+) -> Vec<(&'a str, &'a str)> {
+    let expanded: Vec<(&str, Vec<String>)> = titles
+        .iter()
+        .filter(|t| !t.is_empty())
+        .unique()
+        .map(|t| (*t, cache.expand(t).to_vec()))
+        .collect();
+
+    let mut result = Vec::new();
+    for (i, (first, first_globs)) in expanded.iter().enumerate() {
+        for (second, second_globs) in &expanded[i + 1..] {
+            let overlap = first_globs.iter().any(|f| {
+                second_globs
+                    .iter()
+                    .any(|s| is_refinement(f, s) || is_refinement(s, f))
+            });
+            if overlap {
+                result.push((*first, *second));
+            }
+        }
+    }
+    result
+}
+
+/// Maps section titles to the first title that expands to the same set of globs.
+/// Titles that cannot be expanded are left out
+fn canonical_titles<'a>(
+    titles: &[&'a str],
+    cache: &mut GlobCache<'a>,
+) -> HashMap<&'a str, &'a str> {
+    let mut first_titles: HashMap<Vec<String>, &str> = HashMap::new();
+    let mut result = HashMap::new();
+    for title in titles.iter().unique() {
+        let mut globs = cache.expand(title).to_vec();
+        if globs.is_empty() {
+            continue;
+        }
+        globs.sort_unstable();
+        globs.dedup();
+        result.insert(*title, *first_titles.entry(globs).or_insert(title));
+    }
+    result
+}
+
+/// Finds pairs of sections with different titles that expand to the same set of globs,
+/// i.e. the same files are matched by the section written in two different ways.
+/// A section whose globs are exactly the union of globs of several other sections,
+/// like `[*.{js,jsx}]` along with `[*.js]` and `[*.jsx]`, is paired with each of them
+fn find_equivalent_sections<'a>(
+    titles: &[&'a str],
+    cache: &mut GlobCache<'a>,
+) -> Vec<(&'a str, &'a str)> {
+    let canonical: Vec<(&str, Vec<String>)> = titles
+        .iter()
+        .filter(|t| !t.is_empty())
+        .unique()
+        .map(|t| {
+            let mut globs = cache.expand(t).to_vec();
+            globs.sort_unstable();
+            globs.dedup();
+            (*t, globs)
+        })
+        .filter(|(_, globs)| !globs.is_empty())
+        .collect();
+
+    let mut result = Vec::new();
+    for (i, (first, first_globs)) in canonical.iter().enumerate() {
+        for (second, second_globs) in &canonical[i + 1..] {
+            if first_globs == second_globs {
+                result.push((*first, *second));
+            }
+        }
+    }
+    for (whole, whole_globs) in &canonical {
+        let parts: Vec<&(&str, Vec<String>)> = canonical
+            .iter()
+            .filter(|(_, globs)| {
+                globs.len() < whole_globs.len() && globs.iter().all(|g| whole_globs.contains(g))
+            })
+            .collect();
+        let covered: HashSet<&String> = parts.iter().flat_map(|(_, globs)| globs).collect();
+        if covered.len() == whole_globs.len() {
+            result.extend(parts.iter().map(|(part, _)| (*whole, *part)));
+        }
+    }
+    result
+}
+
+/// Expanded section titles memoized within a single validation pass so that
+/// every distinct title is parsed once no matter how many sections repeat it
+#[derive(Default)]
+struct GlobCache<'a> {
+    expanded: HashMap<&'a str, Vec<String>>,
+}
+
+impl<'a> GlobCache<'a> {
+    fn expand(&mut self, title: &'a str) -> &[String] {
+        self.expanded
+            .entry(title)
+            .or_insert_with(|| glob::parse(title))
+    }
+}
+
+/// Finds pairs of sections where the later section matches strictly more files than the earlier one
+/// including all files the earlier one matches. Every title is expanded once through `cache`
+fn find_shadowed_sections<'a>(
+    titles: &[&'a str],
+    cache: &mut GlobCache<'a>,
+) -> Vec<(&'a str, &'a str)> {
+    let expanded: Vec<(&str, Vec<String>)> = titles
+        .iter()
+        .filter(|t| !t.is_empty())
+        .unique()
+        .map(|t| (*t, cache.expand(t).to_vec()))
+        .collect();
+    let mut result = Vec::new();
+    for (i, (narrow, narrow_globs)) in expanded.iter().enumerate() {
+        for (wide, wide_globs) in &expanded[i + 1..] {
+            if glob::covers_globs(wide_globs, narrow_globs)
+                && !glob::covers_globs(narrow_globs, wide_globs)
+            {
+                result.push((*narrow, *wide));
+            }
+        }
+    }
+    result
+}
+
+/// Checks whether `narrow` glob matches only a subset of files that `wide` matches,
+/// i.e. file name parts are the same but `narrow` is bound to a directory
+fn is_refinement(wide: &str, narrow: &str) -> bool {
+    let (wide_dir, wide_file) = split_glob(wide);
+    let (narrow_dir, narrow_file) = split_glob(narrow);
+    if wide_file != narrow_file || wide_dir == narrow_dir {
+        return false;
+    }
+    if wide_dir.is_empty() {
+        return true;
+    }
+    // only globstar directory may contain nested directories
+    match wide_dir.strip_suffix("**") {
+        Some(prefix) => narrow_dir.starts_with(prefix),
+        None => false,
+    }
+}
+
+/// Splits glob into directory and file name parts. Globs without slash or
+/// started from globstar match in any directory so their directory part is empty.
+fn split_glob(pattern: &str) -> (&str, &str) {
+    let pattern = pattern.trim_start_matches('/');
+    let pattern = pattern.strip_prefix("**/").unwrap_or(pattern);
+    match pattern.rfind('/') {
+        Some(ix) => (&pattern[..ix], &pattern[ix + 1..]),
+        None => ("", pattern),
+    }
+}
+
+/// Splits .editorconfig content into lexical tokens along with their byte offsets.
+/// Lines that cannot be recognized as section head, key/value pair or comment are skipped.
+///
+/// # Example
+///
+/// ```
+/// use editorconfiger::Token;
+///
+/// let tokens: Vec<_> = editorconfiger::tokens("[*.md]\nindent_size = 2").collect();
+///
+/// assert_eq!(Token::Head("*.md"), tokens[0].token);
+/// assert_eq!(1..5, tokens[0].span);
+/// assert_eq!(Token::Pair("indent_size", "2"), tokens[1].token);
+/// assert_eq!(7..22, tokens[1].span);
+/// ```
+pub fn tokens(content: &str) -> impl Iterator<Item = SpannedToken<'_>> {
+    editorconfig::tokens(content)
+}
+
+/// Parses .editorconfig content into sections the same way as validation does and also returns
+/// all lines that cannot be recognized. Such lines are skipped so sections after them are still parsed.
+///
+/// # Example
+///
+/// ```
+/// let (sections, errors) = editorconfiger::parse_with_errors("[*]\nindent_size\n[*.md]\ncharset = utf-8\n");
+///
+/// assert_eq!(2, sections.len());
+/// assert_eq!(1, errors.len());
+/// assert_eq!(2, errors[0].line);
+/// ```
+#[must_use]
+pub fn parse_with_errors(content: &str) -> (Vec<Section<'_>>, Vec<LexerError>) {
+    editorconfig::parse_with_errors(content)
+}
+
+/// Expands every section title of .editorconfig content into the list of
+/// patterns produced by [`glob::parse`], i.e. with braces and brackets unfolded.
+/// Numeric ranges like `{1..3}` aren't unfolded but kept along with their braces
+/// as single alternatives are. Properties defined before the first section are skipped.
+///
+/// # Example
+///
+/// ```
+/// let sections = editorconfiger::expanded_sections("[*.{js,ts}]\nindent_size = 2");
+///
+/// assert_eq!(1, sections.len());
+/// assert_eq!("*.{js,ts}", sections[0].0);
+/// assert_eq!(vec!["*.js", "*.ts"], sections[0].1);
+/// ```
+#[must_use]
+pub fn expanded_sections(content: &str) -> Vec<(String, Vec<String>)> {
+    let mut cache = GlobCache::default();
+    editorconfig::parse(content)
+        .iter()
+        .filter(|s| !s.title.is_empty())
+        .map(|s| (s.title.to_string(), cache.expand(s.title).to_vec()))
+        .collect()
+}
+
+/// Finds recommended properties that `[*]` section of .editorconfig content doesn't set, so that
+/// editors use their own defaults for them. All of them are missing if there is no `[*]` section.
+/// Recommended properties are `charset`, `end_of_line`, `insert_final_newline`, `indent_style` and `indent_size`.
+///
+/// # Example
+///
+/// ```
+/// let config = "root = true\n[*]\ncharset = utf-8\nend_of_line = lf\nindent_style = space\n";
+///
+/// let missing = editorconfiger::missing_recommended(config);
+///
+/// assert_eq!(vec!["insert_final_newline", "indent_size"], missing);
+/// ```
+#[must_use]
+pub fn missing_recommended(content: &str) -> Vec<&'static str> {
+    missing_recommended_with(content, &ValidationConfig::default())
+}
+
+/// Finds recommended properties that `[*]` section doesn't set the same way as [`missing_recommended`]
+/// does but the recommended set is taken from [`ValidationConfig::recommended_properties`] if it's set.
+#[must_use]
+pub fn missing_recommended_with(content: &str, config: &ValidationConfig) -> Vec<&'static str> {
+    let sections = editorconfig::parse(content);
+    let is_set = |name: &str| {
+        sections
+            .iter()
+            .filter(|sec| sec.title == "*")
+            .flat_map(|sec| &sec.properties)
+            .any(|p| p.name.eq_ignore_ascii_case(name))
+    };
+    config
+        .recommended_properties
+        .as_deref()
+        .unwrap_or(&properties::RECOMMENDED)
+        .iter()
+        .copied()
+        .filter(|name| !is_set(name))
+        .collect()
+}
+
+/// Resolves properties that apply to the file from sections of a single .editorconfig content.
+/// `file_path` is relative to the directory of the .editorconfig file and is matched by [`glob::matches`].
+/// Sections that match the file are applied in order so later ones win and `unset` value
+/// removes the property set before.
+/// Keys are lowercased as they're case insensitive.
+/// Indentation is completed the way editorconfig cores do: `indent_style = tab` implies
+/// `indent_size = tab`, `indent_size` implies the same `tab_width` and `indent_size = tab`
+/// resolves to `tab_width` if it's set.
+///
+/// # Example
+///
+/// ```
+/// let config = "root = true\n[*]\nindent_size = 4\n[*.md]\nindent_size = 2\n";
+///
+/// let properties = editorconfiger::effective_properties(config, "docs/readme.md");
+///
+/// assert_eq!(Some(&"2".to_string()), properties.get("indent_size"));
+/// assert_eq!(Some(&"2".to_string()), properties.get("tab_width"));
+/// ```
+#[must_use]
+pub fn effective_properties(content: &str, file_path: &str) -> BTreeMap<String, String> {
+    let mut result: BTreeMap<String, String> = BTreeMap::new();
+    for p in editorconfig::parse(content)
+        .iter()
+        .filter(|sec| !sec.title.is_empty() && glob::matches(sec.title, file_path))
+        .flat_map(|sec| &sec.properties)
     {
-        fn new(assert: F) -> Self {
-            Self { assert }
+        let key = p.name.to_lowercase();
+        if properties::is_unset(p.value) {
+            result.remove(&key);
+        } else {
+            result.insert(key, p.value.to_string());
+        }
+    }
+
+    let get = |result: &BTreeMap<String, String>, key: &str| result.get(key).cloned();
+    if get(&result, "indent_style").is_some_and(|s| s.eq_ignore_ascii_case("tab"))
+        && !result.contains_key("indent_size")
+    {
+        result.insert("indent_size".to_string(), "tab".to_string());
+    }
+    match (get(&result, "indent_size"), get(&result, "tab_width")) {
+        (Some(size), None) if !size.eq_ignore_ascii_case("tab") => {
+            result.insert("tab_width".to_string(), size);
+        }
+        (Some(size), Some(width)) if size.eq_ignore_ascii_case("tab") => {
+            result.insert("indent_size".to_string(), width);
+        }
+        _ => {}
+    }
+    result
+}
+
+/// Compares the properties of two .editorconfig files contents and formats the comparison result.
+///
+/// # Arguments
+///
+/// * `content1` - A string slice holding the first .editorconfig content.
+/// * `content2` - A string slice holding the second .editorconfig content.
+/// * `formatter` - A reference to an implementation of the [`ComparisonFormatter`] trait,
+///                 which will be used to format the comparison results.
+///
+/// The function performs the following steps:
+///
+/// 1. Parses the two configuration contents into sections.
+/// 2. Maps the sections to their properties for both contents.
+/// 3. Iterates over the sections of the first content and compares each property with the corresponding section in the second content.
+/// 4. Collects the comparison results, including properties that are only in the first content, only in the second content, or in both with different values.
+/// 5. Identifies sections that are missing in the first content but present in the second content and includes their properties in the result.
+/// 6. Constructs a `BTreeMap` where the key is the section title and the value is a list of [`]CompareItem`] representing the comparison results for each property.
+/// 7. Uses the provided `formatter` to format the comparison results.
+///
+/// The resulting `BTreeMap` includes:
+///
+/// * Section titles as keys.
+/// * Lists of [`CompareItem`] for each section, representing the property comparisons.
+///   - Each [`CompareItem`] includes the property key, its value in the first content (if any), and its value in the second content (if any).
+pub fn compare<F: ComparisonFormatter>(content1: &str, content2: &str, formatter: &F) {
+    compare_with(content1, content2, &CompareConfig::default(), formatter);
+}
+
+/// Compares the properties of two .editorconfig files contents the same way as [`compare`] does
+/// but using options from `config`.
+///
+/// # Arguments
+///
+/// * `content1` - A string slice holding the first .editorconfig content.
+/// * `content2` - A string slice holding the second .editorconfig content.
+/// * `config` - A reference to [`CompareConfig`] that defines how values are compared.
+/// * `formatter` - A reference to an implementation of the [`ComparisonFormatter`] trait,
+///   which will be used to format the comparison results.
+pub fn compare_with<F: ComparisonFormatter>(
+    content1: &str,
+    content2: &str,
+    config: &CompareConfig,
+    formatter: &F,
+) {
+    compare_contents(content1, content2, config, |result| {
+        formatter.format(result)
+    });
+}
+
+/// Compares the properties of two .editorconfig files contents the same way as [`compare_with`] does
+/// but passes `labels` into [`ComparisonFormatter::format_labeled`] so that the formatter can tell
+/// where the contents come from, e.g. paths of the current and the proposed configuration.
+///
+/// # Example
+///
+/// ```
+/// use editorconfiger::{CompareConfig, ComparisonFormatter, CompareItem};
+/// use std::collections::BTreeMap;
+///
+/// struct Checker;
+///
+/// impl ComparisonFormatter for Checker {
+///     fn format(&self, _result: BTreeMap<&str, Vec<CompareItem>>) {
+///         unreachable!()
+///     }
+///
+///     fn format_labeled(&self, _result: BTreeMap<&str, Vec<CompareItem>>, labels: [&str; 2]) {
+///         assert_eq!(["current", "proposed"], labels);
+///     }
+/// }
+///
+/// editorconfiger::compare_labeled_with(
+///     "[*]\na = b",
+///     "[*]\na = c",
+///     ["current", "proposed"],
+///     &CompareConfig::default(),
+///     &Checker,
+/// );
+/// ```
+pub fn compare_labeled_with<F: ComparisonFormatter>(
+    content1: &str,
+    content2: &str,
+    labels: [&str; 2],
+    config: &CompareConfig,
+    formatter: &F,
+) {
+    compare_contents(content1, content2, config, |result| {
+        formatter.format_labeled(result, labels);
+    });
+}
+
+/// Checks whether two .editorconfig contents are semantically equal, i.e. every section
+/// resolves to the same set of properties with the same values in both. Order of sections
+/// and properties doesn't matter.
+///
+/// # Example
+///
+/// ```
+/// assert!(editorconfiger::configs_equal("[*]\na = b\nc = d", "[*]\nc = d\na = b"));
+/// assert!(!editorconfiger::configs_equal("[*]\na = b", "[*]\na = c"));
+/// ```
+#[must_use]
+pub fn configs_equal(content1: &str, content2: &str) -> bool {
+    configs_equal_with(content1, content2, &CompareConfig::default())
+}
+
+/// Checks whether two .editorconfig contents are semantically equal the same way as
+/// [`configs_equal`] does but compares values using options from `config`.
+#[must_use]
+pub fn configs_equal_with(content1: &str, content2: &str, config: &CompareConfig) -> bool {
+    compare_contents(content1, content2, config, |result| {
+        result
+            .values()
+            .flatten()
+            .all(|item| item.presence.in_both() && item.first_value == item.second_value)
+    })
+}
+
+/// Compares the properties of two .editorconfig files contents and returns the comparison result
+/// as owned data that outlives the contents.
+///
+/// The result has the same shape as the one passed into [`ComparisonFormatter`] by [`compare`]:
+/// section titles as keys and lists of [`OwnedCompareItem`] as values.
+///
+/// # Example
+///
+/// ```
+/// let result = editorconfiger::diff("[*]\na = b\nc = d", "[*]\na = e\n[*.md]\nf = g");
+///
+/// assert_eq!(2, result.len());
+/// let star = &result["*"];
+/// assert_eq!("a", star[0].key);
+/// assert_eq!(Some("b".to_string()), star[0].first_value);
+/// assert_eq!(Some("e".to_string()), star[0].second_value);
+/// assert_eq!(None, star[1].second_value);
+/// assert_eq!(None, result["*.md"][0].first_value);
+/// ```
+#[must_use]
+pub fn diff(content1: &str, content2: &str) -> BTreeMap<String, Vec<OwnedCompareItem>> {
+    compare_contents(content1, content2, &CompareConfig::default(), |result| {
+        result
+            .into_iter()
+            .map(|(section, items)| {
+                let items = items.into_iter().map(OwnedCompareItem::from).collect();
+                (section.to_string(), items)
+            })
+            .collect()
+    })
+}
+
+/// Counts differences between two .editorconfig contents found by [`compare`]
+///
+/// # Example
+///
+/// ```
+/// let stats = editorconfiger::diff_stats("[*]\na = b\nc = d\n[x]\ne = f", "[*]\na = e\n[y]\ng = h");
+///
+/// assert_eq!(1, stats.sections_only_first);
+/// assert_eq!(1, stats.sections_only_second);
+/// assert_eq!(1, stats.props_differing);
+/// assert_eq!(2, stats.props_only_first);
+/// assert_eq!(1, stats.props_only_second);
+/// ```
+#[must_use]
+pub fn diff_stats(content1: &str, content2: &str) -> DiffStats {
+    diff_stats_with(content1, content2, &CompareConfig::default())
+}
+
+/// Counts differences between two .editorconfig contents the same way as [`diff_stats`] does
+/// but compares values using options from `config`.
+#[must_use]
+pub fn diff_stats_with(content1: &str, content2: &str, config: &CompareConfig) -> DiffStats {
+    // sections without properties have no compare items so they're counted using maps
+    map_contents(content1, content2, config, |s1_props, s2_props| {
+        let mut stats = DiffStats {
+            sections_only_first: s1_props
+                .keys()
+                .filter(|title| !s2_props.contains_key(*title))
+                .count(),
+            sections_only_second: s2_props
+                .keys()
+                .filter(|title| !s1_props.contains_key(*title))
+                .count(),
+            ..Default::default()
+        };
+        for item in compare_sections(s1_props, s2_props).values().flatten() {
+            match (item.first_value, item.second_value) {
+                (Some(v1), Some(v2)) if v1 != v2 => stats.props_differing += 1,
+                (Some(_), None) => stats.props_only_first += 1,
+                (None, Some(_)) => stats.props_only_second += 1,
+                _ => {}
+            }
         }
+        stats
+    })
+}
+
+/// Numbers of differences between two compared .editorconfig contents
+///
+/// # Fields
+///
+/// * `sections_only_first` - The number of sections that only the first content has.
+/// * `sections_only_second` - The number of sections that only the second content has.
+/// * `props_differing` - The number of properties both contents set to different values.
+/// * `props_only_first` - The number of properties that only the first content sets
+///   including properties of sections that only the first content has.
+/// * `props_only_second` - The number of properties that only the second content sets
+///   including properties of sections that only the second content has.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DiffStats {
+    pub sections_only_first: usize,
+    pub sections_only_second: usize,
+    pub props_differing: usize,
+    pub props_only_first: usize,
+    pub props_only_second: usize,
+}
+
+impl DiffStats {
+    /// Whether contents have no differences at all
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
     }
+}
+
+/// Re-emits .editorconfig content in canonical form: sections with the same title are merged,
+/// properties are sorted by lowercased key within each section and written as `key = value`.
+/// `root` property stays at the top of the file. Comments of a section are kept above its properties.
+///
+/// Later sections win over earlier ones so canonical form must not change properties files get.
+/// That's why sections keep their order instead of being sorted and a section is merged into
+/// the previous one with the same title only if no section between them sets any of its keys.
+/// Later values win within merged sections the same way. Values are written as they are
+/// up to the end of line, like `b # note` in `a = b # note`, because editors following
+/// the specification don't treat such text as a comment.
+///
+/// # Errors
+///
+/// Returns [`Error::Syntax`] if some line is neither section head, key/value pair nor comment
+/// because such line would be lost otherwise
+///
+/// # Example
+///
+/// ```
+/// let content = "root=true\n[*]\nIndent_Size=2\ncharset =utf-8\n[*.md]\na = b\n[*]\nindent_size = 4\n";
+///
+/// let canonical = editorconfiger::canonicalize(content).unwrap();
+///
+/// assert_eq!(
+///     "root = true\n\n[*]\ncharset = utf-8\nindent_size = 4\n\n[*.md]\na = b\n",
+///     canonical
+/// );
+/// ```
+pub fn canonicalize(content: &str) -> Result<String, Error> {
+    check_syntax(content)?;
+    let sections = editorconfig::parse(content);
+
+    let mut merged: Vec<(&str, Vec<&str>, BTreeMap<String, &str>)> = Vec::new();
+    for sec in &sections {
+        let keys: Vec<String> = sec
+            .properties
+            .iter()
+            .map(|p| p.name.to_lowercase())
+            .collect();
+        let ix = merged
+            .iter()
+            .rposition(|(title, _, _)| *title == sec.title)
+            .filter(|ix| {
+                merged[ix + 1..]
+                    .iter()
+                    .all(|(_, _, properties)| !keys.iter().any(|k| properties.contains_key(k)))
+            })
+            .unwrap_or_else(|| {
+                merged.push((sec.title, Vec::new(), BTreeMap::new()));
+                merged.len() - 1
+            });
+        let (_, comments, properties) = &mut merged[ix];
+        // text after value is a part of it for editors so it's written back with the value
+        let is_inline = |c: &str| {
+            let start = editorconfig::range_of(content, c).start;
+            sec.properties
+                .iter()
+                .any(|p| p.span.start < start && start <= line_end(content, p.span.start))
+        };
+        comments.extend(sec.comments.iter().filter(|c| !is_inline(c)));
+        for p in &sec.properties {
+            properties.insert(p.name.to_lowercase(), raw_value(content, p));
+        }
+    }
+    // properties before the first section head must stay before it
+    merged.sort_by_key(|(title, _, _)| !title.is_empty());
+    // parser skips comments that precede any property or section head
+    let header: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with(['#', ';']))
+        .filter(|line| !line.is_empty())
+        .collect();
+    if !header.is_empty() {
+        match merged.first_mut() {
+            Some(("", comments, _)) => {
+                comments.splice(0..0, header);
+            }
+            _ => merged.insert(0, ("", header, BTreeMap::new())),
+        }
+    }
+
+    let mut blocks: Vec<String> = Vec::new();
+    for (title, comments, mut properties) in merged {
+        let mut lines: Vec<String> = Vec::new();
+        if !title.is_empty() {
+            lines.push(format!("[{title}]"));
+        }
+        lines.extend(comments.into_iter().map(str::to_string));
+        let root = if title.is_empty() {
+            properties.remove_entry("root")
+        } else {
+            None
+        };
+        lines.extend(root.into_iter().chain(properties).map(|(key, value)| {
+            if value.is_empty() {
+                format!("{key} =")
+            } else {
+                format!("{key} = {value}")
+            }
+        }));
+        if !lines.is_empty() {
+            blocks.push(lines.join("\n") + "\n");
+        }
+    }
+    Ok(blocks.join("\n"))
+}
+
+/// Value of the property as written in the source up to the end of its line, so text
+/// that parser takes for inline comment, like `#fff` in `color = #fff`, is kept
+fn raw_value<'a>(content: &'a str, property: &Property<'a>) -> &'a str {
+    content[property.span.start..line_end(content, property.span.start)]
+        .split_once('=')
+        .map_or(property.value, |(_, value)| value.trim())
+}
+
+/// Byte offset of the end of line that contains `offset`
+fn line_end(content: &str, offset: usize) -> usize {
+    content[offset..]
+        .find(['\r', '\n'])
+        .map_or(content.len(), |ix| offset + ix)
+}
+
+fn compare_contents<R>(
+    content1: &str,
+    content2: &str,
+    config: &CompareConfig,
+    action: impl FnOnce(BTreeMap<&str, Vec<CompareItem>>) -> R,
+) -> R {
+    map_contents(content1, content2, config, |s1_props, s2_props| {
+        action(compare_sections(s1_props, s2_props))
+    })
+}
+
+/// Parses both contents and maps their sections to properties the way comparison does
+fn map_contents<R>(
+    content1: &str,
+    content2: &str,
+    config: &CompareConfig,
+    action: impl FnOnce(&SectionsMap, &SectionsMap) -> R,
+) -> R {
+    let f1 = editorconfig::parse(content1);
+    let f2 = editorconfig::parse(content2);
+
+    action(&map_sections(&f1, config), &map_sections(&f2, config))
+}
+
+fn compare_sections<'a>(
+    s1_props: &'a SectionsMap<'_>,
+    s2_props: &'a SectionsMap<'_>,
+) -> BTreeMap<&'a str, Vec<CompareItem<'a>>> {
+    s1_props
+        .iter()
+        .map(|s1| {
+            let props1 = s1.1;
+            let props2 = s2_props.get(s1.0);
+            (s1, props1, props2)
+        })
+        .map(|(s1, props1, props2)| {
+            let items: Vec<CompareItem> = props1
+                .iter()
+                .map(|(k1, v1)| {
+                    let v2 = props2.and_then(|p| p.get(k1)).map(AsRef::as_ref);
+                    CompareItem::new(k1, Some(v1), v2)
+                })
+                .chain(
+                    // Properties in the section that missing in the first
+                    props2
+                        .into_iter()
+                        .flatten()
+                        .filter(|(k, _)| !props1.contains_key(*k))
+                        .map(|(k, v)| CompareItem::only_second(k, v)),
+                )
+                .collect();
+            (*s1.0, items)
+        })
+        .chain(
+            // Sections missing in the first
+            s2_props
+                .iter()
+                .filter(|s| !s1_props.contains_key(s.0))
+                .map(|s| {
+                    let items: Vec<CompareItem> =
+                        s.1.iter()
+                            .map(|p| CompareItem::only_second(p.0, p.1))
+                            .collect();
+                    (*s.0, items)
+                }),
+        )
+        .collect()
+}
+
+fn map_properties<'a>(s1: &Section<'a>, config: &CompareConfig) -> BTreeMap<&'a str, Cow<'a, str>> {
+    s1.properties
+        .iter()
+        .filter(|p| !config.is_ignored_key(p.name))
+        .map(|p| {
+            let value = if config.normalize_whitespace {
+                normalize_whitespace(p.value)
+            } else {
+                Cow::Borrowed(p.value)
+            };
+            (p.name, value)
+        })
+        .collect()
+}
+
+/// Collapses whitespace runs inside value into single space
+fn normalize_whitespace(value: &str) -> Cow<'_, str> {
+    let normalized = value.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if normalized == value {
+        Cow::Borrowed(value)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+/// Section titles mapped to their properties
+type SectionsMap<'a> = HashMap<&'a str, BTreeMap<&'a str, Cow<'a, str>>>;
+
+fn map_sections<'a>(sections: &[Section<'a>], config: &CompareConfig) -> SectionsMap<'a> {
+    let mut result: SectionsMap = HashMap::new();
+    for s in sections {
+        let properties = map_properties(s, config);
+        // section that had only ignored properties
+        if properties.is_empty() && !s.properties.is_empty() {
+            continue;
+        }
+        result.entry(s.title).or_default().extend(properties);
+    }
+    result
+}
+
+/// On Windows added trailing back slash \ if volume and colon passed so as to paths look more pleasant
+#[cfg(target_os = "windows")]
+fn decorate_path(path: &str) -> String {
+    if path.len() == 2 && path.ends_with(':') {
+        format!("{path}\\")
+    } else {
+        String::from(path)
+    }
+}
+
+/// On Unix just passthrough as is
+#[cfg(not(target_os = "windows"))]
+fn decorate_path(path: &str) -> String {
+    String::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_in_result)]
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use rstest::rstest;
+
+    struct TestFormatter<F>
+    where
+        F: Fn(ValidationResult),
+    {
+        assert: F,
+    }
+
+    struct TestCompareFormatter<F>
+    where
+        F: Fn(BTreeMap<&str, Vec<CompareItem>>),
+    {
+        assert: F,
+    }
+
+    #[derive(Default)]
+    struct TestErrorer {
+        errors: std::cell::RefCell<Vec<String>>,
+    }
+
+    impl Errorer for TestErrorer {
+        fn error(&self, path: &str, _err: &str) {
+            self.errors.borrow_mut().push(path.to_string());
+        }
+    }
+
+    #[derive(Default)]
+    struct FailFastErrorer {
+        errors: std::cell::RefCell<Vec<String>>,
+    }
+
+    impl Errorer for FailFastErrorer {
+        fn error(&self, path: &str, _err: &str) {
+            self.errors.borrow_mut().push(path.to_string());
+        }
+
+        fn recover(&self, path: &str, err: &str) -> ControlFlow<()> {
+            self.error(path, err);
+            ControlFlow::Break(())
+        }
+    }
+
+    impl<F> TestFormatter<F>
+    where
+        F: Fn(ValidationResult),
+    {
+        fn new(assert: F) -> Self {
+            Self { assert }
+        }
+    }
+
+    impl<F> TestCompareFormatter<F>
+    where
+        F: Fn(BTreeMap<&str, Vec<CompareItem>>),
+    {
+        fn new(assert: F) -> Self {
+            Self { assert }
+        }
+    }
+
+    impl<F> ValidationFormatter for TestFormatter<F>
+    where
+        F: Fn(ValidationResult),
+    {
+        fn format(&self, result: ValidationResult) {
+            (self.assert)(result);
+        }
+    }
+
+    impl<F> ComparisonFormatter for TestCompareFormatter<F>
+    where
+        F: Fn(BTreeMap<&str, Vec<CompareItem>>),
+    {
+        fn format(&self, result: BTreeMap<&str, Vec<CompareItem>>) {
+            (self.assert)(result);
+        }
+    }
+
+    #[test]
+    fn validate_success() {
+        // Arrange
+        let config = r#"
+root = true
+[*]
+a = b
+c = d
+
+[*.md]
+e = f"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.is_ok());
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case(
+        "S=\u{1b}\u{1b}\u{1e}_=\u{1b}\n\u{1b},\u{1b}s=\u{1b}\u{0}\u{0}\u{1b}\u{1b}1L",
+        "\n*\u{1b}\u{1b}",
+        false
+    )]
+    #[trace]
+    fn validate_arbitrary(#[case] content: &str, #[case] path: &str, #[case] expected: bool) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.is_ok(), expected);
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate(content, path, &formatter);
+    }
+
+    #[rstest]
+    #[case(ValidationState::Valid, true)]
+    #[case(ValidationState::Invalid, false)]
+    #[case(ValidationState::SomeProblems, false)]
+    #[trace]
+    fn is_ok_tests(#[case] state: ValidationState, #[case] expected: bool) {
+        // Arrange
+
+        // Act
+        assert_eq!(state.is_ok(), expected);
+    }
+
+    #[test]
+    fn validate_success_brackets_in_section_name() {
+        // Arrange
+        let config = r#"
+[[*]]
+a = b
+c = d
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.is_ok());
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_success_inline_comments() {
+        // Arrange
+        let config = r#"
+[*]
+a = b # comment 1
+c = d # comment 2
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.is_ok());
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_fail_duplicate_keys_in_not_root() {
+        // Arrange
+        let config = r#"
+root = true
+[*]
+a = b
+a = e
+c = d
+
+[*.md]
+e = f"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(!result.duplicate_properties.is_empty());
+            assert!(result.duplicate_sections.is_empty());
+            assert!(result.similar_properties.is_empty());
+            assert_eq!(result.state(), ValidationState::Invalid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_duplicate_property_span() {
+        // Arrange
+        let config = "root = true\n\n[*]\nindent_size = 2\ncharset = utf-8\n  indent_size = 4\n";
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(
+                result.findings,
+                vec![Finding {
+                    kind: FindingKind::DuplicateProperty,
+                    section: "*",
+                    name: "indent_size",
+                    span: Some(Span {
+                        line: 6,
+                        col: 3,
+                        len: 15,
+                    }),
+                }]
+            );
+        });
+
+        // Act
+        validate_with(
+            config,
+            "",
+            &ValidationConfig::new().track_spans(true),
+            &formatter,
+        );
+    }
+
+    #[rstest]
+    #[case(false, None)]
+    #[case(true, Some(Span { line: 4, col: 16, len: 4 }))]
+    #[trace]
+    fn validate_invalid_value_span(#[case] track_spans: bool, #[case] expected: Option<Span>) {
+        // Arrange
+        let config = "[a]\r\nk = v\r\n[*]\rindent_style = tabs\n[a]\n";
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            let invalid: Vec<&Finding> = result
+                .findings
+                .iter()
+                .filter(|f| f.kind == FindingKind::InvalidValue)
+                .collect();
+            assert_eq!(invalid.len(), 1);
+            assert_eq!(invalid[0].name, "indent_style");
+            assert_eq!(invalid[0].span, expected);
+            assert!(result
+                .findings
+                .iter()
+                .any(|f| f.kind == FindingKind::DuplicateSection && f.name == "a"));
+        });
+
+        // Act
+        validate_with(
+            config,
+            "",
+            &ValidationConfig::new().track_spans(track_spans),
+            &formatter,
+        );
+    }
+
+    #[test]
+    fn validate_fail_similar_keys_in_not_root() {
+        // Arrange
+        let config = r#"
+root = true
+[*]
+ab = b
+dab = e
+c = d
+
+[*.md]
+e = f"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.duplicate_properties.is_empty());
+            assert!(result.duplicate_sections.is_empty());
+            assert!(!result.similar_properties.is_empty());
+            assert!(result.ext_problems.is_empty());
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_fail_duplicate_keys_in_root() {
+        // Arrange
+        let config = r#"
+root = true
+root = false
+
+[*]
+a = b
+c = d
+
+[*.md]
+e = f"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(!result.duplicate_properties.is_empty());
+            assert!(result.duplicate_sections.is_empty());
+            assert!(result.similar_properties.is_empty());
+            assert!(result.ext_problems.is_empty());
+            assert_eq!(result.state(), ValidationState::Invalid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_fail_duplicate_keys_ext_across_different_sections() {
+        // Arrange
+        let config = r#"
+[*.{md,txt}]
+a = b
+c = d
+
+[*.md]
+a = d
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.duplicate_properties.is_empty());
+            assert!(result.duplicate_sections.is_empty());
+            assert!(result.similar_properties.is_empty());
+            assert_eq!(result.ext_problems.len(), 1);
+            assert_eq!(result.state(), ValidationState::Invalid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_ext_conflicts() {
+        // Arrange
+        let config = r#"
+[*.{js,jsx}]
+quote = single
+indent_size = 2
+
+[*.jsx]
+quote = double
+indent_size = 2
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            let conflicts = result.ext_conflicts();
+            assert_eq!(conflicts.len(), 1);
+            assert_eq!(
+                conflicts["*.jsx"],
+                vec![&ExtDuplicate {
+                    name: "quote",
+                    values: vec![("*.jsx", "double"), ("*.{js,jsx}", "single")],
+                }]
+            );
+            assert_eq!(result.state(), ValidationState::Invalid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("charset", "utf-8", "utf-8", false)]
+    #[case("charset", "utf-8", "UTF-8", false)]
+    #[case("Charset", "utf-8", "UTF-8", false)]
+    #[case("charset", "utf-8", "latin1", true)]
+    #[case("a", "b", "b", false)]
+    #[case("a", "b", "B", true)]
+    #[trace]
+    fn ext_duplicate_is_conflicting(
+        #[case] name: &str,
+        #[case] first: &str,
+        #[case] second: &str,
+        #[case] expected: bool,
+    ) {
+        // Arrange
+        let duplicate = ExtDuplicate {
+            name,
+            values: vec![("*.md", first), ("*.{md,txt}", second)],
+        };
+
+        // Act
+        let actual = duplicate.is_conflicting();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("a = b", "a = b", ValidationState::SomeProblems)]
+    #[case("a = b", "a = c", ValidationState::Invalid)]
+    #[case("a = b", "a = B", ValidationState::Invalid)]
+    #[case("indent_size = 2", "indent_size = 2", ValidationState::SomeProblems)]
+    #[case(
+        "indent_style = tab",
+        "indent_style = Tab",
+        ValidationState::SomeProblems
+    )]
+    #[trace]
+    fn validate_ext_duplicate_state(
+        #[case] first: &str,
+        #[case] second: &str,
+        #[case] expected: ValidationState,
+    ) {
+        // Arrange
+        let config = format!("root = true\n[*.{{md,txt}}]\n{first}\n[*.md]\n{second}\n");
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.ext_problems.len(), 1);
+            assert_eq!(result.state(), expected);
+        });
+
+        // Act
+        validate(&config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_ext_conflicting_indent_style() {
+        // Arrange
+        let config = r#"
+[*.js]
+indent_style = tab
+
+[*.{js,ts}]
+indent_style = space
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            let conflicts = result.ext_conflicts();
+            assert_eq!(
+                conflicts.keys().copied().collect::<Vec<&str>>(),
+                vec!["*.js"]
+            );
+            assert_eq!(conflicts["*.js"][0].name, "indent_style");
+            assert_eq!(
+                conflicts["*.js"][0].values,
+                vec![("*.js", "tab"), ("*.{js,ts}", "space")]
+            );
+            assert_eq!(result.state(), ValidationState::Invalid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_redundant_duplicate_keys_ext_across_different_sections() {
+        // Arrange
+        let config = r#"
+[*.{md,txt}]
+charset = utf-8
+c = d
+
+[*.md]
+charset = UTF-8
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.ext_problems.len(), 1);
+            assert_eq!(
+                result.ext_problems[0].duplicates,
+                vec![ExtDuplicate {
+                    name: "charset",
+                    values: vec![("*.md", "UTF-8"), ("*.{md,txt}", "utf-8")],
+                }]
+            );
+            assert!(!result.ext_problems[0].duplicates[0].is_conflicting());
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case(false, 0)]
+    #[case(true, 1)]
+    #[trace]
+    fn validate_duplicate_keys_ext_differ_in_case(
+        #[case] case_insensitive: bool,
+        #[case] expected: usize,
+    ) {
+        // Arrange
+        let config = r#"
+[*.md]
+a = b
+
+[*.MD]
+a = d
+"#;
+        let validation_config = ValidationConfig::new().case_insensitive_globs(case_insensitive);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.ext_problems.len(), expected);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[test]
+    fn validate_fail_similar_keys_ext_across_different_sections() {
+        // Arrange
+        let config = r#"
+[*.{md,txt}]
+a_b_c = b
+x = d
+
+[*.md]
+d_a_b_c = d
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.duplicate_properties.is_empty());
+            assert!(result.duplicate_sections.is_empty());
+            assert!(result.similar_properties.is_empty());
+            assert_eq!(result.ext_problems.len(), 1);
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_fail_duplicate_sections() {
+        // Arrange
+        let config = r#"
+root = true
+
+[*]
+a = b
+c = d
+
+[*]
+e = f"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.duplicate_properties.is_empty());
+            assert!(!result.duplicate_sections.is_empty());
+            assert!(result.similar_properties.is_empty());
+            assert_eq!(result.state(), ValidationState::Invalid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_overlapping_sections() {
+        // Arrange
+        let config = r#"
+[*.js]
+a = b
+
+[src/*.js]
+c = d
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.overlapping_sections, vec![("*.js", "src/*.js")]);
+            assert!(result.duplicate_sections.is_empty());
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[*.{c,h}]\na = b\n[*.[ch]]\nc = d\n", vec![("*.{c,h}", "*.[ch]")])]
+    #[case("[{a,b}.md]\na = b\n[{a.md,b.md}]\nc = d\n", vec![("{a,b}.md", "{a.md,b.md}")])]
+    #[case("[*.{js,jsx}]\na = b\n[*.{jsx,js}]\nc = d\n", vec![("*.{js,jsx}", "*.{jsx,js}")])]
+    #[case("[*.{js,jsx}]\na = b\n[*.{ts,tsx}]\nc = d\n", vec![])]
+    #[case("[*.{js,jsx}]\na = b\n[*.js]\nc = d\n", vec![])]
+    #[case("[*.{js,jsx}]\na = b\n[*.js]\nc = d\n[*.jsx]\ne = f\n", vec![("*.{js,jsx}", "*.js"), ("*.{js,jsx}", "*.jsx")])]
+    #[case("[*.js]\na = b\n[*.jsx]\nc = d\n[*.{js,jsx}]\ne = f\n", vec![("*.{js,jsx}", "*.js"), ("*.{js,jsx}", "*.jsx")])]
+    #[case("[*.{js,jsx,ts}]\na = b\n[*.js]\nc = d\n[*.jsx]\ne = f\n", vec![])]
+    #[trace]
+    fn validate_equivalent_sections(#[case] config: &str, #[case] expected: Vec<(&str, &str)>) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.equivalent_sections, expected);
+            assert!(result.duplicate_sections.is_empty());
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn canonical_titles_map_to_first_equivalent_title() {
+        // Arrange
+        let titles = ["*.{a,b}", "*.md", "*.{b,a}", "*.[ab]", "*.[]"];
+        let mut cache = GlobCache::default();
+
+        // Act
+        let actual = canonical_titles(&titles, &mut cache);
+
+        // Assert
+        assert_eq!(
+            actual,
+            HashMap::from([
+                ("*.{a,b}", "*.{a,b}"),
+                ("*.md", "*.md"),
+                ("*.{b,a}", "*.{a,b}"),
+                ("*.[ab]", "*.{a,b}"),
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_equivalent_sections_some_problems() {
+        // Arrange
+        let config = "root = true\n[*.{c,h}]\nindent_size = 2\n[*.[ch]]\ncharset = utf-8\n";
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case(&[("indent_size", "2"), ("indent_size", "4")], vec!["indent_size"], vec![])]
+    #[case(&[("indent_style", "spaces")], vec![], vec![("indent_style", "spaces")])]
+    #[case(
+        &[("indent_style", "tabs"), ("indent_size", "2"), ("indent_style", "tab")],
+        vec!["indent_style"],
+        vec![("indent_style", "tabs")]
+    )]
+    #[case(&[("indent_style", "space"), ("indent_size", "2")], vec![], vec![])]
+    #[trace]
+    fn validate_section_tests(
+        #[case] properties: &[(&str, &str)],
+        #[case] duplicate_properties: Vec<&str>,
+        #[case] invalid_values: Vec<(&str, &str)>,
+    ) {
+        // Act
+        let actual = validate_section("*.md", properties);
+
+        // Assert
+        assert_eq!(
+            actual,
+            SectionReport {
+                title: "*.md",
+                duplicate_properties,
+                similar_properties: vec![],
+                invalid_values,
+            }
+        );
+    }
+
+    #[rstest]
+    #[case("root = true\n[ *.md ]\na = b\n", vec![" *.md "])]
+    #[case("root = true\n[*.md\t]\na = b\n", vec!["*.md\t"])]
+    #[case("root = true\n[*.md]\na = b\n", vec![])]
+    #[case("root = true\n[{a b,c}.md]\na = b\n", vec![])]
+    #[trace]
+    fn validate_whitespace_titles(#[case] config: &str, #[case] expected: Vec<&str>) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            let state = if expected.is_empty() {
+                ValidationState::Valid
+            } else {
+                ValidationState::SomeProblems
+            };
+            assert_eq!(result.whitespace_titles, expected);
+            assert_eq!(result.state(), state);
+            assert!(result
+                .whitespace_titles
+                .iter()
+                .all(|title| !glob::matches(title, "foo.md")));
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_disjoint_sections_not_overlapping() {
+        // Arrange
+        let config = r#"
+[*]
+x = y
+
+[*.md]
+a = b
+
+[*.rs]
+c = d
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.overlapping_sections.is_empty());
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("root = true\n[*]\na = b", true)]
+    #[case("ROOT = TRUE\n[*]\na = b", true)]
+    #[case("root = false\n[*]\na = b", false)]
+    #[case("[*]\na = b", false)]
+    #[case("[*]\nroot = true", false)]
+    #[trace]
+    fn validate_is_root(#[case] config: &str, #[case] expected: bool) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.is_root, expected);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_empty_section() {
+        // Arrange
+        let config = r#"
+root = true
+
+[*]
+a = b
+
+[*.txt]
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.empty_sections, vec!["*.txt"]);
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case(false, ValidationState::SomeProblems)]
+    #[case(true, ValidationState::Invalid)]
+    #[trace]
+    fn validate_similar_properties_strict(#[case] strict: bool, #[case] expected: ValidationState) {
+        // Arrange
+        let config = "[*]\na_bc = c\nbc = d\n";
+        let validation_config = ValidationConfig::new().strict(strict);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.similar_properties.len(), 1);
+            assert_eq!(result.state(), expected);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[rstest]
+    #[case(None, vec![("*.md", "xabc", "abc")])]
+    #[case(Some(1), vec![("*", "ba", "a"), ("*.md", "xabc", "abc")])]
+    #[case(Some(3), vec![("*.md", "xabc", "abc")])]
+    #[case(Some(4), vec![])]
+    #[trace]
+    fn validate_min_similarity_length(
+        #[case] min_len: Option<usize>,
+        #[case] expected: Vec<(&str, &str, &str)>,
+    ) {
+        // Arrange
+        let config = "[*]\nba = c\na = d\n[*.md]\nabc = e\nxabc = f\n";
+        let validation_config = match min_len {
+            Some(len) => ValidationConfig::new().min_similarity_length(len),
+            None => ValidationConfig::new(),
+        };
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            let actual: Vec<(&str, &str, &str)> = result
+                .similar_properties
+                .into_iter()
+                .flat_map(|(sec, pairs)| pairs.into_iter().map(move |(a, b)| (sec, a, b)))
+                .collect();
+            assert_eq!(actual, expected);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[test]
+    fn validate_valid_strict() {
+        // Arrange
+        let config = "root = true\n[*]\na = b\n";
+        let validation_config = ValidationConfig::new().strict(true);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[test]
+    fn validate_deprecated_property() {
+        // Arrange
+        let config = "root = true\n[*]\nOld_Key = a\nindent_size = 4\nother = b\n";
+        let validation_config = ValidationConfig::new()
+            .unknown_properties(true)
+            .deprecated_properties(vec![("old_key", "new_key")]);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.deprecated_properties, vec![("Old_Key", "new_key")]);
+            assert_eq!(
+                result.unknown_properties.get("*").unwrap(),
+                &vec![UnknownProperty {
+                    name: "other",
+                    suggestion: None
+                }]
+            );
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[rstest]
+    #[case("root = true\n[*]\ntab_width = 4\n")]
+    #[case("root = true\n[*]\ntab_size = 4\n")]
+    #[trace]
+    fn validate_current_property_not_deprecated(#[case] config: &str) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.deprecated_properties.is_empty());
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_section_property_counts() {
+        // Arrange
+        let config = "root = true\n[*]\na = b\nc = d\n[*.md]\ne = f\n";
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(
+                result.section_property_counts,
+                BTreeMap::from([("", 1), ("*", 2), ("*.md", 1)])
+            );
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\ncharset = utf-8\n[*.md]\ncharset = latin1\n", vec![("*.md", "charset", "utf-8", "latin1")])]
+    #[case("[*]\ncharset = utf-8\n[*.md]\ncharset = utf-8\n", vec![])]
+    #[case("[*]\ncharset = utf-8\n[*.md]\nCharset = UTF-8\n", vec![])]
+    #[case("[*]\ncharset = utf-8\n[*]\ncharset = latin1\n", vec![])]
+    #[case("[*.md]\ncharset = latin1\n", vec![])]
+    #[case("[*]\nindent_size = 2\n[*]\nindent_size = 4\n[*.md]\nindent_size = 2\n", vec![("*.md", "indent_size", "4", "2")])]
+    #[case("[*]\nindent_size = 2\n[*.md]\nindent_size = 3\nindent_size = 4\n", vec![("*.md", "indent_size", "2", "4")])]
+    #[case("[*]\nindent_size = 2\n[*.md]\nindent_size = unset\n", vec![])]
+    #[case("[*]\nindent_size = 2\n[*.md]\nindent_size = UNSET\nindent_size = 4\n", vec![("*.md", "indent_size", "2", "4")])]
+    #[case("[*]\nindent_size = 2\n[*.md]\nindent_size = 4\n[*]\nindent_size = 4\n", vec![("*.md", "indent_size", "2", "4")])]
+    #[case("[*.md]\nindent_size = 4\n[*]\nindent_size = 2\n", vec![])]
+    #[trace]
+    fn validate_override_conflicts(
+        #[case] content: &str,
+        #[case] expected: Vec<(&str, &str, &str, &str)>,
+    ) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            let actual: Vec<(&str, &str, &str, &str)> = result
+                .override_conflicts
+                .into_iter()
+                .flat_map(|(sec, conflicts)| {
+                    conflicts.into_iter().map(move |(k, p, c)| (sec, k, p, c))
+                })
+                .collect();
+            assert_eq!(actual, expected);
+        });
+
+        // Act
+        validate(content, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\na = b\n[*.md]\nc = d\n", vec![])]
+    #[case("[*.md]\nc = d\n[*]\na = b\n", vec![("*.md", "*")])]
+    #[case("[src/*.js]\na = b\n[*.js]\nc = d\n", vec![("src/*.js", "*.js")])]
+    #[case("[*.js]\na = b\n[*.{js,ts}]\nc = d\n", vec![("*.js", "*.{js,ts}")])]
+    #[case("[src/*]\na = b\n[src/**]\nc = d\n", vec![("src/*", "src/**")])]
+    #[case("[src/**]\na = b\n[src/*]\nc = d\n", vec![])]
+    #[case("[*]\na = b\n[**]\nc = d\n", vec![])]
+    #[case("[*.md]\na = b\n[*.txt]\nc = d\n", vec![])]
+    #[case("[?.md]\na = b\n[*.md]\nc = d\n[*]\ne = f\n", vec![("?.md", "*.md"), ("?.md", "*"), ("*.md", "*")])]
+    #[trace]
+    fn validate_shadowed_sections(#[case] content: &str, #[case] expected: Vec<(&str, &str)>) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.shadowed_sections, expected);
+        });
+
+        // Act
+        validate(content, "", &formatter);
+    }
+
+    #[test]
+    fn find_shadowed_sections_expands_every_title_once() {
+        // Arrange
+        let titles = ["?.md", "*.md", "*", "*.md", "?.md"];
+        let mut cache = GlobCache::default();
+
+        // Act
+        let actual = find_shadowed_sections(&titles, &mut cache);
+
+        // Assert
+        assert_eq!(actual, vec![("?.md", "*.md"), ("?.md", "*"), ("*.md", "*")]);
+        assert_eq!(cache.expanded.len(), 3);
+    }
+
+    #[rstest]
+    #[case("[*.go]\nindent_style = tab\nindent_size = tab\n", true, vec!["*.go"])]
+    #[case("[*]\nindent_size = tab\n[*.go]\nindent_size = TAB\n", true, vec!["*", "*.go"])]
+    #[case("[*.go]\nindent_style = tab\nindent_size = tab\ntab_width = 4\n", true, vec![])]
+    #[case("[*]\ntab_width = 4\n[*.go]\nindent_style = tab\nindent_size = tab\n", true, vec![])]
+    #[case("[*.go]\nindent_size = 4\n", true, vec![])]
+    #[case("[*.go]\nindent_style = tab\nindent_size = tab\n", false, vec![])]
+    #[trace]
+    fn validate_indent_size_tab_without_width(
+        #[case] content: &str,
+        #[case] enabled: bool,
+        #[case] expected: Vec<&str>,
+    ) {
+        // Arrange
+        let config = ValidationConfig::new().tab_width_fallback(enabled);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.indent_size_tab_without_width, expected);
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate_with(content, "", &config, &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\ninsert_final_newline = true\n[*.md]\ninsert_final_newline = false\n", vec![("*.md", "insert_final_newline", "true", "false")])]
+    #[case("[*]\ntrim_trailing_whitespace = false\n[*.md]\ntrim_trailing_whitespace = TRUE\n", vec![("*.md", "trim_trailing_whitespace", "false", "TRUE")])]
+    #[case("[*]\ninsert_final_newline = true\n[*.md]\ninsert_final_newline = true\n", vec![])]
+    #[case("[*]\ninsert_final_newline = true\n[*.md]\ninsert_final_newline = unset\n", vec![])]
+    #[case("[*]\nindent_size = 2\n[*.md]\nindent_size = 4\n", vec![])]
+    #[trace]
+    fn validate_boolean_flips(
+        #[case] content: &str,
+        #[case] expected: Vec<(&str, &str, &str, &str)>,
+    ) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.state(), ValidationState::Valid);
+            let actual: Vec<(&str, &str, &str, &str)> = result
+                .boolean_flips
+                .into_iter()
+                .flat_map(|(sec, flips)| flips.into_iter().map(move |(k, p, c)| (sec, k, p, c)))
+                .collect();
+            assert_eq!(actual, expected);
+        });
+
+        // Act
+        validate(content, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[*.md]\ninsert_final_newline = true\n[docs/*.md]\ninsert_final_newline = false\n", vec![("docs/*.md", "insert_final_newline", "true", "false")])]
+    #[case("[*]\ninsert_final_newline = false\n[*.md]\ninsert_final_newline = true\n[docs/*.md]\ninsert_final_newline = true\n", vec![("*.md", "insert_final_newline", "false", "true")])]
+    #[case("[*.md]\ninsert_final_newline = true\n[*.txt]\ninsert_final_newline = false\n", vec![])]
+    #[case("[docs/*.md]\ninsert_final_newline = false\n[*.md]\ninsert_final_newline = true\n", vec![])]
+    #[case("[*.md]\ninsert_final_newline = true\n[*.md]\ninsert_final_newline = false\n", vec![])]
+    #[trace]
+    fn find_boolean_flips_tests(
+        #[case] content: &str,
+        #[case] expected: Vec<(&str, &str, &str, &str)>,
+    ) {
+        // Arrange
+        let sections = editorconfig::parse(content);
+        let mut cache = GlobCache::default();
+
+        // Act
+        let actual: Vec<(&str, &str, &str, &str)> = find_boolean_flips(&sections, &mut cache)
+            .into_iter()
+            .flat_map(|(sec, flips)| flips.into_iter().map(move |(k, p, c)| (sec, k, p, c)))
+            .collect();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn validate_comment_only_section_not_empty() {
+        // Arrange
+        let config = "root = true\n\n[*.md]\n\n# note\n\n[*.txt]\n\n";
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.empty_sections, vec!["*.txt"]);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_oversized_value() {
+        // Arrange
+        let config = format!("[*]\na = b\nlong = {}\n", "x".repeat(5000));
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.oversized_properties, vec![("*", "long")]);
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate(&config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\na\u{1b}b = c\nd = e\n", vec!["a\u{1b}b"])]
+    #[case("[*]\na\u{fffd} = c\n[*.md]\na\u{fffd} = d\n", vec!["a\u{fffd}"])]
+    #[case("[*]\na\u{e001} = c\n", vec!["a\u{e001}"])]
+    #[case("[*]\nключ = c\ndéjà_vu = d\n", vec![])]
+    #[case("[*]\na\u{200b} = c\n", vec![])]
+    #[trace]
+    fn validate_invalid_key_chars(#[case] content: &str, #[case] expected: Vec<&str>) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.invalid_key_chars, expected);
+            if !expected.is_empty() {
+                assert_eq!(result.state(), ValidationState::SomeProblems);
+            }
+        });
+
+        // Act
+        validate(content, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\nindent_size\nfoo bar\n[*.md]\ncharset = utf-8\n", vec![2, 3])]
+    #[case("[*]\r\nindent_size\r\nfoo bar\r\ncharset = utf-8\r\n", vec![2, 3])]
+    #[case("[*]\n\n  \n# comment\nindent_size = 2\n", vec![])]
+    #[trace]
+    fn validate_lexer_errors(#[case] content: &str, #[case] expected: Vec<usize>) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            let lines: Vec<usize> = result.lexer_errors.iter().map(|e| e.line).collect();
+            assert_eq!(lines, expected);
+            if expected.is_empty() {
+                assert_eq!(result.state(), ValidationState::Valid);
+            } else {
+                assert_eq!(result.state(), ValidationState::SomeProblems);
+            }
+        });
+
+        // Act
+        validate(content, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\na = b\n[*.md\nc = d\n", vec![(3, "[*.md")], vec![])]
+    #[case("[[*]]\na = b\n", vec![], vec![])]
+    #[case("[[*]\na = b\n[*.md\nfoo\n", vec![(1, "[[*]"), (3, "[*.md")], vec![4])]
+    #[trace]
+    fn validate_unbalanced_brackets(
+        #[case] content: &str,
+        #[case] expected: Vec<(usize, &str)>,
+        #[case] expected_lexer_errors: Vec<usize>,
+    ) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.unbalanced_brackets, expected);
+            let lines: Vec<usize> = result.lexer_errors.iter().map(|e| e.line).collect();
+            assert_eq!(lines, expected_lexer_errors);
+            if expected.is_empty() {
+                assert_eq!(result.state(), ValidationState::Valid);
+            } else {
+                assert_eq!(result.state(), ValidationState::SomeProblems);
+            }
+        });
+
+        // Act
+        validate(content, "", &formatter);
+    }
+
+    #[test]
+    fn validate_lexer_errors_still_validates_following_sections() {
+        // Arrange
+        let content = "[*]\nindent_size\n[*.md]\ncharset = utf-8\ncharset = latin1\n";
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.lexer_errors.len(), 1);
+            assert_eq!(result.lexer_errors[0].line, 2);
+            assert_eq!(&content[result.lexer_errors[0].span.clone()], "indent_size");
+            assert_eq!(
+                result.duplicate_properties.get("*.md"),
+                Some(&vec!["charset"])
+            );
+            assert_eq!(result.state(), ValidationState::Invalid);
+        });
+
+        // Act
+        validate(content, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\na = b\tc\nd = e\n", true, vec![("*", "a")])]
+    #[case("[*]\na = \tb\t\nd = e f\n", true, vec![])]
+    #[case("[*]\na = b\tc\n", false, vec![])]
+    #[trace]
+    fn validate_tab_in_value(
+        #[case] content: &str,
+        #[case] enabled: bool,
+        #[case] expected: Vec<(&str, &str)>,
+    ) {
+        // Arrange
+        let config = ValidationConfig::new().tab_in_value(enabled);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.tab_in_value, expected);
+            if !expected.is_empty() {
+                assert_eq!(result.state(), ValidationState::SomeProblems);
+            }
+        });
+
+        // Act
+        validate_with(content, "", &config, &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\na=b\nc = d\ne = f\n", true, vec![("*", "a")])]
+    #[case("[*]\na=b\nc=d\n[*.md]\ne = f\n", true, vec![("*.md", "e")])]
+    #[case("[*]\na=b\nc = d\n", true, vec![("*", "a")])]
+    #[case("[*]\na= b\nc =d\ne = f\n", true, vec![])]
+    #[case("[*]\na = b\nc =\n", true, vec![])]
+    #[case("[*]\na=b\nc = d\ne = f\n", false, vec![])]
+    #[trace]
+    fn validate_assignment_style(
+        #[case] content: &str,
+        #[case] enabled: bool,
+        #[case] expected: Vec<(&str, &str)>,
+    ) {
+        // Arrange
+        let config = ValidationConfig::new().assignment_style(enabled);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.inconsistent_assignment_style, expected);
+            if !expected.is_empty() {
+                assert_eq!(result.state(), ValidationState::SomeProblems);
+            }
+        });
+
+        // Act
+        validate_with(content, "", &config, &formatter);
+    }
+
+    #[test]
+    fn validate_oversized_key() {
+        // Arrange
+        let key = "k".repeat(1025);
+        let config = format!("[*]\n{key} = b\n");
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.oversized_properties, vec![("*", key.as_str())]);
+        });
+
+        // Act
+        validate(&config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_bom_in_property_name() {
+        // Arrange
+        let config = "[*]\na = b\n\u{feff}c = d\n";
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(
+                result.encoding_warnings,
+                vec!["Property \\u{feff}c in section [*] contains U+FEFF character"]
+            );
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_mixed_line_endings() {
+        // Arrange
+        let config = "[*]\r\na = b\nc = d\r\n";
+        let validation_config = ValidationConfig::new().line_endings(true);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(
+                result.encoding_warnings,
+                vec!["Mixed line endings (LF: 1, CRLF: 2)"]
+            );
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\na = b\n")]
+    #[case("[*]\r\na = b\r\n")]
+    #[case("[*]\ra = b")]
+    #[case("[*]")]
+    #[trace]
+    fn find_mixed_line_endings_single_kind(#[case] content: &str) {
+        // Act
+        let actual = find_mixed_line_endings(content);
+
+        // Assert
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn validate_mixed_line_endings_disabled_by_default() {
+        // Arrange
+        let config = "[*]\r\na = b\nc = d\r\n";
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.encoding_warnings.is_empty());
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_zero_width_space_in_title() {
+        // Arrange
+        let config = "[*.md\u{200b}]\na = b\n";
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.encoding_warnings.len(), 1);
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_unknown_properties_with_suggestion() {
+        // Arrange
+        let config = r#"
+root = true
+
+[*]
+indnet_size = 2
+charset = utf-8
+ij_any_option = true
+"#;
+        let validation_config = ValidationConfig::new().unknown_properties(true);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.unknown_properties.len(), 1);
+            assert_eq!(
+                result.unknown_properties.get("*").unwrap(),
+                &vec![
+                    UnknownProperty {
+                        name: "indnet_size",
+                        suggestion: Some("indent_size")
+                    },
+                    UnknownProperty {
+                        name: "ij_any_option",
+                        suggestion: None
+                    },
+                ]
+            );
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[test]
+    fn validate_unknown_properties_disabled_by_default() {
+        // Arrange
+        let config = r#"
+[*]
+indnet_size = 2
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.unknown_properties.is_empty());
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_indent_style_inherited_from_star_section() {
+        // Arrange
+        let config = r#"
+[*]
+indent_style = space
+
+[*.md]
+indent_size = 2
+"#;
+        let validation_config = ValidationConfig::new().completeness(true);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.incomplete_sections.is_empty());
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[test]
+    fn validate_indent_size_without_indent_style() {
+        // Arrange
+        let config = r#"
+[*]
+charset = utf-8
+
+[*.md]
+indent_size = 2
+tab_width = 4
+"#;
+        let validation_config = ValidationConfig::new().completeness(true);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(
+                result.incomplete_sections.get("*.md"),
+                Some(&vec!["indent_size", "tab_width"])
+            );
+            assert_eq!(result.incomplete_sections.len(), 1);
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\nindent_style = space\ntab_width = 4\n", vec![("*", vec!["tab_width"])])]
+    #[case("[*]\nindent_style = space\n[*.md]\ntab_width = 4\n", vec![("*.md", vec!["tab_width"])])]
+    #[case("[*]\nindent_style = space\n[*.md]\nindent_style = tab\ntab_width = 4\n", vec![])]
+    #[case("[*]\nindent_style = tab\ntab_width = 4\n", vec![])]
+    #[case("[*]\ntab_width = 4\n", vec![])]
+    #[trace]
+    fn validate_useless_properties(#[case] config: &str, #[case] expected: Vec<(&str, Vec<&str>)>) {
+        // Arrange
+        let validation_config = ValidationConfig::new().useless_properties(true);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            let actual: Vec<(&str, Vec<&str>)> = result.useless_properties.into_iter().collect();
+            assert_eq!(actual, expected);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\nindentSize = 2\n", vec![("*", vec![("indentSize", "indent_size")])])]
+    #[case("[*]\nindent-size = 2\n", vec![("*", vec![("indent-size", "indent_size")])])]
+    #[case("[*]\nindent_size = 2\n", vec![])]
+    #[trace]
+    fn validate_non_canonical_keys(
+        #[case] config: &str,
+        #[case] expected: Vec<(&str, Vec<(&str, &str)>)>,
+    ) {
+        // Arrange
+        let validation_config = ValidationConfig::new().canonical_keys(true);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            let actual: Vec<(&str, Vec<(&str, &str)>)> = result
+                .non_canonical_keys
+                .iter()
+                .map(|(sec, keys)| (*sec, keys.iter().map(|(k, n)| (*k, n.as_str())).collect()))
+                .collect();
+            assert_eq!(actual, expected);
+            let expected_state = if expected.is_empty() {
+                ValidationState::Valid
+            } else {
+                ValidationState::SomeProblems
+            };
+            assert_eq!(result.state(), expected_state);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[test]
+    fn validate_non_canonical_keys_off_by_default() {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert!(result.non_canonical_keys.is_empty());
+        });
+
+        // Act
+        validate("[*]\nindentSize = 2\n", "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\nindent_style = tab\nindent_size = 4\ntab_width = 4\n", vec![])]
+    #[case("[*]\nindent_style = tab\nindent_size = 2\ntab_width = 4\n", vec![("*", vec![("2", "4")])])]
+    #[case("[*]\nindent_style = tab\ntab_width = 4\n", vec![])]
+    #[case("[*]\nindent_style = tab\nindent_size = 2\n", vec![])]
+    #[case("[*]\nindent_style = tab\nindent_size = tab\ntab_width = 4\n", vec![])]
+    #[case("[*]\nindent_style = space\nindent_size = 2\ntab_width = 4\n", vec![])]
+    #[case("[*]\nindent_style = tab\ntab_width = 4\n[*.md]\nindent_size = 2\n", vec![("*.md", vec![("2", "4")])])]
+    #[case("[*]\nindent_style = tab\nindent_size = 2\ntab_width = 4\n[*.md]\ncharset = utf-8\n", vec![("*", vec![("2", "4")])])]
+    #[case("[*]\nindent_style = tab\ntab_width = 4\n[*.md]\nindent_size = 2\ntab_width = unset\n", vec![])]
+    #[case("[*]\nindent_style = tab\ntab_width = 4\n[*.md]\nindent_style = unset\nindent_size = 2\n", vec![])]
+    #[trace]
+    fn validate_tab_width_conflicts(
+        #[case] config: &str,
+        #[case] expected: Vec<(&str, Vec<(&str, &str)>)>,
+    ) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            let actual: Vec<(&str, Vec<(&str, &str)>)> =
+                result.tab_width_conflicts.clone().into_iter().collect();
+            assert_eq!(actual, expected);
+            if !expected.is_empty() {
+                assert_eq!(result.state(), ValidationState::SomeProblems);
+            }
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\nindent_size = 2\n# indent_size = 4\n", vec!["# indent_size = 4"])]
+    #[case("[*]\nindent_size = 2\n;INDENT_SIZE=4\n", vec![";INDENT_SIZE=4"])]
+    #[case("[*]\n# indent_size = 4\nindent_size = 2\n", vec![])]
+    #[case("[*]\nindent_size = 2\n## indent_size = 4\n", vec!["## indent_size = 4"])]
+    #[case("[*]\nindent_size = 2\n# tab_width = 4\n", vec![])]
+    #[case("[*]\nindent_size = 2\n[*.md]\n# indent_size = 4\n", vec![])]
+    #[case("[*]\nindent_size = 2\n# use indent_size = 4 for docs\n", vec![])]
+    #[case("[*]\nindent_size = 2 # indent_size = 4\n", vec!["# indent_size = 4"])]
+    #[trace]
+    fn validate_commented_overrides(#[case] config: &str, #[case] expected: Vec<&str>) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.commented_overrides, expected);
+            assert!(result.is_ok());
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("root = true\n[*]\nindent_size = 2\n", vec![])]
+    #[case("[*]\nindent_size = 2\nroot = true\n", vec![("*", 3)])]
+    #[case("root = true\n[*]\nindent_size = 2\n[*.md]\nROOT = true\n", vec![("*.md", 5)])]
+    #[trace]
+    fn validate_misplaced_root(#[case] config: &str, #[case] expected: Vec<(&str, usize)>) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.misplaced_root, expected);
+            if !expected.is_empty() {
+                assert_eq!(result.state(), ValidationState::SomeProblems);
+            }
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[]\nindent_size = 2\n", vec![""])]
+    #[case("[*.{}]\nindent_size = 2\n", vec![])]
+    #[case("[*.md]\nindent_size = 2\n", vec![])]
+    #[case("indent_size = 2\n[*.md]\nindent_size = 2\n", vec![])]
+    #[case("root = true\n[]\na = b\n", vec![""])]
+    #[trace]
+    fn validate_dead_sections(#[case] config: &str, #[case] expected: Vec<&str>) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.dead_sections, expected);
+            if !expected.is_empty() {
+                assert_ne!(result.state(), ValidationState::Valid);
+            }
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case(false, vec![], 0)]
+    #[case(true, vec!["*.{a,b}"], 1)]
+    #[trace]
+    fn validate_reordered_brace_duplicates(
+        #[case] canonical: bool,
+        #[case] expected: Vec<&str>,
+        #[case] findings: usize,
+    ) {
+        // Arrange
+        let content = "[*.{a,b}]\nindent_size = 2\n[*.{b,a}]\ncharset = utf-8\n[*.c]\nx = y\n";
+        let config = ValidationConfig::new().canonical_duplicate_sections(canonical);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.duplicate_sections, expected);
+            assert_eq!(
+                result
+                    .findings
+                    .iter()
+                    .filter(|f| f.kind == FindingKind::DuplicateSection)
+                    .count(),
+                findings
+            );
+            assert_eq!(result.equivalent_sections.is_empty(), canonical);
+        });
+
+        // Act
+        validate_with(content, "", &config, &formatter);
+    }
+
+    #[test]
+    fn validate_duplicate_glob_members() {
+        // Arrange
+        let config = "[*.{md,md}]\nindent_size = 2\n[*.{c,h}]\nindent_size = 4\n";
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(
+                result.duplicate_glob_members,
+                BTreeMap::from([("*.{md,md}", vec!["md"])])
+            );
+            assert_eq!(result.state(), ValidationState::SomeProblems);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("[*]\nab = 1\ndab = 2 # editorconfiger-ignore: similar\n", 0)]
+    #[case("[*]\nab = 1 ; editorconfiger-ignore: similar\ndab = 2\n", 0)]
+    #[case("[*]\nab = 1\ndab = 2 # editorconfiger-ignore: duplicate\n", 1)]
+    #[case("[*]\nab = 1\ndab = 2\n# editorconfiger-ignore: similar\n", 1)]
+    #[case("[*]\nab = 1\ndab = 2\n", 1)]
+    #[trace]
+    fn validate_suppressed_similar(#[case] config: &str, #[case] expected: usize) {
+        // Arrange
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            let similar = result.similar_properties.get("*").map_or(0, Vec::len);
+            assert_eq!(similar, expected);
+            assert_eq!(
+                result
+                    .findings
+                    .iter()
+                    .filter(|f| f.kind == FindingKind::SimilarProperty)
+                    .count(),
+                expected
+            );
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[test]
+    fn validate_suppressed_by_category() {
+        // Arrange
+        let config = "[*]\na = 1\na = 2 # editorconfiger-ignore: duplicate\nindent_size = four # editorconfiger-ignore: invalid-value\nx_y = 1 # editorconfiger-ignore: unknown, similar\n";
+        let validation_config = ValidationConfig::new().unknown_properties(true);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert!(result.duplicate_properties.is_empty());
+            assert!(result.invalid_values.is_empty());
+            assert_eq!(
+                result
+                    .unknown_properties
+                    .get("*")
+                    .map(|u| u.iter().map(|p| p.name).collect()),
+                Some(vec!["a"])
+            );
+            assert!(result.findings.is_empty());
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[test]
+    fn validate_ignored_sections_skipped() {
+        // Arrange
+        let config = r#"
+[*.cs]
+a_b = c
+
+[*.generated.cs]
+a_b = d
+b = e
+b = f
+
+[*.{x,y}]
+b = e
+b = f
+"#;
+        let validation_config = ValidationConfig::new()
+            .ignore_sections(vec!["*.generated.cs".to_string(), "*.{x,y,z}".to_string()]);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.duplicate_properties.is_empty());
+            assert!(result.ext_problems.is_empty());
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate_with(config, "", &validation_config, &formatter);
+    }
+
+    #[rstest]
+    #[case("*.cs")]
+    #[case("*.{cs,csx}")]
+    #[case("{a,b}/**/*.[ch]")]
+    #[case("")]
+    #[trace]
+    fn glob_cache_same_as_parse(#[case] title: &str) {
+        // Arrange
+        let mut cache = GlobCache::default();
+
+        // Act
+        let first = cache.expand(title).to_vec();
+        let second = cache.expand(title).to_vec();
+
+        // Assert
+        assert_eq!(first, glob::parse(title));
+        assert_eq!(second, first);
+        assert_eq!(cache.expanded.len(), 1);
+    }
+
+    #[test]
+    fn validate_embedded_blocks() {
+        // Arrange
+        let markdown = "# Config\n\n```editorconfig\n[*]\nindent_size = 4\n```\n\nBad one:\n\n~~~ EditorConfig\n[*]\na = b\na = c\n~~~\n\n```ini\n[*]\nx = 1\nx = 2\n```\n";
+        let results = std::cell::RefCell::new(Vec::new());
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            results
+                .borrow_mut()
+                .push((result.path.to_string(), result.state()));
+        });
+
+        // Act
+        let state = validate_embedded(markdown, &formatter);
+
+        // Assert
+        assert_eq!(state, Some(ValidationState::Invalid));
+        assert_eq!(
+            results.into_inner(),
+            vec![
+                ("<embedded:3>".to_string(), ValidationState::Valid),
+                ("<embedded:10>".to_string(), ValidationState::Invalid),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("[*]\na = b\n[*.md]\nc = d\n", "[*]\na = b\n[*.md]\nc = d\n", true)]
+    #[case(
+        "[*]\na = b\ne = f\n[*.md]\nc = d\n",
+        "[*.md]\nc = d\n[*]\ne = f\na = b\n",
+        true
+    )]
+    #[case("[*]\na = b\n[*]\ne = f\n", "[*]\ne = f\na = b\n", true)]
+    #[case("[*]\na = b\n", "[*]\na = c\n", false)]
+    #[case("[*]\na = b\n", "[*]\na = b\nc = d\n", false)]
+    #[case("[*]\na = b\n", "[*.md]\na = b\n", false)]
+    #[case("[*]\na = \n", "[*]\n", false)]
+    #[case("", "", true)]
+    #[trace]
+    fn configs_equal_tests(#[case] content1: &str, #[case] content2: &str, #[case] expected: bool) {
+        // Act
+        let actual = configs_equal(content1, content2);
+        let reversed = configs_equal(content2, content1);
+
+        // Assert
+        assert_eq!(actual, expected);
+        assert_eq!(reversed, expected);
+    }
+
+    #[rstest]
+    #[case("no code\n", vec![])]
+    #[case("```editorconfig\n[*]\n```\n", vec![(1, "[*]\n")])]
+    #[case("```editorconfig\r\n[*]\r\n```", vec![(1, "[*]\r\n")])]
+    #[case("````editorconfig\n```\n[*]\n````\n", vec![(1, "```\n[*]\n")])]
+    #[case("```\n```editorconfig\n```\n", vec![])]
+    #[case("text\n```editorconfig\n[*]\na = b", vec![(2, "[*]\na = b")])]
+    #[trace]
+    fn embedded_configs_tests(#[case] markdown: &str, #[case] expected: Vec<(usize, &str)>) {
+        // Act
+        let actual = embedded_configs(markdown);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("", vec!["charset", "end_of_line", "insert_final_newline", "indent_style", "indent_size"])]
+    #[case("root = true\n[*.md]\ncharset = utf-8\n", vec!["charset", "end_of_line", "insert_final_newline", "indent_style", "indent_size"])]
+    #[case("root = true\n[*]\nindent_style = space\n", vec!["charset", "end_of_line", "insert_final_newline", "indent_size"])]
+    #[case("root = true\n[*]\ncharset = utf-8\nEND_OF_LINE = lf\ninsert_final_newline = true\n[*]\nindent_style = space\nindent_size = 4\n", vec![])]
+    #[trace]
+    fn missing_recommended_tests(#[case] content: &str, #[case] expected: Vec<&str>) {
+        // Act
+        let actual = missing_recommended(content);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn missing_recommended_custom_set() {
+        // Arrange
+        let config =
+            ValidationConfig::new().recommended_properties(vec!["charset", "max_line_length"]);
+
+        // Act
+        let actual = missing_recommended_with("[*]\ncharset = utf-8\n", &config);
+
+        // Assert
+        assert_eq!(actual, vec!["max_line_length"]);
+    }
+
+    #[rstest]
+    #[case("foo.md", vec![("indent_size", "2"), ("indent_style", "space"), ("tab_width", "2")])]
+    #[case("docs/foo.md", vec![("indent_size", "2"), ("indent_style", "space"), ("tab_width", "2")])]
+    #[case("foo.rs", vec![("indent_size", "4"), ("indent_style", "space"), ("tab_width", "4")])]
+    #[case("Makefile", vec![("indent_size", "8"), ("indent_style", "tab"), ("tab_width", "8")])]
+    #[trace]
+    fn effective_properties_tests(#[case] path: &str, #[case] expected: Vec<(&str, &str)>) {
+        // Arrange
+        let config = "root = true\n[*]\nindent_style = space\nindent_size = 4\n[*.md]\nINDENT_SIZE = 2\n[Makefile]\nindent_style = tab\nindent_size = tab\ntab_width = 8\n";
+
+        // Act
+        let actual = effective_properties(config, path);
+
+        // Assert
+        let expected: BTreeMap<String, String> = expected
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("", "")]
+    #[case("[*]\n", "[*]\n")]
+    #[case("[*]\nb=2\nA = 1\n", "[*]\na = 1\nb = 2\n")]
+    #[case(
+        "[*]\na = 1\n[*.md]\nc = 3\n[*]\nb = 2\na = 4\n",
+        "[*]\na = 4\nb = 2\n\n[*.md]\nc = 3\n"
+    )]
+    #[case(
+        "[*]\na = 1\n[*.md]\na = 2\n[*]\na = 4\n",
+        "[*]\na = 1\n\n[*.md]\na = 2\n\n[*]\na = 4\n"
+    )]
+    #[case(
+        "[*]\na = 1\n[*.md]\na = 2\n[*]\nb = 3\n[*]\na = 4\n",
+        "[*]\na = 1\nb = 3\n\n[*.md]\na = 2\n\n[*]\na = 4\n"
+    )]
+    #[case("[*]\nroot = true\na = 1\n", "[*]\na = 1\nroot = true\n")]
+    #[case(
+        "a = 1\nroot = true\n[*]\nb = 2\n",
+        "root = true\na = 1\n\n[*]\nb = 2\n"
+    )]
+    #[case(
+        "# top\nroot = true\n\n[*]\n; note\nb = 2\n",
+        "# top\nroot = true\n\n[*]\n; note\nb = 2\n"
+    )]
+    #[case("# top\n\n[*]\nb = 2\n", "# top\n\n[*]\nb = 2\n")]
+    #[case(
+        "[*]\nlist = a,  b\nkey  =\tvalue\n",
+        "[*]\nkey = value\nlist = a,  b\n"
+    )]
+    #[case("[*]\ncolor = #fff\n", "[*]\ncolor = #fff\n")]
+    #[case("[*]\nb = c # note\na = 1\n", "[*]\na = 1\nb = c # note\n")]
+    #[case(
+        "[*]\nb = 2 ; x\n# own line\na = #1\n",
+        "[*]\n# own line\na = #1\nb = 2 ; x\n"
+    )]
+    #[trace]
+    fn canonicalize_tests(#[case] content: &str, #[case] expected: &str) {
+        // Arrange
+
+        // Act
+        let actual = canonicalize(content).unwrap();
+
+        // Assert
+        assert_eq!(actual, expected);
+        assert_eq!(canonicalize(&actual).unwrap(), actual);
+        for path in ["x.md", "x.rs"] {
+            assert_eq!(
+                effective_properties(&actual, path),
+                effective_properties(content, path)
+            );
+        }
+    }
+
+    #[test]
+    fn canonicalize_invalid_line() {
+        // Arrange
+        let content = "[*]\nindent_size\n";
+
+        // Act
+        let actual = canonicalize(content);
+
+        // Assert
+        assert!(matches!(actual, Err(Error::Syntax { line: 2, .. })));
+    }
+
+    #[rstest]
+    #[case("foo.md", vec![("charset", "utf-8")])]
+    #[case("foo.rs", vec![("charset", "utf-8"), ("indent_size", "4"), ("tab_width", "4")])]
+    #[case("docs/foo.md", vec![("charset", "utf-8"), ("indent_size", "2"), ("tab_width", "2")])]
+    #[trace]
+    fn effective_properties_unset(#[case] path: &str, #[case] expected: Vec<(&str, &str)>) {
+        // Arrange
+        let config = "[*]\ncharset = utf-8\nindent_size = 4\n[*.md]\nindent_size = Unset\n[docs/*.md]\nindent_size = 2\n";
+
+        // Act
+        let actual = effective_properties(config, path);
+
+        // Assert
+        let expected: BTreeMap<String, String> = expected
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("[*]\nindent_style = tab\n", vec![("indent_size", "tab"), ("indent_style", "tab")])]
+    #[case("[*]\nindent_style = tab\ntab_width = 2\n", vec![("indent_size", "2"), ("indent_style", "tab"), ("tab_width", "2")])]
+    #[case("[*.md]\nindent_size = 2\n", vec![])]
+    #[case("", vec![])]
+    #[case("[*]\nindent_style = tab\nindent_size = unset\n", vec![("indent_size", "tab"), ("indent_style", "tab")])]
+    #[trace]
+    fn effective_properties_indentation(#[case] config: &str, #[case] expected: Vec<(&str, &str)>) {
+        // Act
+        let actual = effective_properties(config, "main.c");
+
+        // Assert
+        let expected: BTreeMap<String, String> = expected
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("src/app.js", vec![("indent_size", "2"), ("tab_width", "2")])]
+    #[case("lib/app.ts", vec![("indent_size", "2"), ("tab_width", "2")])]
+    #[case("lib/nested/app.ts", vec![])]
+    #[case("docs/app.js", vec![])]
+    #[case("src/app.rs", vec![])]
+    #[trace]
+    fn effective_properties_several_lists_title(
+        #[case] path: &str,
+        #[case] expected: Vec<(&str, &str)>,
+    ) {
+        // Arrange
+        let config = "root = true\n[{src,lib}/*.{js,ts}]\nindent_size = 2\n";
+
+        // Act
+        let actual = effective_properties(config, path);
+
+        // Assert
+        let expected: BTreeMap<String, String> = expected
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("[*.{js,ts}]\n", vec!["*.js", "*.ts"])]
+    #[case("[*.[ch]]\n", vec!["*.c", "*.h"])]
+    #[case("[f[a-c].txt]\n", vec!["fa.txt", "fb.txt", "fc.txt"])]
+    #[case("[f{1..3}.txt]\n", vec!["f{1..3}.txt"])]
+    #[case("[*]\n", vec!["*"])]
+    #[trace]
+    fn expanded_sections_tests(#[case] content: &str, #[case] expected: Vec<&str>) {
+        // Arrange
+        let title = &content[1..content.len() - 2];
+
+        // Act
+        let actual = expanded_sections(content);
+
+        // Assert
+        assert_eq!(
+            actual,
+            vec![(
+                title.to_string(),
+                expected.iter().map(ToString::to_string).collect::<Vec<_>>()
+            )]
+        );
+    }
+
+    #[test]
+    fn expanded_sections_skips_preamble() {
+        // Arrange
+        let content = "root = true\n[*.md]\na = b\n[*.md]\nc = d\n";
+
+        // Act
+        let actual = expanded_sections(content);
+
+        // Assert
+        assert_eq!(actual.len(), 2);
+        assert!(actual
+            .iter()
+            .all(|(title, patterns)| title == "*.md" && patterns == &["*.md"]));
+    }
+
+    #[test]
+    fn validate_repeated_sections_same_with_cache() {
+        // Arrange
+        let config = "[*.cs]\na = b\n[*.cs]\nc = d\n[src/*.cs]\ne = f\n[*.[c]s]\ng = h\n";
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert_eq!(result.duplicate_sections, vec!["*.cs"]);
+            assert_eq!(
+                result.overlapping_sections,
+                vec![("*.cs", "src/*.cs"), ("src/*.cs", "*.[c]s")]
+            );
+            assert_eq!(result.equivalent_sections, vec![("*.cs", "*.[c]s")]);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("*.md", "*.md", true)]
+    #[case("*.md", "*.{md,txt}", true)]
+    #[case("*.{md,txt}", "*.md", false)]
+    #[case("*.md", "*.txt", false)]
+    #[case("", "*.txt", false)]
+    #[trace]
+    fn is_ignored_tests(#[case] title: &str, #[case] pattern: &str, #[case] expected: bool) {
+        // Arrange
+        let config = ValidationConfig::new().ignore_sections(vec![pattern.to_string()]);
+
+        // Act
+        let actual = config.is_ignored(title, &mut GlobCache::default());
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn validate_completeness_disabled_by_default() {
+        // Arrange
+        let config = r#"
+[*.md]
+indent_size = 2
+"#;
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.incomplete_sections.is_empty());
+            assert_eq!(result.state(), ValidationState::Valid);
+        });
+
+        // Act
+        validate(config, "", &formatter);
+    }
+
+    #[rstest]
+    #[case("*.js", "src/*.js", true)]
+    #[case("*.js", "src/**/*.js", true)]
+    #[case("**/*.js", "src/*.js", true)]
+    #[case("src/**/*.js", "src/lib/*.js", true)]
+    #[case("src/*.js", "src/lib/*.js", false)]
+    #[case("src/*.js", "*.js", false)]
+    #[case("*.js", "*.js", false)]
+    #[case("*.md", "src/*.rs", false)]
+    #[trace]
+    fn is_refinement_tests(#[case] wide: &str, #[case] narrow: &str, #[case] expected: bool) {
+        // Arrange
+
+        // Act
+        let actual = is_refinement(wide, narrow);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn validate_all_missing_root() {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let top = dir.path();
+        let sub = top.join("sub");
+        let other = top.join("other");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::create_dir_all(&other).unwrap();
+        std::fs::write(top.join(EDITOR_CONFIG), "root = false\n[*]\na = b\n").unwrap();
+        std::fs::write(sub.join(EDITOR_CONFIG), "[*]\na = c\n").unwrap();
+        std::fs::write(other.join(EDITOR_CONFIG), "root = true\n[*]\na = d\n").unwrap();
+        let options = WalkOptions::new().missing_root(true);
+        let formatter = TestFormatter::new(|_: ValidationResult| {});
+        let err = TestErrorer::default();
+
+        // Act
+        let report = validate_all_with(
+            top.to_str().unwrap(),
+            &options,
+            &ValidationConfig::default(),
+            &formatter,
+            &err,
+        );
+
+        // Assert
+        assert_eq!(report.files, 3);
+        assert_eq!(
+            report.missing_root,
+            vec![top.join(EDITOR_CONFIG).to_str().unwrap().to_string()]
+        );
+    }
+
+    #[test]
+    fn validate_all_nested_root() {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let top = dir.path();
+        let sub = top.join("sub");
+        let inner = sub.join("inner");
+        let other = top.join("other");
+        std::fs::create_dir_all(&inner).unwrap();
+        std::fs::create_dir_all(&other).unwrap();
+        std::fs::write(top.join(EDITOR_CONFIG), "root = true\n[*]\na = b\n").unwrap();
+        std::fs::write(sub.join(EDITOR_CONFIG), "[*]\na = c\n").unwrap();
+        std::fs::write(inner.join(EDITOR_CONFIG), "root = true\n[*]\na = d\n").unwrap();
+        std::fs::write(other.join(EDITOR_CONFIG), "[*]\na = e\n").unwrap();
+        let options = WalkOptions::new().nested_root(true);
+        let formatter = TestFormatter::new(|_: ValidationResult| {});
+        let err = TestErrorer::default();
+
+        // Act
+        let report = validate_all_with(
+            top.to_str().unwrap(),
+            &options,
+            &ValidationConfig::default(),
+            &formatter,
+            &err,
+        );
+
+        // Assert
+        assert_eq!(report.files, 4);
+        assert_eq!(
+            report.nested_root,
+            vec![inner.join(EDITOR_CONFIG).to_str().unwrap().to_string()]
+        );
+        assert!(report.missing_root.is_empty());
+    }
+
+    #[rstest]
+    #[case(vec!["/a/.editorconfig", "/a/b/c/.editorconfig", "/d/.editorconfig"], vec!["/a/b/c/.editorconfig"])]
+    #[case(vec!["/a/.editorconfig", "/ab/.editorconfig"], vec![])]
+    #[case(vec!["/a/b/.editorconfig"], vec![])]
+    #[trace]
+    fn find_nested_tests(#[case] files: Vec<&str>, #[case] expected: Vec<&str>) {
+        // Arrange
+        let files: Vec<&Path> = files.into_iter().map(Path::new).collect();
+
+        // Act
+        let actual = find_nested(&files);
+
+        // Assert
+        assert_eq!(
+            actual,
+            expected.into_iter().map(Path::new).collect::<Vec<&Path>>()
+        );
+    }
+
+    #[test]
+    fn list_config_files_nested() {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        let inner = sub.join("inner");
+        std::fs::create_dir_all(&inner).unwrap();
+        std::fs::write(dir.path().join(EDITOR_CONFIG), "[*]\n").unwrap();
+        std::fs::write(inner.join(EDITOR_CONFIG), "[*]\n").unwrap();
+        std::fs::write(sub.join("other.txt"), "[*]\n").unwrap();
+
+        // Act
+        let mut actual = list_config_files(dir.path().to_str().unwrap(), &WalkOptions::default());
+
+        // Assert
+        actual.sort();
+        assert_eq!(
+            actual,
+            vec![dir.path().join(EDITOR_CONFIG), inner.join(EDITOR_CONFIG)]
+        );
+    }
+
+    #[test]
+    fn validate_all_custom_file_name() {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(dir.path().join(".my-editorconfig"), "[*]\na = b\n").unwrap();
+        std::fs::write(sub.join(".my-editorconfig"), "[*]\na = b\n").unwrap();
+        std::fs::write(sub.join(EDITOR_CONFIG), "[*]\na = b\n").unwrap();
+        let options = WalkOptions::new().file_name(".my-editorconfig");
+        let validated = std::cell::RefCell::new(Vec::new());
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            validated.borrow_mut().push(PathBuf::from(result.path));
+        });
+
+        // Act
+        let report = validate_all_with(
+            dir.path().to_str().unwrap(),
+            &options,
+            &ValidationConfig::default(),
+            &formatter,
+            &TestErrorer::default(),
+        );
+
+        // Assert
+        let mut validated = validated.into_inner();
+        validated.sort();
+        assert_eq!(
+            validated,
+            vec![
+                dir.path().join(".my-editorconfig"),
+                sub.join(".my-editorconfig")
+            ]
+        );
+        assert_eq!(report.found, 2);
+    }
+
+    #[rstest]
+    #[case(0, vec![""])]
+    #[case(1, vec!["", "sub"])]
+    #[case(2, vec!["", "sub", "sub/inner"])]
+    #[trace]
+    fn validate_all_max_depth(#[case] depth: usize, #[case] expected: Vec<&str>) {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let inner = dir.path().join("sub").join("inner");
+        std::fs::create_dir_all(&inner).unwrap();
+        for sub in ["", "sub", "sub/inner"] {
+            std::fs::write(dir.path().join(sub).join(EDITOR_CONFIG), "[*]\n").unwrap();
+        }
+        let options = WalkOptions::new().max_depth(depth);
+        let validated = std::cell::RefCell::new(Vec::new());
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            validated.borrow_mut().push(PathBuf::from(result.path));
+        });
+
+        // Act
+        let report = validate_all_with(
+            dir.path().to_str().unwrap(),
+            &options,
+            &ValidationConfig::default(),
+            &formatter,
+            &TestErrorer::default(),
+        );
+
+        // Assert
+        let mut validated = validated.into_inner();
+        validated.sort();
+        let expected: Vec<PathBuf> = expected
+            .iter()
+            .map(|sub| dir.path().join(sub).join(EDITOR_CONFIG))
+            .collect();
+        assert_eq!(validated, expected);
+        assert_eq!(report.found, expected.len());
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    #[trace]
+    fn validate_all_property_frequency(#[case] enabled: bool) {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(
+            dir.path().join(EDITOR_CONFIG),
+            "root = true\n[*]\nindent_style = space\nfoo = Bar\n[*.md]\nindent_style = tab\n",
+        )
+        .unwrap();
+        std::fs::write(
+            sub.join(EDITOR_CONFIG),
+            "[*]\nIndent_Style = Space\nfoo = bar\n[*.txt]\nfoo = baz\n",
+        )
+        .unwrap();
+        let config = ValidationConfig::new().ignore_sections(vec!["*.txt".to_owned()]);
+
+        // Act
+        let report = validate_all_with(
+            dir.path().to_str().unwrap(),
+            &WalkOptions::new().property_frequency(enabled),
+            &config,
+            &TestFormatter::new(|_| {}),
+            &TestErrorer::default(),
+        );
+
+        // Assert
+        let expected: Vec<(String, String, usize)> = if enabled {
+            [
+                ("indent_style", "space", 2),
+                ("foo", "Bar", 1),
+                ("foo", "bar", 1),
+                ("indent_style", "tab", 1),
+                ("root", "true", 1),
+            ]
+            .into_iter()
+            .map(|(name, value, count)| (name.to_owned(), value.to_owned(), count))
+            .collect()
+        } else {
+            Vec::new()
+        };
+        assert_eq!(report.property_frequency, expected);
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    #[trace]
+    fn validate_all_relative_paths(#[case] relative: bool) {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(sub.join(EDITOR_CONFIG), "root = true\n[*]\n").unwrap();
+        std::fs::write(dir.path().join(EDITOR_CONFIG), "[*]\n").unwrap();
+        let options = WalkOptions::new()
+            .relative_paths(relative)
+            .missing_root(true);
+        let validated = std::cell::RefCell::new(Vec::new());
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            validated.borrow_mut().push(PathBuf::from(result.path));
+        });
+
+        // Act
+        let report = validate_all_with(
+            dir.path().to_str().unwrap(),
+            &options,
+            &ValidationConfig::default(),
+            &formatter,
+            &TestErrorer::default(),
+        );
+
+        // Assert
+        let base = if relative { Path::new("") } else { dir.path() };
+        let mut validated = validated.into_inner();
+        validated.sort();
+        let expected = vec![
+            base.join(EDITOR_CONFIG),
+            base.join("sub").join(EDITOR_CONFIG),
+        ];
+        assert_eq!(validated, expected);
+        assert_eq!(
+            report.missing_root,
+            vec![base.join(EDITOR_CONFIG).to_string_lossy().into_owned()]
+        );
+    }
+
+    #[rstest]
+    #[case(b"\xEF\xBB\xBF[*]\na = b\n", "[*]\na = b\n")]
+    #[case(b"[*]\na = b\n", "[*]\na = b\n")]
+    #[case(b"ab", "ab")]
+    #[case(b"", "")]
+    #[trace]
+    fn read_content_skips_bom(#[case] bytes: &[u8], #[case] expected: &str) {
+        // Act
+        let actual = read_content(bytes).unwrap();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn validate_zip_archive() {
+        use zip::write::SimpleFileOptions;
+        use zip::ZipWriter;
+
+        // Arrange
+        let mut writer = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        writer.start_file(EDITOR_CONFIG, options).unwrap();
+        writer.write_all(b"root = true\n[*]\na = b\n").unwrap();
+        writer.start_file("README.md", options).unwrap();
+        writer.write_all(b"[*]\na = b\na = c\n").unwrap();
+        writer.start_file("sub/dir/.editorconfig", options).unwrap();
+        writer.write_all(b"[*]\na = b\na = c\n").unwrap();
+        let archive = writer.finish().unwrap();
+        let validated = std::cell::Cell::new(0);
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            validated.set(validated.get() + 1);
+            if result.path == "ci.zip!sub/dir/.editorconfig" {
+                assert_eq!(result.duplicate_properties.get("*"), Some(&vec!["a"]));
+            } else {
+                assert_eq!(result.path, "ci.zip!.editorconfig");
+                assert_eq!(result.state(), ValidationState::Valid);
+            }
+        });
+        let err = TestErrorer::default();
+
+        // Act
+        let state = validate_zip("ci.zip", archive, &formatter, &err);
+
+        // Assert
+        assert_eq!(state, Some(ValidationState::Invalid));
+        assert_eq!(validated.get(), 2);
+        assert!(err.errors.borrow().is_empty());
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn validate_archive_not_zip() {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ci.zip");
+        std::fs::write(&path, "[*]\na = b\n").unwrap();
+        let formatter = TestFormatter::new(|_: ValidationResult| {
+            panic!("nothing must be validated");
+        });
+        let err = TestErrorer::default();
+
+        // Act
+        let state = validate_archive(path.to_str().unwrap(), &formatter, &err);
+
+        // Assert
+        assert_eq!(state, None);
+        assert_eq!(err.errors.borrow().len(), 1);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn validate_one_gzip_compressed() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        // Arrange
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"[*]\na = b\na = c\n").unwrap();
+        let compressed = encoder.finish().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".editorconfig.gz");
+        std::fs::write(&path, compressed).unwrap();
+        let expected_path = dir.path().join(EDITOR_CONFIG);
+        let expected_path = expected_path.to_str().unwrap();
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            // Assert
+            assert_eq!(result.path, expected_path);
+            assert_eq!(result.duplicate_properties.get("*"), Some(&vec!["a"]));
+        });
+        let err = TestErrorer::default();
+
+        // Act
+        let state = validate_one(path.to_str().unwrap(), &formatter, &err);
+
+        // Assert
+        assert_eq!(state, Some(ValidationState::Invalid));
+        assert!(err.errors.borrow().is_empty());
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn list_config_files_compressed() {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(EDITOR_CONFIG), "[*]\n").unwrap();
+        std::fs::write(dir.path().join(".editorconfig.gz"), "").unwrap();
+        let path = dir.path().to_str().unwrap();
+
+        // Act
+        let plain = list_config_files(path, &WalkOptions::default());
+        let mut all = list_config_files(path, &WalkOptions::default().compressed(true));
+
+        // Assert
+        all.sort();
+        assert_eq!(plain, vec![dir.path().join(EDITOR_CONFIG)]);
+        assert_eq!(
+            all,
+            vec![
+                dir.path().join(EDITOR_CONFIG),
+                dir.path().join(".editorconfig.gz")
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn validate_all_non_utf8_directory() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join(OsStr::from_bytes(b"sub\xff"));
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(sub.join(EDITOR_CONFIG), "[*]\na = b\na = c\n").unwrap();
+        let formatter = TestFormatter::new(|result: ValidationResult| {
+            assert!(result.path.contains("sub\u{fffd}"));
+            assert_eq!(result.state(), ValidationState::Invalid);
+        });
+        let err = TestErrorer::default();
+
+        // Act
+        let report = validate_all_with(
+            dir.path().to_str().unwrap(),
+            &WalkOptions::default(),
+            &ValidationConfig::default(),
+            &formatter,
+            &err,
+        );
+
+        // Assert
+        assert_eq!(report.files, 1);
+        assert!(err.errors.borrow().is_empty());
+    }
+
+    #[rstest]
+    #[case(false, 2)]
+    #[case(true, 1)]
+    #[trace]
+    fn validate_all_unreadable_files(#[case] fail_fast: bool, #[case] expected: usize) {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        std::fs::create_dir_all(&sub).unwrap();
+        // Invalid UTF-8 cannot be read into string
+        std::fs::write(dir.path().join(EDITOR_CONFIG), b"[*]\xff\xfe").unwrap();
+        std::fs::write(sub.join(EDITOR_CONFIG), b"[*]\xff\xfe").unwrap();
+        let path = dir.path().to_str().unwrap();
+        let formatter = TestFormatter::new(|_: ValidationResult| {});
+
+        // Act
+        let (files, errors) = if fail_fast {
+            let err = FailFastErrorer::default();
+            let report = validate_all_with(
+                path,
+                &WalkOptions::default(),
+                &ValidationConfig::default(),
+                &formatter,
+                &err,
+            );
+            let errors = err.errors.borrow().len();
+            assert_eq!(report.errors, errors);
+            (report.files, errors)
+        } else {
+            let err = TestErrorer::default();
+            let report = validate_all_with(
+                path,
+                &WalkOptions::default(),
+                &ValidationConfig::default(),
+                &formatter,
+                &err,
+            );
+            let errors = err.errors.borrow().len();
+            assert_eq!(report.errors, errors);
+            assert_eq!(report.worst_state, None);
+            (report.files, errors)
+        };
+
+        // Assert
+        assert_eq!(files, expected);
+        assert_eq!(errors, expected);
+    }
+
+    #[test]
+    fn validate_all_counts_readable_and_unreadable() {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(dir.path().join(EDITOR_CONFIG), "root = true\n[*]\na = b\n").unwrap();
+        // Invalid UTF-8 cannot be read into string
+        std::fs::write(sub.join(EDITOR_CONFIG), b"[*]\xff\xfe").unwrap();
+        let formatter = TestFormatter::new(|_: ValidationResult| {});
+        let err = TestErrorer::default();
+
+        // Act
+        let counts = validate_all(dir.path().to_str().unwrap(), &formatter, &err);
+
+        // Assert
+        assert_eq!(
+            counts,
+            FileCounts {
+                found: 2,
+                validated: 1,
+                errored: 1
+            }
+        );
+        assert_eq!(*counts, 2);
+    }
+
+    #[rstest]
+    #[case(ValidationState::Valid, ValidationState::Valid, ValidationState::Valid)]
+    #[case(
+        ValidationState::Valid,
+        ValidationState::SomeProblems,
+        ValidationState::SomeProblems
+    )]
+    #[case(
+        ValidationState::SomeProblems,
+        ValidationState::Valid,
+        ValidationState::SomeProblems
+    )]
+    #[case(
+        ValidationState::SomeProblems,
+        ValidationState::Invalid,
+        ValidationState::Invalid
+    )]
+    #[case(
+        ValidationState::Invalid,
+        ValidationState::Valid,
+        ValidationState::Invalid
+    )]
+    #[trace]
+    fn validation_state_worst(
+        #[case] first: ValidationState,
+        #[case] second: ValidationState,
+        #[case] expected: ValidationState,
+    ) {
+        // Act
+        let actual = first.worst(second);
 
-    impl<F> TestCompareFormatter<F>
-    where
-        F: Fn(BTreeMap<&str, Vec<CompareItem>>),
-    {
-        fn new(assert: F) -> Self {
-            Self { assert }
-        }
+        // Assert
+        assert_eq!(actual, expected);
     }
 
-    impl<F> ValidationFormatter for TestFormatter<F>
-    where
-        F: Fn(ValidationResult),
-    {
-        fn format(&self, result: ValidationResult) {
-            (self.assert)(result);
-        }
+    #[test]
+    fn validate_all_worst_state() {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(dir.path().join(EDITOR_CONFIG), "root = true\n[*]\na = b\n").unwrap();
+        std::fs::write(sub.join(EDITOR_CONFIG), "[*]\nab = c\ndab = d\n").unwrap();
+        let formatter = TestFormatter::new(|_: ValidationResult| {});
+        let err = TestErrorer::default();
+
+        // Act
+        let report = validate_all_with(
+            dir.path().to_str().unwrap(),
+            &WalkOptions::default(),
+            &ValidationConfig::default(),
+            &formatter,
+            &err,
+        );
+
+        // Assert
+        assert_eq!(report.errors, 0);
+        assert_eq!(report.worst_state, Some(ValidationState::SomeProblems));
     }
 
-    impl<F> ComparisonFormatter for TestCompareFormatter<F>
-    where
-        F: Fn(BTreeMap<&str, Vec<CompareItem>>),
-    {
-        fn format(&self, result: BTreeMap<&str, Vec<CompareItem>>) {
-            (self.assert)(result);
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    #[trace]
+    fn validate_all_timings(#[case] enabled: bool) {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(dir.path().join(EDITOR_CONFIG), "root = true\n[*]\na = b\n").unwrap();
+        std::fs::write(sub.join(EDITOR_CONFIG), "[*]\na = c\n").unwrap();
+        let formatter = TestFormatter::new(|_: ValidationResult| {});
+        let err = TestErrorer::default();
+
+        // Act
+        let report = validate_all_with(
+            dir.path().to_str().unwrap(),
+            &WalkOptions::new().timings(enabled),
+            &ValidationConfig::default(),
+            &formatter,
+            &err,
+        );
+
+        // Assert
+        assert_eq!(report.timings.is_some(), enabled);
+        if let Some(timings) = report.timings {
+            assert_eq!(timings.files.len(), 2);
+            assert!(timings.files[0].1 >= timings.files[1].1);
+            assert_eq!(
+                timings.validation,
+                timings.files.iter().map(|(_, d)| *d).sum::<Duration>()
+            );
         }
     }
 
     #[test]
-    fn validate_success() {
+    fn validate_all_missing_root_disabled_by_default() {
         // Arrange
-        let config = r#"
-root = true
-[*]
-a = b
-c = d
-
-[*.md]
-e = f"#;
-        let formatter = TestFormatter::new(|result: ValidationResult| {
-            assert!(result.is_ok());
-            assert_eq!(result.state(), ValidationState::Valid);
-        });
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(EDITOR_CONFIG), "[*]\na = b\n").unwrap();
+        let formatter = TestFormatter::new(|_: ValidationResult| {});
+        let err = TestErrorer::default();
 
         // Act
-        validate(config, "", &formatter);
+        let report = validate_all_with(
+            dir.path().to_str().unwrap(),
+            &WalkOptions::default(),
+            &ValidationConfig::default(),
+            &formatter,
+            &err,
+        );
+
+        // Assert
+        assert_eq!(report.files, 1);
+        assert!(report.missing_root.is_empty());
     }
 
     #[rstest]
-    #[case(
-        "S=\u{1b}\u{1b}\u{1e}_=\u{1b}\n\u{1b},\u{1b}s=\u{1b}\u{0}\u{0}\u{1b}\u{1b}1L",
-        "\n*\u{1b}\u{1b}",
-        false
-    )]
+    #[case(vec!["/a/.editorconfig", "/a/b/.editorconfig"], vec!["/a/.editorconfig"])]
+    #[case(vec!["/a/b/.editorconfig", "/a/c/.editorconfig"], vec!["/a/b/.editorconfig", "/a/c/.editorconfig"])]
+    #[case(vec!["/a/b/c/.editorconfig", "/a/.editorconfig"], vec!["/a/.editorconfig"])]
+    #[case(vec![], vec![])]
     #[trace]
-    fn validate_arbitrary(#[case] content: &str, #[case] path: &str, #[case] expected: bool) {
+    fn find_top_most_tests(#[case] files: Vec<&str>, #[case] expected: Vec<&str>) {
         // Arrange
-        let formatter = TestFormatter::new(|result: ValidationResult| {
-            assert_eq!(result.is_ok(), expected);
-            assert_eq!(result.state(), ValidationState::SomeProblems);
-        });
+        let files: Vec<PathBuf> = files.into_iter().map(PathBuf::from).collect();
 
         // Act
-        validate(content, path, &formatter);
+        let actual = find_top_most(&files);
+
+        // Assert
+        assert_eq!(
+            actual,
+            expected.into_iter().map(Path::new).collect::<Vec<&Path>>()
+        );
     }
 
     #[rstest]
-    #[case(ValidationState::Valid, true)]
-    #[case(ValidationState::Invalid, false)]
-    #[case(ValidationState::SomeProblems, false)]
+    #[case(false, Some("b  c"))]
+    #[case(true, Some("b c"))]
     #[trace]
-    fn is_ok_tests(#[case] state: ValidationState, #[case] expected: bool) {
+    fn compare_values_differ_in_internal_spacing(
+        #[case] normalize: bool,
+        #[case] expected: Option<&str>,
+    ) {
         // Arrange
+        let config1 = "[*]\na = b c\n";
+        let config2 = "[*]\na = b  c\n";
+        let config = CompareConfig::new().normalize_whitespace(normalize);
+        let formatter = TestCompareFormatter::new(|res: BTreeMap<&str, Vec<CompareItem>>| {
+            let star = res.get("*").unwrap();
+            assert_eq!(star[0].first_value, Some("b c"));
+            assert_eq!(star[0].second_value, expected);
+        });
 
         // Act
-        assert_eq!(state.is_ok(), expected);
+        compare_with(config1, config2, &config, &formatter);
     }
 
-    #[test]
-    fn validate_success_brackets_in_section_name() {
+    #[rstest]
+    #[case(vec!["ij_*"], vec![("*", "a"), ("*", "c")])]
+    #[case(vec!["IJ_*", "c"], vec![("*", "a")])]
+    #[case(vec!["ij_any"], vec![("*", "a"), ("*", "ij_anyway"), ("*", "c"), ("*.kt", "ij_kotlin_x")])]
+    #[case(vec![], vec![("*", "a"), ("*", "ij_any"), ("*", "ij_anyway"), ("*", "c"), ("*.kt", "ij_kotlin_x")])]
+    #[trace]
+    fn compare_ignore_keys(#[case] keys: Vec<&str>, #[case] expected: Vec<(&str, &str)>) {
         // Arrange
-        let config = r#"
-[[*]]
-a = b
-c = d
-"#;
-        let formatter = TestFormatter::new(|result: ValidationResult| {
-            assert!(result.is_ok());
-            assert_eq!(result.state(), ValidationState::Valid);
+        let config1 = "[*]\na = b\nij_any = 1\nij_anyway = 2\n[*.kt]\nij_kotlin_x = 3\n";
+        let config2 = "[*]\na = c\nc = d\nij_any = 2\n";
+        let config = CompareConfig::new().ignore_keys(keys.into_iter().map(String::from).collect());
+        let formatter = TestCompareFormatter::new(|res: BTreeMap<&str, Vec<CompareItem>>| {
+            // Assert
+            let actual: Vec<(&str, &str)> = res
+                .iter()
+                .flat_map(|(title, items)| items.iter().map(move |item| (*title, item.key)))
+                .collect();
+            assert_eq!(actual, expected);
         });
 
         // Act
-        validate(config, "", &formatter);
+        compare_with(config1, config2, &config, &formatter);
     }
 
     #[test]
-    fn validate_success_inline_comments() {
+    fn configs_equal_ignore_keys() {
         // Arrange
-        let config = r#"
-[*]
-a = b # comment 1
-c = d # comment 2
-"#;
-        let formatter = TestFormatter::new(|result: ValidationResult| {
-            assert!(result.is_ok());
-            assert_eq!(result.state(), ValidationState::Valid);
-        });
+        let config = CompareConfig::new().ignore_keys(vec!["dotnet_*".to_string()]);
 
         // Act
-        validate(config, "", &formatter);
+        let equal = configs_equal_with(
+            "[*]\na = b\ndotnet_style = x\n[*.cs]\ndotnet_sort = true\n",
+            "[*]\na = b\ndotnet_style = y\n",
+            &config,
+        );
+
+        // Assert
+        assert!(equal);
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn validate_fail_duplicate_keys_in_not_root() {
+    fn validation_result_serializes_to_json() {
         // Arrange
-        let config = r#"
-root = true
-[*]
-a = b
-a = e
-c = d
-
-[*.md]
-e = f"#;
+        let config = ValidationConfig::new().track_spans(true);
+        let json = std::cell::RefCell::new(serde_json::Value::Null);
         let formatter = TestFormatter::new(|result: ValidationResult| {
-            assert!(!result.duplicate_properties.is_empty());
-            assert!(result.duplicate_sections.is_empty());
-            assert!(result.similar_properties.is_empty());
-            assert_eq!(result.state(), ValidationState::Invalid);
+            *json.borrow_mut() = serde_json::to_value(&result).unwrap();
         });
 
         // Act
-        validate(config, "", &formatter);
+        validate_with("[*]\na = b\na = c\n", "test", &config, &formatter);
+
+        // Assert
+        let json = json.into_inner();
+        assert_eq!(json["path"], "test");
+        assert_eq!(json["is_root"], false);
+        assert_eq!(json["duplicate_properties"]["*"][0], "a");
+        assert_eq!(json["findings"][0]["kind"], "DuplicateProperty");
+        assert_eq!(json["findings"][0]["span"]["line"], 3);
+        assert!(json["ext_problems"].as_array().is_some_and(Vec::is_empty));
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn validate_fail_similar_keys_in_not_root() {
+    fn compare_item_serializes_to_json() {
         // Arrange
-        let config = r#"
-root = true
-[*]
-ab = b
-dab = e
-c = d
+        let item = CompareItem::only_second("a", "b");
 
-[*.md]
-e = f"#;
-        let formatter = TestFormatter::new(|result: ValidationResult| {
-            assert!(result.duplicate_properties.is_empty());
-            assert!(result.duplicate_sections.is_empty());
-            assert!(!result.similar_properties.is_empty());
-            assert!(result.ext_problems.is_empty());
-            assert_eq!(result.state(), ValidationState::SomeProblems);
+        // Act
+        let json = serde_json::to_value(&item).unwrap();
+
+        // Assert
+        assert_eq!(json["key"], "a");
+        assert_eq!(json["first_value"], serde_json::Value::Null);
+        assert_eq!(json["second_value"], "b");
+        assert_eq!(json["presence"]["in_first"], false);
+        assert_eq!(json["presence"]["in_second"], true);
+    }
+
+    #[rstest]
+    #[case("[*]\na = \n", "[*]\n", true, false)]
+    #[case("[*]\na = \n", "[*]\na = \n", true, true)]
+    #[case("[*]\n", "[*]\na = \n", false, true)]
+    #[case("[*]\na = b\n", "[*]\na = \n", true, true)]
+    #[case("", "[*]\na = \n", false, true)]
+    #[trace]
+    fn compare_presence_missing_vs_empty(
+        #[case] config1: &str,
+        #[case] config2: &str,
+        #[case] in_first: bool,
+        #[case] in_second: bool,
+    ) {
+        // Arrange
+        let formatter = TestCompareFormatter::new(|res: BTreeMap<&str, Vec<CompareItem>>| {
+            // Assert
+            let star = res.get("*").unwrap();
+            assert_eq!(
+                star[0].presence,
+                Presence {
+                    in_first,
+                    in_second
+                }
+            );
+            assert_eq!(star[0].first_value.is_some(), in_first);
+            assert_eq!(star[0].second_value.is_some(), in_second);
         });
 
         // Act
-        validate(config, "", &formatter);
+        compare(config1, config2, &formatter);
     }
 
-    #[test]
-    fn validate_fail_duplicate_keys_in_root() {
-        // Arrange
-        let config = r#"
-root = true
-root = false
+    #[rstest]
+    #[case("b", "b")]
+    #[case("b c", "b c")]
+    #[case("b  c", "b c")]
+    #[case("b \t c", "b c")]
+    #[trace]
+    fn normalize_whitespace_tests(#[case] value: &str, #[case] expected: &str) {
+        // Act
+        let actual = normalize_whitespace(value);
 
-[*]
-a = b
-c = d
+        // Assert
+        assert_eq!(actual, expected);
+    }
 
-[*.md]
-e = f"#;
-        let formatter = TestFormatter::new(|result: ValidationResult| {
-            assert!(!result.duplicate_properties.is_empty());
-            assert!(result.duplicate_sections.is_empty());
-            assert!(result.similar_properties.is_empty());
-            assert!(result.ext_problems.is_empty());
-            assert_eq!(result.state(), ValidationState::Invalid);
-        });
+    struct PanicNCompareFormatter;
 
-        // Act
-        validate(config, "", &formatter);
+    impl NCompareFormatter for PanicNCompareFormatter {
+        fn format(&self, _result: BTreeMap<&str, Vec<NCompareItem>>) {
+            panic!("nothing must be compared");
+        }
     }
 
     #[test]
-    fn validate_fail_duplicate_keys_ext_across_different_sections() {
+    fn compare_many_unreadable_file() {
         // Arrange
-        let config = r#"
-[*.{md,txt}]
-a = b
-c = d
-
-[*.md]
-a = d
-"#;
-        let formatter = TestFormatter::new(|result: ValidationResult| {
-            assert!(result.duplicate_properties.is_empty());
-            assert!(result.duplicate_sections.is_empty());
-            assert!(result.similar_properties.is_empty());
-            assert_eq!(result.ext_problems.len(), 1);
-            assert_eq!(result.state(), ValidationState::Invalid);
-        });
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join(EDITOR_CONFIG);
+        std::fs::write(&existing, "[*]\na = b\n").unwrap();
+        let missing = dir.path().join("missing");
+        let err = TestErrorer::default();
 
         // Act
-        validate(config, "", &formatter);
+        compare_many(
+            &[existing.to_str().unwrap(), missing.to_str().unwrap()],
+            &err,
+            &PanicNCompareFormatter,
+        );
+
+        // Assert
+        assert_eq!(err.errors.borrow().len(), 1);
     }
 
     #[test]
-    fn validate_fail_similar_keys_ext_across_different_sections() {
+    fn compare_effective_stops_at_root() {
         // Arrange
-        let config = r#"
-[*.{md,txt}]
-a_b_c = b
-x = d
+        let dir = tempfile::tempdir().unwrap();
+        let top = dir.path();
+        let root = top.join("root");
+        let sub = root.join("sub");
+        let inner = sub.join("inner");
+        std::fs::create_dir_all(&inner).unwrap();
+        std::fs::write(top.join(EDITOR_CONFIG), "[*]\nz = 1\n").unwrap();
+        std::fs::write(root.join(EDITOR_CONFIG), "root = true\n[*]\na = b\nc = d\n").unwrap();
+        std::fs::write(sub.join(EDITOR_CONFIG), "[*]\na = x\n").unwrap();
+        let target = inner.join(EDITOR_CONFIG);
+        std::fs::write(&target, "[*]\na = x\nc = d\n").unwrap();
 
-[*.md]
-d_a_b_c = d
-"#;
-        let formatter = TestFormatter::new(|result: ValidationResult| {
-            assert!(result.duplicate_properties.is_empty());
-            assert!(result.duplicate_sections.is_empty());
-            assert!(result.similar_properties.is_empty());
-            assert_eq!(result.ext_problems.len(), 1);
-            assert_eq!(result.state(), ValidationState::SomeProblems);
+        let formatter = TestCompareFormatter::new(|res: BTreeMap<&str, Vec<CompareItem>>| {
+            assert_eq!(2, res.len());
+            assert_eq!(res.get("").unwrap().len(), 1);
+            let star = res.get("*").unwrap();
+            assert_eq!(star.len(), 2);
+            assert!(star.iter().all(|i| i.first_value == i.second_value));
         });
+        let err = TestErrorer::default();
 
         // Act
-        validate(config, "", &formatter);
+        compare_effective(target.to_str().unwrap(), &err, &formatter);
+
+        // Assert
+        assert!(err.errors.borrow().is_empty());
     }
 
     #[test]
-    fn validate_fail_duplicate_sections() {
+    fn compare_effective_root_target_ignores_parents() {
         // Arrange
-        let config = r#"
-root = true
-
-[*]
-a = b
-c = d
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(
+            dir.path().join(EDITOR_CONFIG),
+            "root = true\n[*]\na = b\nz = 1\n",
+        )
+        .unwrap();
+        let target = sub.join(EDITOR_CONFIG);
+        std::fs::write(&target, "root = true\n[*]\na = x\n").unwrap();
 
-[*]
-e = f"#;
-        let formatter = TestFormatter::new(|result: ValidationResult| {
-            assert!(result.duplicate_properties.is_empty());
-            assert!(!result.duplicate_sections.is_empty());
-            assert!(result.similar_properties.is_empty());
-            assert_eq!(result.state(), ValidationState::Invalid);
+        let formatter = TestCompareFormatter::new(|res: BTreeMap<&str, Vec<CompareItem>>| {
+            let items: Vec<&CompareItem> = res.values().flatten().collect();
+            assert_eq!(items.len(), 2);
+            assert!(items.iter().all(|i| i.first_value.is_none()));
+            assert!(res.get("*").unwrap().iter().all(|i| i.key != "z"));
         });
+        let err = TestErrorer::default();
 
         // Act
-        validate(config, "", &formatter);
+        compare_effective(target.to_str().unwrap(), &err, &formatter);
+
+        // Assert
+        assert!(err.errors.borrow().is_empty());
     }
 
     #[test]
@@ -911,6 +6521,46 @@ c = d2
         compare(config1, config2, &formatter);
     }
 
+    #[test]
+    fn diff_outlives_contents() {
+        // Arrange
+        let config1 = String::from("[*]\na = b\nc = d\n");
+        let config2 = String::from("[*]\na = b1\n[*.md]\ne = f\n");
+
+        // Act
+        let result = diff(&config1, &config2);
+        drop(config1);
+        drop(config2);
+
+        // Assert
+        let expected = BTreeMap::from([
+            (
+                "*".to_string(),
+                vec![
+                    OwnedCompareItem {
+                        key: "a".to_string(),
+                        first_value: Some("b".to_string()),
+                        second_value: Some("b1".to_string()),
+                    },
+                    OwnedCompareItem {
+                        key: "c".to_string(),
+                        first_value: Some("d".to_string()),
+                        second_value: None,
+                    },
+                ],
+            ),
+            (
+                "*.md".to_string(),
+                vec![OwnedCompareItem {
+                    key: "e".to_string(),
+                    first_value: None,
+                    second_value: Some("f".to_string()),
+                }],
+            ),
+        ]);
+        assert_eq!(result, expected);
+    }
+
     #[test]
     fn compare_plain_with_general() {
         // Arrange
@@ -1016,6 +6666,46 @@ d = 8
         compare(config1, config2, &formatter);
     }
 
+    #[rstest]
+    #[case("root = true\n[*]\na = b\nc = d\n", "[*]\na = b1\nc = d2\n", DiffStats { sections_only_first: 1, props_differing: 2, props_only_first: 1, ..Default::default() })]
+    #[case("[*]\na = b\nc = d\n", "[*]\na = b1\nd = d2\n", DiffStats { props_differing: 1, props_only_first: 1, props_only_second: 1, ..Default::default() })]
+    #[case("[x]\na = b\nc = d\n", "[y]\na = b1\nd = d2\n", DiffStats { sections_only_first: 1, sections_only_second: 1, props_only_first: 2, props_only_second: 2, ..Default::default() })]
+    #[case("[*]\na = 1\nc = 2\n[*]\nb = 3\nd = 4\n", "[*]\na = 5\nc = 6\n[*]\nb = 7\nd = 8\n", DiffStats { props_differing: 4, ..Default::default() })]
+    #[case("[*]\na = b\nc = d\n", "[*]\nc = d\na = b\n", DiffStats::default())]
+    #[case("[*]\na = b\n[x]\n", "[*]\na = b\n", DiffStats { sections_only_first: 1, ..Default::default() })]
+    #[trace]
+    fn diff_stats_tests(
+        #[case] content1: &str,
+        #[case] content2: &str,
+        #[case] expected: DiffStats,
+    ) {
+        // Arrange
+
+        // Act
+        let actual = diff_stats(content1, content2);
+
+        // Assert
+        assert_eq!(actual, expected);
+        assert_eq!(actual.is_empty(), expected == DiffStats::default());
+    }
+
+    #[rstest]
+    #[case("/home/.editorconfig", true)]
+    #[case("/home/.editorconfig.template", false)]
+    #[case("/home/x.editorconfig", false)]
+    #[case(".editorconfig", true)]
+    #[trace]
+    fn walk_options_default_names_tests(#[case] path: &str, #[case] expected: bool) {
+        // Arrange
+        let options = WalkOptions::default();
+
+        // Act
+        let actual = options.matches(Path::new(path));
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
     #[cfg(not(target_os = "windows"))]
     #[rstest]
     #[case("", "")]
@@ -0,0 +1,298 @@
+use crate::similar;
+
+/// Properties defined by the editorconfig specification
+pub const WELL_KNOWN: [&str; 10] = [
+    "root",
+    "indent_style",
+    "indent_size",
+    "tab_width",
+    "end_of_line",
+    "charset",
+    "spelling_language",
+    "trim_trailing_whitespace",
+    "insert_final_newline",
+    "max_line_length",
+];
+
+/// Properties every `[*]` section is expected to set so that editors don't fall back to their own defaults
+pub const RECOMMENDED: [&str; 5] = [
+    "charset",
+    "end_of_line",
+    "insert_final_newline",
+    "indent_style",
+    "indent_size",
+];
+
+/// Boolean properties whose flip between sections changes how the same files are saved
+pub const BOOLEAN_TOGGLES: [&str; 2] = ["insert_final_newline", "trim_trailing_whitespace"];
+
+/// Maximum edit distance between unknown and well known property to suggest it
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Checks whether property is defined by the specification. Property names are case insensitive.
+pub fn is_well_known(name: &str) -> bool {
+    WELL_KNOWN.iter().any(|k| k.eq_ignore_ascii_case(name))
+}
+
+/// Finds the closest well known property that may be meant instead of the unknown one
+pub fn suggest(name: &str) -> Option<&'static str> {
+    let name = name.to_ascii_lowercase();
+    WELL_KNOWN
+        .iter()
+        .map(|k| (*k, similar::levenshtein(&name, k)))
+        .filter(|(_, distance)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(k, _)| k)
+}
+
+/// Finds replacement of the deprecated property among `deprecated` pairs.
+/// There is no built-in registry because the specification deprecates no property.
+/// Property names are case insensitive.
+pub fn replacement(
+    name: &str,
+    deprecated: &[(&'static str, &'static str)],
+) -> Option<&'static str> {
+    deprecated
+        .iter()
+        .find(|(deprecated, _)| deprecated.eq_ignore_ascii_case(name))
+        .map(|(_, replacement)| *replacement)
+}
+
+/// Property value interpreted according to the type the specification defines for the property
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TypedValue<'a> {
+    /// `true` or `false` of properties like `root` or `insert_final_newline`
+    Bool(bool),
+    /// Number of properties like `indent_size` or `tab_width`
+    Int(i64),
+    /// One of the values the specification lists for the property, like `utf-8` for `charset`
+    /// or `tab` for `indent_size`
+    Keyword(&'a str),
+    /// `unset` that any property may have to remove the value set by preceding sections
+    Unset,
+    /// Value of the property not defined by the specification or the value that isn't allowed
+    Raw(&'a str),
+}
+
+/// Interprets property value according to the type the specification defines for the property.
+/// Property names and values are case insensitive.
+pub fn typed_value<'a>(name: &str, value: &'a str) -> TypedValue<'a> {
+    interpret(name, value).unwrap_or(TypedValue::Raw(value))
+}
+
+/// Checks whether the value is `unset` that removes the property. Values are case insensitive.
+pub fn is_unset(value: &str) -> bool {
+    value.eq_ignore_ascii_case("unset")
+}
+
+/// Checks whether value is allowed for the property by the specification.
+/// Values of properties not defined by the specification are always considered valid.
+/// Values are case insensitive and `unset` is allowed for every property.
+pub fn is_valid_value(name: &str, value: &str) -> bool {
+    interpret(name, value).is_some()
+}
+
+/// Interprets value or returns `None` if the value isn't allowed for the property
+fn interpret<'a>(name: &str, value: &'a str) -> Option<TypedValue<'a>> {
+    if is_unset(value) {
+        return Some(TypedValue::Unset);
+    }
+    let lower = value.to_ascii_lowercase();
+    let keyword = |allowed: &[&str]| {
+        allowed
+            .contains(&lower.as_str())
+            .then_some(TypedValue::Keyword(value))
+    };
+    // numbers too big for i64 are still allowed but kept raw
+    let number = || {
+        (!value.is_empty() && value.chars().all(|c| c.is_ascii_digit())).then(|| {
+            value
+                .parse()
+                .map_or(TypedValue::Raw(value), TypedValue::Int)
+        })
+    };
+    match name.to_ascii_lowercase().as_str() {
+        "root" | "trim_trailing_whitespace" | "insert_final_newline" => match lower.as_str() {
+            "true" => Some(TypedValue::Bool(true)),
+            "false" => Some(TypedValue::Bool(false)),
+            _ => None,
+        },
+        "indent_style" => keyword(&["tab", "space"]),
+        "indent_size" => number().or_else(|| keyword(&["tab"])),
+        "tab_width" => number(),
+        "max_line_length" => number().or_else(|| keyword(&["off"])),
+        "end_of_line" => keyword(&["lf", "cr", "crlf"]),
+        "charset" => keyword(&["latin1", "utf-8", "utf-8-bom", "utf-16be", "utf-16le"]),
+        _ => Some(TypedValue::Raw(value)),
+    }
+}
+
+/// Converts property key into lowercase snake case like `indentSize` or `indent-size`
+/// into `indent_size`. Returns `None` if the key is canonical already.
+pub fn canonical_key(name: &str) -> Option<String> {
+    if name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return None;
+    }
+    let mut result = String::with_capacity(name.len() + 2);
+    let mut prev: Option<char> = None;
+    for c in name.chars() {
+        if c.is_uppercase() {
+            // word boundary of camel case key
+            if prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()) {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else if c == '-' || c == '.' || c.is_whitespace() {
+            result.push('_');
+        } else {
+            result.push(c);
+        }
+        prev = Some(c);
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("indent_size", true)]
+    #[case("INDENT_SIZE", true)]
+    #[case("indnet_size", false)]
+    #[case("ij_any", false)]
+    #[trace]
+    fn is_well_known_tests(#[case] name: &str, #[case] expected: bool) {
+        // Arrange
+
+        // Act
+        let actual = is_well_known(name);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("indnet_size", Some("indent_size"))]
+    #[case("Charst", Some("charset"))]
+    #[case("tab_widht", Some("tab_width"))]
+    #[case("ij_continuation_indent_size", None)]
+    #[case("x", None)]
+    #[trace]
+    fn suggest_tests(#[case] name: &str, #[case] expected: Option<&str>) {
+        // Arrange
+
+        // Act
+        let actual = suggest(name);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("tab_size", &[], None)]
+    #[case("tab_width", &[], None)]
+    #[case("old_key", &[("old_key", "new_key")], Some("new_key"))]
+    #[case("OLD_KEY", &[("old_key", "new_key")], Some("new_key"))]
+    #[case("tab_size", &[("old_key", "new_key")], None)]
+    #[trace]
+    fn replacement_tests(
+        #[case] name: &str,
+        #[case] deprecated: &[(&'static str, &'static str)],
+        #[case] expected: Option<&str>,
+    ) {
+        // Arrange
+
+        // Act
+        let actual = replacement(name, deprecated);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("indent_style", "space", true)]
+    #[case("indent_style", "Tab", true)]
+    #[case("indent_style", "spaces", false)]
+    #[case("indent_size", "tab", true)]
+    #[case("indent_size", "4", true)]
+    #[case("indent_size", "four", false)]
+    #[case("tab_width", "tab", false)]
+    #[case("max_line_length", "off", true)]
+    #[case("end_of_line", "crlf", true)]
+    #[case("end_of_line", "windows", false)]
+    #[case("charset", "utf-8-bom", true)]
+    #[case("charset", "utf8", false)]
+    #[case("insert_final_newline", "yes", false)]
+    #[case("trim_trailing_whitespace", "unset", true)]
+    #[case("ij_any", "anything", true)]
+    #[trace]
+    fn is_valid_value_tests(#[case] name: &str, #[case] value: &str, #[case] expected: bool) {
+        // Arrange
+
+        // Act
+        let actual = is_valid_value(name, value);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("indent_size", "4", TypedValue::Int(4))]
+    #[case("tab_width", "08", TypedValue::Int(8))]
+    #[case("max_line_length", "120", TypedValue::Int(120))]
+    #[case("max_line_length", "Off", TypedValue::Keyword("Off"))]
+    #[case("indent_size", "tab", TypedValue::Keyword("tab"))]
+    #[case(
+        "indent_size",
+        "99999999999999999999",
+        TypedValue::Raw("99999999999999999999")
+    )]
+    #[case("indent_size", "four", TypedValue::Raw("four"))]
+    #[case("tab_width", "-2", TypedValue::Raw("-2"))]
+    #[case("root", "true", TypedValue::Bool(true))]
+    #[case("Insert_Final_Newline", "FALSE", TypedValue::Bool(false))]
+    #[case("trim_trailing_whitespace", "yes", TypedValue::Raw("yes"))]
+    #[case("charset", "utf-8", TypedValue::Keyword("utf-8"))]
+    #[case("end_of_line", "CRLF", TypedValue::Keyword("CRLF"))]
+    #[case("indent_style", "spaces", TypedValue::Raw("spaces"))]
+    #[case("tab_width", "unset", TypedValue::Unset)]
+    #[case("indent_style", "UNSET", TypedValue::Unset)]
+    #[case("ij_any", "unset", TypedValue::Unset)]
+    #[case("ij_any", "4", TypedValue::Raw("4"))]
+    #[trace]
+    fn typed_value_tests(#[case] name: &str, #[case] value: &str, #[case] expected: TypedValue) {
+        // Arrange
+
+        // Act
+        let actual = typed_value(name, value);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("indent_size", None)]
+    #[case("ij_kotlin_name_count_2", None)]
+    #[case("indentSize", Some("indent_size"))]
+    #[case("IndentSize", Some("indent_size"))]
+    #[case("indent-size", Some("indent_size"))]
+    #[case("INDENT_SIZE", Some("indent_size"))]
+    #[case("Max_Line_Length", Some("max_line_length"))]
+    #[case("utf8Charset", Some("utf8_charset"))]
+    #[trace]
+    fn canonical_key_tests(#[case] name: &str, #[case] expected: Option<&str>) {
+        // Arrange
+
+        // Act
+        let actual = canonical_key(name);
+
+        // Assert
+        assert_eq!(actual.as_deref(), expected);
+    }
+}
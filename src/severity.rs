@@ -0,0 +1,186 @@
+//! Severity of finding categories that defines [`crate::ValidationState`] of the validated file
+
+/// How bad findings of some category are. Errors make the file invalid and warnings
+/// only mean that the file has some problems unless strict validation is enabled.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// Category of findings. Each category corresponds to a field of [`crate::ValidationResult`]
+/// except extension problems that are split into conflicting values and other problems.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Category {
+    DuplicateSections,
+    DuplicateProperties,
+    ExtensionConflicts,
+    ExtensionProblems,
+    SimilarProperties,
+    InvalidValues,
+    OverlappingSections,
+    EquivalentSections,
+    IncompleteSections,
+    OversizedProperties,
+    TabInValue,
+    InconsistentAssignmentStyle,
+    InvalidKeyChars,
+    LexerErrors,
+    UnbalancedBrackets,
+    EmptySections,
+    WhitespaceTitles,
+    EncodingWarnings,
+    UnknownProperties,
+    UselessProperties,
+    TabWidthConflicts,
+    DuplicateGlobMembers,
+    DeadSections,
+    MisplacedRoot,
+    NonCanonicalKeys,
+    DeprecatedProperties,
+}
+
+/// Default severity of every finding category as [`Category::severity`] defines it
+pub const SEVERITIES: [(Category, Severity); Category::ALL.len()] = {
+    let mut result = [(Category::DuplicateSections, Severity::Error); Category::ALL.len()];
+    let mut i = 0;
+    while i < result.len() {
+        result[i] = (Category::ALL[i], Category::ALL[i].severity());
+        i += 1;
+    }
+    result
+};
+
+impl Category {
+    /// Every category in the order they're declared
+    pub const ALL: [Category; 26] = [
+        Category::DuplicateSections,
+        Category::DuplicateProperties,
+        Category::ExtensionConflicts,
+        Category::ExtensionProblems,
+        Category::SimilarProperties,
+        Category::InvalidValues,
+        Category::OverlappingSections,
+        Category::EquivalentSections,
+        Category::IncompleteSections,
+        Category::OversizedProperties,
+        Category::TabInValue,
+        Category::InconsistentAssignmentStyle,
+        Category::InvalidKeyChars,
+        Category::LexerErrors,
+        Category::UnbalancedBrackets,
+        Category::EmptySections,
+        Category::WhitespaceTitles,
+        Category::EncodingWarnings,
+        Category::UnknownProperties,
+        Category::UselessProperties,
+        Category::TabWidthConflicts,
+        Category::DuplicateGlobMembers,
+        Category::DeadSections,
+        Category::MisplacedRoot,
+        Category::NonCanonicalKeys,
+        Category::DeprecatedProperties,
+    ];
+
+    /// Gets default severity of the category. Duplicates and conflicting values are errors
+    /// while other findings are warnings
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use editorconfiger::severity::{Category, Severity};
+    ///
+    /// assert_eq!(Severity::Error, Category::DuplicateProperties.severity());
+    /// assert_eq!(Severity::Warning, Category::SimilarProperties.severity());
+    /// ```
+    #[must_use]
+    pub const fn severity(self) -> Severity {
+        match self {
+            Category::DuplicateSections
+            | Category::DuplicateProperties
+            | Category::ExtensionConflicts => Severity::Error,
+            Category::ExtensionProblems
+            | Category::SimilarProperties
+            | Category::InvalidValues
+            | Category::OverlappingSections
+            | Category::EquivalentSections
+            | Category::IncompleteSections
+            | Category::OversizedProperties
+            | Category::TabInValue
+            | Category::InconsistentAssignmentStyle
+            | Category::InvalidKeyChars
+            | Category::LexerErrors
+            | Category::UnbalancedBrackets
+            | Category::EmptySections
+            | Category::WhitespaceTitles
+            | Category::EncodingWarnings
+            | Category::UnknownProperties
+            | Category::UselessProperties
+            | Category::TabWidthConflicts
+            | Category::DuplicateGlobMembers
+            | Category::DeadSections
+            | Category::MisplacedRoot
+            | Category::NonCanonicalKeys
+            | Category::DeprecatedProperties => Severity::Warning,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Category::DuplicateSections, Severity::Error)]
+    #[case(Category::DuplicateProperties, Severity::Error)]
+    #[case(Category::ExtensionConflicts, Severity::Error)]
+    #[case(Category::ExtensionProblems, Severity::Warning)]
+    #[case(Category::SimilarProperties, Severity::Warning)]
+    #[case(Category::DeprecatedProperties, Severity::Warning)]
+    #[trace]
+    fn severity_tests(#[case] category: Category, #[case] expected: Severity) {
+        // Arrange
+
+        // Act
+        let actual = category.severity();
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn all_categories_have_no_duplicates() {
+        // Arrange
+
+        // Act
+        let duplicates: Vec<Category> = Category::ALL
+            .iter()
+            .enumerate()
+            .filter(|(ix, category)| Category::ALL[..*ix].contains(category))
+            .map(|(_, category)| *category)
+            .collect();
+
+        // Assert
+        assert!(duplicates.is_empty());
+    }
+
+    #[test]
+    fn severities_table_lists_every_category() {
+        // Arrange
+
+        // Act
+        let categories: Vec<Category> = SEVERITIES.iter().map(|(c, _)| *c).collect();
+
+        // Assert
+        assert_eq!(categories, Category::ALL);
+        assert!(SEVERITIES.iter().all(|(c, s)| c.severity() == *s));
+    }
+
+    #[test]
+    fn error_is_worse_than_warning() {
+        assert!(Severity::Error > Severity::Warning);
+    }
+}
@@ -1,6 +1,16 @@
+use std::collections::BTreeMap;
+
 use aho_corasick::{AhoCorasickBuilder, MatchKind};
 
+use crate::editorconfig::{self, Section};
+use crate::enumerable::IteratorExt;
+
+/// Minimum length in characters of the suffix that makes two properties similar by default,
+/// so that single character keys like `a` and `ba` aren't reported
+pub const DEFAULT_MIN_SUFFIX_LEN: usize = 2;
+
 /// This function finds all pairs where the second item is the suffix of the first one
+/// and is at least `min_len` characters long
 ///
 /// # Example
 ///
@@ -8,11 +18,14 @@ use aho_corasick::{AhoCorasickBuilder, MatchKind};
 /// use editorconfiger::similar;
 ///
 /// let items = vec!["ab", "aba", "b"];
-/// let result = similar::find_suffix_pairs(&items);
+/// let result = similar::find_suffix_pairs(&items, 1);
 /// assert_eq!(vec![("ab", "b")], result);
+///
+/// let result = similar::find_suffix_pairs(&items, 2);
+/// assert!(result.is_empty());
 /// ```
 #[must_use]
-pub fn find_suffix_pairs<'a>(items: &[&'a str]) -> Vec<(&'a str, &'a str)> {
+pub fn find_suffix_pairs<'a>(items: &[&'a str], min_len: usize) -> Vec<(&'a str, &'a str)> {
     let machine = AhoCorasickBuilder::new()
         .match_kind(MatchKind::Standard)
         .ascii_case_insensitive(true)
@@ -28,12 +41,76 @@ pub fn find_suffix_pairs<'a>(items: &[&'a str]) -> Vec<(&'a str, &'a str)> {
             .filter(|(_item, mat)| !mat.is_empty())
             .map(|(item, mat)| (item, &item[mat.start()..mat.end()]))
             .filter(|(item, found)| *item != *found && (*item).ends_with(*found))
+            .filter(|(_, found)| found.chars().count() >= min_len)
             .collect()
     } else {
         Vec::new()
     }
 }
 
+/// Finds pairs of section properties where the second one is the suffix of the first one,
+/// i.e. properties that are probably mistyped. Each property name is considered once.
+/// Suffixes shorter than `min_len` characters are ignored.
+#[must_use]
+pub fn similar_in_section<'a>(section: &Section<'a>, min_len: usize) -> Vec<(&'a str, &'a str)> {
+    let names: Vec<&str> = section.properties.iter().map(|p| p.name).unique().collect();
+    find_suffix_pairs(&names, min_len)
+}
+
+/// Finds similar properties in every section of .editorconfig content the same way
+/// validation does. Only sections that have similar properties are included.
+///
+/// # Example
+///
+/// ```
+/// use editorconfiger::similar;
+///
+/// let result = similar::similar_in_content("[*]\nindent_size = 2\nsize = 2\n[*.md]\na = b\n");
+/// assert_eq!(1, result.len());
+/// assert_eq!(vec![("indent_size".to_string(), "size".to_string())], result["*"]);
+/// ```
+#[must_use]
+pub fn similar_in_content(content: &str) -> BTreeMap<String, Vec<(String, String)>> {
+    let mut result: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+    for section in editorconfig::parse(content) {
+        let pairs = similar_in_section(&section, DEFAULT_MIN_SUFFIX_LEN);
+        if !pairs.is_empty() {
+            result.entry(section.title.to_string()).or_default().extend(
+                pairs
+                    .into_iter()
+                    .map(|(first, second)| (first.to_string(), second.to_string())),
+            );
+        }
+    }
+    result
+}
+
+/// Calculates Levenshtein (edit) distance between two strings
+///
+/// # Example
+///
+/// ```
+/// use editorconfiger::similar;
+///
+/// assert_eq!(2, similar::levenshtein("indnet_size", "indent_size"));
+/// assert_eq!(0, similar::levenshtein("a", "a"));
+/// ```
+#[must_use]
+pub fn levenshtein(first: &str, second: &str) -> usize {
+    let second: Vec<char> = second.chars().collect();
+    let mut previous: Vec<usize> = (0..=second.len()).collect();
+    let mut current = vec![0; second.len() + 1];
+    for (i, c1) in first.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, c2) in second.iter().enumerate() {
+            let substitution = previous[j] + usize::from(c1 != *c2);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[second.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,7 +126,50 @@ mod tests {
         // Arrange
 
         // Act
-        let actual = find_suffix_pairs(&items);
+        let actual = find_suffix_pairs(&items, 1);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(vec!["a", "ba"], 1, vec![("ba", "a")])]
+    #[case(vec!["a", "ba"], 3, vec![])]
+    #[case(vec!["ab", "cab"], 1, vec![("cab", "ab")])]
+    #[case(vec!["ab", "cab"], 3, vec![])]
+    #[case(vec!["size", "indent_size"], 3, vec![("indent_size", "size")])]
+    #[case(vec!["aab", "aaab", "b"], 3, vec![("aaab", "aab")])]
+    #[case(vec!["яб", "аяб"], 2, vec![("аяб", "яб")])]
+    #[case(vec!["яб", "аяб"], 3, vec![])]
+    #[trace]
+    fn find_suffix_min_len_tests(
+        #[case] items: Vec<&str>,
+        #[case] min_len: usize,
+        #[case] expected: Vec<(&str, &str)>,
+    ) {
+        // Arrange
+
+        // Act
+        let actual = find_suffix_pairs(&items, min_len);
+
+        // Assert
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("", "", 0)]
+    #[case("abc", "", 3)]
+    #[case("", "abc", 3)]
+    #[case("kitten", "sitting", 3)]
+    #[case("indnet_size", "indent_size", 2)]
+    #[case("charst", "charset", 1)]
+    #[case("charset", "charset", 0)]
+    #[trace]
+    fn levenshtein_tests(#[case] first: &str, #[case] second: &str, #[case] expected: usize) {
+        // Arrange
+
+        // Act
+        let actual = levenshtein(first, second);
 
         // Assert
         assert_eq!(actual, expected);
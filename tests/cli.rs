@@ -0,0 +1,266 @@
+#![cfg(feature = "build-binary")]
+
+use std::process::{Command, Output};
+
+use rstest::rstest;
+
+const SIMILAR_ONLY: &str = "root = true\\n[*]\\nab = b\\ndab = e";
+const DUPLICATES: &str = "[*]\\nindent_size=2\\nindent_size=4";
+
+fn execute(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_editorconfiger"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn run(args: &[&str]) -> String {
+    let output = execute(args);
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn validate_inline_content_valid() {
+    // Act
+    let stdout = run(&["--color", "never", "vf", "--content", "[*]\\nindent_size=2"]);
+
+    // Assert
+    assert!(stdout.contains("<inline> valid"));
+}
+
+#[test]
+fn validate_inline_content_with_duplicates() {
+    // Act
+    let stdout = run(&[
+        "--color",
+        "never",
+        "vf",
+        "--fail-on",
+        "never",
+        "--content",
+        DUPLICATES,
+    ]);
+
+    // Assert
+    assert!(stdout.contains("<inline> invalid"));
+    assert!(stdout.contains("indent_size"));
+}
+
+#[rstest]
+#[case(SIMILAR_ONLY, "invalid", 0)]
+#[case(SIMILAR_ONLY, "problems", 1)]
+#[case(SIMILAR_ONLY, "never", 0)]
+#[case(DUPLICATES, "invalid", 1)]
+#[case(DUPLICATES, "problems", 1)]
+#[case(DUPLICATES, "never", 0)]
+#[trace]
+fn validate_inline_content_fail_on(
+    #[case] content: &str,
+    #[case] fail_on: &str,
+    #[case] expected: i32,
+) {
+    // Act
+    let output = execute(&["vf", "--fail-on", fail_on, "--content", content]);
+
+    // Assert
+    assert_eq!(output.status.code(), Some(expected));
+}
+
+#[test]
+fn validate_several_files() {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let clean = dir.path().join("clean.editorconfig");
+    let dirty = dir.path().join("dirty.editorconfig");
+    std::fs::write(&clean, "root = true\n[*]\nindent_size = 2\n").unwrap();
+    std::fs::write(&dirty, "[*]\nindent_size = 2\nindent_size = 4\n").unwrap();
+    let clean = clean.to_str().unwrap();
+    let dirty = dirty.to_str().unwrap();
+
+    // Act
+    let output = execute(&["--color", "never", "vf", clean, dirty]);
+
+    // Assert
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&format!("{clean} valid")));
+    assert!(stdout.contains(&format!("{dirty} invalid")));
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn validate_inline_content_invalid_fails_by_default() {
+    // Act
+    let output = execute(&["vf", "--content", DUPLICATES]);
+
+    // Assert
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[rstest]
+#[case("[*]\na = b\nc = d\n", "[*]\nc = d\na = b\n", 0)]
+#[case("[*]\na = b\n", "[*]\na = c\n", 1)]
+#[trace]
+fn compare_quiet(#[case] content1: &str, #[case] content2: &str, #[case] expected: i32) {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("first.editorconfig");
+    let second = dir.path().join("second.editorconfig");
+    std::fs::write(&first, content1).unwrap();
+    std::fs::write(&second, content2).unwrap();
+
+    // Act
+    let output = execute(&[
+        "c",
+        "--quiet",
+        first.to_str().unwrap(),
+        second.to_str().unwrap(),
+    ]);
+
+    // Assert
+    assert_eq!(output.status.code(), Some(expected));
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn compare_ignore_key() {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("first.editorconfig");
+    let second = dir.path().join("second.editorconfig");
+    std::fs::write(&first, "[*]\na = b\nij_any = 1\n[*.kt]\nij_kotlin = 2\n").unwrap();
+    std::fs::write(&second, "[*]\na = b\nij_any = 3\n").unwrap();
+
+    // Act
+    let output = execute(&[
+        "c",
+        "--quiet",
+        "--ignore-key",
+        "ij_*",
+        first.to_str().unwrap(),
+        second.to_str().unwrap(),
+    ]);
+
+    // Assert
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn compare_quiet_unreadable_file() {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing.editorconfig");
+
+    // Act
+    let output = execute(&[
+        "c",
+        "-q",
+        missing.to_str().unwrap(),
+        missing.to_str().unwrap(),
+    ]);
+
+    // Assert
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+}
+
+#[rstest]
+#[case(false)]
+#[case(true)]
+#[trace]
+fn format_file(#[case] write: bool) {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(".editorconfig");
+    std::fs::write(&path, "root=true\n[*]\nb=2\na = 1\n[*]\nc =3\n").unwrap();
+    let path_arg = path.to_str().unwrap();
+    let args = if write {
+        vec!["fmt", "--write", path_arg]
+    } else {
+        vec!["fmt", path_arg]
+    };
+
+    // Act
+    let stdout = run(&args);
+
+    // Assert
+    let expected = "root = true\n\n[*]\na = 1\nb = 2\nc = 3\n";
+    if write {
+        assert!(stdout.is_empty());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), expected);
+    } else {
+        assert_eq!(stdout, expected);
+    }
+}
+
+#[test]
+fn format_file_with_invalid_line() {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(".editorconfig");
+    std::fs::write(&path, "[*]\nindent_size\n").unwrap();
+
+    // Act
+    let output = execute(&["--color", "never", "fmt", "--write", path.to_str().unwrap()]);
+
+    // Assert
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "[*]\nindent_size\n"
+    );
+}
+
+#[test]
+fn compare_stats() {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("first.editorconfig");
+    let second = dir.path().join("second.editorconfig");
+    std::fs::write(&first, "[*]\na = b\nc = d\n[x]\ne = f\n").unwrap();
+    std::fs::write(&second, "[*]\na = e\n").unwrap();
+
+    // Act
+    let stdout = run(&[
+        "--color",
+        "never",
+        "c",
+        "--stats",
+        first.to_str().unwrap(),
+        second.to_str().unwrap(),
+    ]);
+
+    // Assert
+    assert!(stdout.contains("Sections only in first.editorconfig: 1"));
+    assert!(stdout.contains("Sections only in second.editorconfig: 0"));
+    assert!(stdout.contains("Properties with different values: 1"));
+    assert!(stdout.contains("Properties only in first.editorconfig: 2"));
+    assert!(stdout.contains("Properties only in second.editorconfig: 0"));
+}
+
+#[rstest]
+#[case(&[])]
+#[case(&["--with"])]
+#[trace]
+fn compare_markdown_prints_only_table(#[case] with: &[&str]) {
+    // Arrange
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("first.editorconfig");
+    let second = dir.path().join("second.editorconfig");
+    std::fs::write(&first, "[*]\na = b\n").unwrap();
+    std::fs::write(&second, "[*]\na = c\n").unwrap();
+    let mut args = vec!["--color", "never", "c", "-f", "markdown"];
+    args.push(first.to_str().unwrap());
+    args.extend(with);
+    args.push(second.to_str().unwrap());
+
+    // Act
+    let stdout = run(&args);
+
+    // Assert
+    assert!(!stdout.contains("FILE #"));
+    assert!(stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .all(|line| line.starts_with('|')));
+}
@@ -0,0 +1,166 @@
+//! Glob matching cases modeled after editorconfig-core-test glob suite.
+//! Every `.in` file from `tests/conformance` is applied to a file path the way
+//! editors do it and resulting property value is compared with expected one.
+
+use editorconfiger::{glob, Token};
+use rstest::rstest;
+use std::collections::BTreeMap;
+
+/// Collects properties of all sections that match `path` in order they're defined
+fn properties_of(content: &str, path: &str) -> BTreeMap<String, String> {
+    let mut result = BTreeMap::new();
+    let mut applies = false;
+    for spanned in editorconfiger::tokens(content) {
+        match spanned.token {
+            Token::Head(title) => applies = glob::matches(title, path),
+            Token::Pair(key, value) if applies => {
+                result.insert(key.to_lowercase(), value.to_string());
+            }
+            Token::Pair(..) | Token::Comment(_) => {}
+        }
+    }
+    result
+}
+
+#[rstest]
+#[case("star.in", "ace.c", "key", Some("value"))]
+#[case("star.in", "abcde.c", "key", Some("value"))]
+#[case("star.in", "a/e.c", "key", None)]
+#[case("star.in", "Bar/foo.txt", "keyb", Some("valueb"))]
+#[case("star.in", "Bar/sub/foo.txt", "keyb", None)]
+#[case("star.in", "any/file", "keyc", Some("valuec"))]
+#[case("question.in", "some.c", "key", Some("value"))]
+#[case("question.in", "som.c", "key", None)]
+#[case("question.in", "som/.c", "key", None)]
+#[case("brackets.in", "a.a", "choice", Some("true"))]
+#[case("brackets.in", "b.a", "choice", Some("true"))]
+#[case("brackets.in", "c.a", "choice", None)]
+#[case("brackets.in", "c.b", "choice", Some("false"))]
+#[case("brackets.in", "a.b", "choice", None)]
+#[case("brackets.in", "b.b", "choice", None)]
+#[case("brackets.in", "d.c", "range", Some("true"))]
+#[case("brackets.in", "f.c", "range", Some("true"))]
+#[case("brackets.in", "g.c", "range", Some("true"))]
+#[case("brackets.in", "c.c", "range", None)]
+#[case("brackets.in", "h.c", "range", None)]
+#[case("brackets.in", "c.d", "range", Some("false"))]
+#[case("brackets.in", "h.d", "range", Some("false"))]
+#[case("brackets.in", "d.d", "range", None)]
+#[case("brackets.in", "g.d", "range", None)]
+#[case("brackets.in", "a.e", "range_and_choice", Some("true"))]
+#[case("brackets.in", "b.e", "range_and_choice", Some("true"))]
+#[case("brackets.in", "e.e", "range_and_choice", Some("true"))]
+#[case("brackets.in", "c.e", "range_and_choice", None)]
+#[case("brackets.in", "h.e", "range_and_choice", None)]
+#[case("brackets.in", "-.f", "choice_with_dash", Some("true"))]
+#[case("brackets.in", "a.f", "choice_with_dash", Some("true"))]
+#[case("brackets.in", "c.f", "choice_with_dash", None)]
+#[case("brackets.in", "].g", "close_inside", Some("true"))]
+#[case("brackets.in", "a.g", "close_inside", Some("true"))]
+#[case("brackets.in", "c.g", "close_inside", Some("false"))]
+#[case("brackets.in", "b].g", "close_outside", Some("true"))]
+#[case("brackets.in", "c].g", "close_outside", Some("false"))]
+#[case("brackets.in", "a.g", "close_outside", None)]
+#[case("brackets.in", "ab[e/]cd.i", "slash_inside", Some("true"))]
+#[case("brackets.in", "ab/cd.i", "slash_inside", None)]
+#[case("brackets.in", "abecd.i", "slash_inside", None)]
+#[case("brackets.in", "ab[/c", "slash_half_open", Some("true"))]
+#[case("brackets.in", "ab/c", "slash_half_open", None)]
+#[case("braces.in", "test.py", "choice", Some("true"))]
+#[case("braces.in", "test.js", "choice", Some("true"))]
+#[case("braces.in", "test.html", "choice", Some("true"))]
+#[case("braces.in", "test.pyc", "choice", None)]
+#[case("braces.in", "{single}.b", "choice", Some("single"))]
+#[case("braces.in", "single.b", "choice", None)]
+#[case("braces.in", ".b", "choice", None)]
+#[case("braces.in", "{}.c", "empty", Some("all"))]
+#[case("braces.in", ".c", "empty", None)]
+#[case("braces.in", "ab.d", "empty", Some("word"))]
+#[case("braces.in", "ac.d", "empty", Some("word"))]
+#[case("braces.in", "a.d", "empty", Some("word"))]
+#[case("braces.in", "ad.d", "empty", None)]
+#[case("braces.in", "a.e", "empty", Some("words"))]
+#[case("braces.in", "ab.e", "empty", Some("words"))]
+#[case("braces.in", "ac.e", "empty", Some("words"))]
+#[case("braces.in", "ad.e", "empty", None)]
+#[case("braces.in", "{.f", "closing", Some("false"))]
+#[case("braces.in", ".f", "closing", None)]
+#[case("braces.in", "word.g", "nested", Some("true"))]
+#[case("braces.in", "{also}.g", "nested", Some("true"))]
+#[case("braces.in", "this.g", "nested", Some("true"))]
+#[case("braces.in", "also.g", "nested", None)]
+#[case("braces.in", "a.k", "nested_start", Some("true"))]
+#[case("braces.in", "b.k", "nested_start", Some("true"))]
+#[case("braces.in", "c.k", "nested_start", Some("true"))]
+#[case("braces.in", "d.k", "nested_start", None)]
+#[case("braces.in", "a.l", "nested_end", Some("true"))]
+#[case("braces.in", "b.l", "nested_end", Some("true"))]
+#[case("braces.in", "c.l", "nested_end", Some("true"))]
+#[case("braces.in", "d.l", "nested_end", None)]
+#[case("braces.in", "{},b}.h", "closing", Some("inside"))]
+#[case("braces.in", "b.h", "closing", None)]
+#[case("braces.in", "{{,b,c{d}.i", "unmatched", Some("true"))]
+#[case("braces.in", "b.i", "unmatched", None)]
+#[case("braces.in", "c{d.i", "unmatched", None)]
+#[case("braces.in", "a,b.txt", "comma", Some("yes"))]
+#[case("braces.in", "cd.txt", "comma", Some("yes"))]
+#[case("braces.in", "a.txt", "comma", None)]
+#[case("braces.in", "e.txt", "closing", Some("yes"))]
+#[case("braces.in", "}.txt", "closing", Some("yes"))]
+#[case("braces.in", "f.txt", "closing", Some("yes"))]
+#[case("braces.in", "g.txt", "backslash", Some("yes"))]
+#[case("braces.in", "\\.txt", "backslash", Some("yes"))]
+#[case("braces.in", "i.txt", "backslash", Some("yes"))]
+#[case("braces.in", "some.j", "patterns", Some("nested"))]
+#[case("braces.in", "abe.j", "patterns", Some("nested"))]
+#[case("braces.in", "abf.j", "patterns", Some("nested"))]
+#[case("braces.in", "abg.j", "patterns", None)]
+#[case("braces.in", "ace.j", "patterns", Some("nested"))]
+#[case("braces.in", "acf.j", "patterns", Some("nested"))]
+#[case("braces.in", "acg.j", "patterns", None)]
+#[case("braces.in", "abce.j", "patterns", Some("nested"))]
+#[case("braces.in", "abcf.j", "patterns", Some("nested"))]
+#[case("braces.in", "abcg.j", "patterns", None)]
+#[case("braces.in", "ae.j", "patterns", None)]
+#[case("braces.in", ".j", "patterns", None)]
+#[case("braces.in", "1", "number", None)]
+#[case("braces.in", "3", "number", Some("true"))]
+#[case("braces.in", "15", "number", Some("true"))]
+#[case("braces.in", "60", "number", Some("true"))]
+#[case("braces.in", "5a", "number", None)]
+#[case("braces.in", "120", "number", Some("true"))]
+#[case("braces.in", "121", "number", None)]
+#[case("braces.in", "060", "number", None)]
+#[case("braces.in", "{aardvark..antelope}", "words", Some("a"))]
+#[case("braces.in", "aardvark", "words", None)]
+#[case("braces.in", "agreement", "words", None)]
+#[case("braces.in", "antelope", "words", None)]
+#[case("star_star.in", "a/z.c", "key1", Some("value1"))]
+#[case("star_star.in", "amnz.c", "key1", Some("value1"))]
+#[case("star_star.in", "am/nz.c", "key1", Some("value1"))]
+#[case("star_star.in", "b/z.c", "key2", Some("value2"))]
+#[case("star_star.in", "b/mn/z.c", "key2", Some("value2"))]
+#[case("star_star.in", "cm/n/z.c", "key3", Some("value3"))]
+#[case("star_star.in", "d/z.c", "key4", Some("value4"))]
+#[case("star_star.in", "d/mn/z.c", "key4", Some("value4"))]
+#[case("star_star.in", "dz.c", "key4", None)]
+#[trace]
+fn glob_conformance(
+    #[case] file: &str,
+    #[case] path: &str,
+    #[case] key: &str,
+    #[case] expected: Option<&str>,
+) {
+    // Arrange
+    let in_file = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("conformance")
+        .join(file);
+    let content = std::fs::read_to_string(in_file).unwrap();
+
+    // Act
+    let properties = properties_of(&content, path);
+
+    // Assert
+    assert_eq!(properties.get(key).map(String::as_str), expected);
+}